@@ -1,25 +1,69 @@
-pub fn main(input: &str) -> anyhow::Result<()> {
-    let mut floor = 0;
-    let mut first_basement_visit_at_position = None;
-    for (i, c) in input.chars().enumerate() {
+//! Day 1: Not Quite Lisp, following parentheses up and down the floors of a
+//! building.
+
+use anyhow::Context;
+
+use aoc_core::{Answer, Solution};
+
+/// Returns the floor Santa is on after following each instruction.
+pub fn floors(instructions: &str) -> impl Iterator<Item = i64> + '_ {
+    instructions.chars().scan(0, |floor, c| {
         match c {
             '(' => {
-                floor += 1;
+                *floor += 1;
             }
             ')' => {
-                floor -= 1;
+                *floor -= 1;
             }
             _ => {}
         }
-        if floor == -1 && first_basement_visit_at_position == None {
+        Some(*floor)
+    })
+}
+
+/// Finds the floor the instructions lead to, then the first instruction
+/// entering the basement.
+#[derive(Default)]
+pub struct Solver;
+
+impl Solution for Solver {
+    type Input = String;
+
+    fn parse(&self, input: &str) -> anyhow::Result<Self::Input> {
+        Ok(input.to_string())
+    }
+
+    fn part1(&self, instructions: &Self::Input) -> anyhow::Result<Answer> {
+        let floor = floors(instructions).last().unwrap_or(0);
+        Ok(floor.into())
+    }
+
+    fn part2(&self, instructions: &Self::Input) -> anyhow::Result<Answer> {
+        let first_basement_visit_at_position = floors(instructions)
+            .position(|floor| floor == -1)
             // The first character in the instructions has position 1, the second character has position 2, and so on.
-            first_basement_visit_at_position = Some(i + 1);
+            .map(|i| i + 1)
+            .context("Santa never enters the basement")?;
+        Ok(first_basement_visit_at_position.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use aoc_core::solve_part;
+
+    #[test]
+    fn test_examples() {
+        for (instructions, floor) in [("(())", 0), ("(((", 3), ("))(((((", 3), ("())", -1)] {
+            assert_eq!(
+                solve_part(&Solver, instructions, 1).unwrap(),
+                Answer::from(floor)
+            );
         }
+        assert_eq!(solve_part(&Solver, ")())())", 1).unwrap(), Answer::from(-3));
+        assert_eq!(solve_part(&Solver, ")", 2).unwrap(), Answer::from(1));
+        assert_eq!(solve_part(&Solver, "()())", 2).unwrap(), Answer::from(5));
     }
-    println!("Floor: {}", floor);
-    println!(
-        "First basement visit at position: {:?}",
-        first_basement_visit_at_position
-    );
-    Ok(())
 }
@@ -1,22 +1,33 @@
+//! Day 2: I Was Told There Would Be No Math, ordering wrapping paper and
+//! ribbon for gift boxes.
+
+use std::fmt;
 use std::str::FromStr;
 
-use anyhow::anyhow;
+use aoc_core::parse::{self, Line, ParseError};
+use aoc_core::{Answer, Solution};
 
-#[derive(Debug)]
-struct GiftBox {
-    length: u64,
-    width: u64,
-    height: u64,
+/// A gift box, given like `2x3x4`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GiftBox {
+    /// The length in feet.
+    pub length: u64,
+    /// The width in feet.
+    pub width: u64,
+    /// The height in feet.
+    pub height: u64,
 }
 
 impl GiftBox {
-    fn surface_area(&self) -> u64 {
+    /// The total area of the six sides.
+    pub fn surface_area(&self) -> u64 {
         (2 * self.length * self.width)
             + (2 * self.width * self.height)
             + (2 * self.height * self.length)
     }
 
-    fn required_wrapping_paper(&self) -> u64 {
+    /// The surface area plus the area of the smallest side as slack.
+    pub fn required_wrapping_paper(&self) -> u64 {
         let side_areas = [
             self.length * self.width,
             self.width * self.height,
@@ -26,11 +37,13 @@ impl GiftBox {
         self.surface_area() + smallest_side_area
     }
 
-    fn volume(&self) -> u64 {
+    /// The cubic feet of space the box takes.
+    pub fn volume(&self) -> u64 {
         self.length * self.width * self.height
     }
 
-    fn required_ribbon(&self) -> u64 {
+    /// The smallest perimeter to wrap plus the volume for the bow.
+    pub fn required_ribbon(&self) -> u64 {
         let perimeters = [
             2 * self.length + 2 * self.width,
             2 * self.width + 2 * self.height,
@@ -41,32 +54,79 @@ impl GiftBox {
     }
 }
 
+/// Parses a gift box from a line of the list.
+pub fn parse_gift_box(line: Line) -> Result<GiftBox, ParseError> {
+    let (length, rest) = line.split_once(line.text, "x", "`<length>x<width>x<height>`")?;
+    let (width, height) = line.split_once(rest, "x", "`<width>x<height>`")?;
+    Ok(GiftBox {
+        length: line.parse(length, "a length")?,
+        width: line.parse(width, "a width")?,
+        height: line.parse(height, "a height")?,
+    })
+}
+
 impl FromStr for GiftBox {
-    type Err = anyhow::Error;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        if let [length, width, height] = s.split('x').collect::<Vec<_>>()[..] {
-            let length = length.parse()?;
-            let width = width.parse()?;
-            let height = height.parse()?;
-            return Ok(GiftBox {
-                length,
-                width,
-                height,
-            });
-        }
-        Err(anyhow!("Invalid input: {}", s))
+        parse_gift_box(Line::new(1, s))
+    }
+}
+
+impl fmt::Display for GiftBox {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}x{}x{}", self.length, self.width, self.height)
     }
 }
 
-pub fn main(input: &str) -> anyhow::Result<()> {
-    let giftboxes = input
-        .lines()
-        .map(GiftBox::from_str)
-        .collect::<anyhow::Result<Vec<_>>>()?;
-    let wrapping_paper: u64 = giftboxes.iter().map(GiftBox::required_wrapping_paper).sum();
-    let ribbon: u64 = giftboxes.iter().map(GiftBox::required_ribbon).sum();
-    println!("Square feet of wrapping paper: {}", wrapping_paper);
-    println!("Feet of ribbon: {}", ribbon);
-    Ok(())
+/// Totals the wrapping paper, then the ribbon, the elves should order.
+#[derive(Default)]
+pub struct Solver;
+
+impl Solution for Solver {
+    type Input = Vec<GiftBox>;
+
+    fn parse(&self, input: &str) -> anyhow::Result<Self::Input> {
+        Ok(parse::parse_lines(input, parse_gift_box)?)
+    }
+
+    fn part1(&self, giftboxes: &Self::Input) -> anyhow::Result<Answer> {
+        let wrapping_paper: u64 = giftboxes.iter().map(GiftBox::required_wrapping_paper).sum();
+        Ok(wrapping_paper.into())
+    }
+
+    fn part2(&self, giftboxes: &Self::Input) -> anyhow::Result<Answer> {
+        let ribbon: u64 = giftboxes.iter().map(GiftBox::required_ribbon).sum();
+        Ok(ribbon.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use proptest::prelude::*;
+
+    use aoc_core::solve_part;
+
+    #[test]
+    fn test_examples() {
+        assert_eq!(solve_part(&Solver, "2x3x4", 1).unwrap(), Answer::from(58));
+        assert_eq!(solve_part(&Solver, "1x1x10", 1).unwrap(), Answer::from(43));
+        assert_eq!(solve_part(&Solver, "2x3x4", 2).unwrap(), Answer::from(34));
+        assert_eq!(solve_part(&Solver, "1x1x10", 2).unwrap(), Answer::from(14));
+    }
+
+    proptest! {
+        #[test]
+        fn test_gift_box_round_trip(length: u64, width: u64, height: u64) {
+            let gift_box = GiftBox { length, width, height };
+            prop_assert_eq!(gift_box.to_string().parse::<GiftBox>().ok(), Some(gift_box));
+        }
+
+        #[test]
+        fn test_parse_gift_box_never_panics(line in "\\PC*") {
+            let _ = line.parse::<GiftBox>();
+        }
+    }
 }
@@ -1,28 +1,26 @@
+//! Day 3: Perfectly Spherical Houses in a Vacuum, delivering presents on an
+//! infinite grid of houses.
+
 use std::collections::HashSet;
 
-type Position = (i64, i64);
+use aoc_core::geometry::{Direction, Point};
+use aoc_core::render::{self, Image, Renderer};
+use aoc_core::{Answer, Solution};
 
-struct Santa {
-    visited_houses: HashSet<Position>,
-    position: Position,
+/// A Santa, or Robo-Santa, and the houses it delivered presents to.
+pub struct Santa {
+    /// The houses it delivered at least a present to.
+    pub visited_houses: HashSet<Point>,
+    /// The house it is at.
+    pub position: Point,
 }
 
 impl Santa {
-    fn move_to(&mut self, instruction: char) {
-        match instruction {
-            '^' => {
-                self.position = (self.position.0, self.position.1 + 1);
-            }
-            'v' => {
-                self.position = (self.position.0, self.position.1 - 1);
-            }
-            '>' => {
-                self.position = (self.position.0 + 1, self.position.1);
-            }
-            '<' => {
-                self.position = (self.position.0 - 1, self.position.1);
-            }
-            _ => {}
+    /// Moves one house north, south, east or west for `^`, `v`, `>` or `<`,
+    /// ignoring other characters.
+    pub fn move_to(&mut self, instruction: char) {
+        if let Some(direction) = Direction::from_arrow(instruction) {
+            self.position += direction;
         }
 
         self.visited_houses.insert(self.position);
@@ -32,54 +30,99 @@ impl Santa {
 impl Default for Santa {
     fn default() -> Self {
         Santa {
-            visited_houses: HashSet::from([(0, 0)]),
-            position: (0, 0),
+            visited_houses: HashSet::from([Point::ORIGIN]),
+            position: Point::ORIGIN,
         }
     }
 }
 
-fn part1(input: &str) -> anyhow::Result<()> {
-    let mut santa = Santa::default();
-
-    for c in input.chars() {
-        santa.move_to(c);
+/// Delivers presents with `count` Santas taking turns following the
+/// instructions.
+pub fn deliver(instructions: &str, count: usize) -> Vec<Santa> {
+    let mut santas: Vec<Santa> = (0..count).map(|_| Santa::default()).collect();
+    for (i, c) in instructions.chars().enumerate() {
+        santas[i % count].move_to(c);
     }
+    santas
+}
 
-    println!(
-        "[Part 1]: Houses that received at least one present: {}",
-        santa.visited_houses.len()
-    );
-
-    Ok(())
+/// Draws the houses visited by `santas`, with one color per combination of
+/// Santas visiting a house as given by `palette`, which starts with the color
+/// of houses no Santa visited.
+fn image(santas: &[Santa], palette: &[render::Rgb]) -> Image {
+    let houses = || santas.iter().flat_map(|santa| &santa.visited_houses);
+    let min_x = houses().map(|house| house.x).min().unwrap_or(0);
+    let max_x = houses().map(|house| house.x).max().unwrap_or(0);
+    let min_y = houses().map(|house| house.y).min().unwrap_or(0);
+    let max_y = houses().map(|house| house.y).max().unwrap_or(0);
+    let width = (max_x - min_x + 1) as usize;
+    let height = (max_y - min_y + 1) as usize;
+    Image::from_fn(width, height, palette, |x, y| {
+        let house = Point::new(min_x + x as i64, min_y + y as i64);
+        santas
+            .iter()
+            .enumerate()
+            .filter(|(_, santa)| santa.visited_houses.contains(&house))
+            .map(|(i, _)| 1 << i)
+            .sum()
+    })
 }
 
-fn part2(input: &str) -> anyhow::Result<()> {
-    let mut santa = Santa::default();
-    let mut robo_santa = Santa::default();
+/// Counts the houses getting presents from Santa alone, then from Santa and
+/// Robo-Santa taking turns.
+#[derive(Default)]
+pub struct Solver;
 
-    for (i, c) in input.chars().enumerate() {
-        if i % 2 == 0 {
-            santa.move_to(c);
-        } else {
-            robo_santa.move_to(c);
-        }
+impl Solution for Solver {
+    type Input = String;
+
+    fn parse(&self, input: &str) -> anyhow::Result<Self::Input> {
+        Ok(input.to_string())
     }
 
-    let visited_houses = santa
-        .visited_houses
-        .union(&robo_santa.visited_houses)
-        .count();
-    println!(
-        "[Part 2]: Houses that received at least one present: {}",
-        visited_houses
-    );
+    fn part1(&self, input: &Self::Input) -> anyhow::Result<Answer> {
+        let santas = deliver(input, 1);
+        Ok(santas[0].visited_houses.len().into())
+    }
 
-    Ok(())
+    fn part2(&self, input: &Self::Input) -> anyhow::Result<Answer> {
+        let santas = deliver(input, 2);
+        let visited_houses = santas[0]
+            .visited_houses
+            .union(&santas[1].visited_houses)
+            .count();
+        Ok(visited_houses.into())
+    }
+
+    fn render(&self, input: &Self::Input, renderer: &mut Renderer) -> anyhow::Result<()> {
+        let santas = deliver(input, 1);
+        renderer.png("part1", &image(&santas, &[render::BLACK, render::RED]))?;
+
+        let santas = deliver(input, 2);
+        let palette = [render::BLACK, render::RED, render::GREEN, render::YELLOW];
+        renderer.png("part2", &image(&santas, &palette))
+    }
 }
 
-pub fn main(input: &str) -> anyhow::Result<()> {
-    part1(input)?;
-    part2(input)?;
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use aoc_core::solve_part;
 
-    Ok(())
+    #[test]
+    fn test_examples() {
+        assert_eq!(solve_part(&Solver, ">", 1).unwrap(), Answer::from(2));
+        assert_eq!(solve_part(&Solver, "^>v<", 1).unwrap(), Answer::from(4));
+        assert_eq!(
+            solve_part(&Solver, "^v^v^v^v^v", 1).unwrap(),
+            Answer::from(2)
+        );
+        assert_eq!(solve_part(&Solver, "^v", 2).unwrap(), Answer::from(3));
+        assert_eq!(solve_part(&Solver, "^>v<", 2).unwrap(), Answer::from(3));
+        assert_eq!(
+            solve_part(&Solver, "^v^v^v^v^v", 2).unwrap(),
+            Answer::from(11)
+        );
+    }
 }
@@ -1,43 +1,56 @@
-use std::fmt::Write;
+//! Day 4: The Ideal Stocking Stuffer, mining AdventCoins with MD5 hashes.
 
-use md5::{Digest, Md5};
+use anyhow::Context;
 
-fn to_md5_hex(s: &str) -> String {
-    let mut hasher = Md5::new();
-    hasher.update(s.as_bytes());
-    let result = hasher.finalize();
+use aoc_core::hash::{leading_zeros, Miner};
+use aoc_core::{Answer, Solution};
 
-    let mut hash = String::new();
-    for byte in result {
-        write!(&mut hash, "{:02x}", byte).unwrap();
-    }
-    hash
+/// Finds the lowest positive number which, appended to `secret_key`, gives
+/// an MD5 hash starting with `difficulty` zeroes.
+pub fn find_answer(secret_key: &str, difficulty: u32) -> Option<u64> {
+    Miner::new(secret_key)
+        .mine(1, |hash| leading_zeros(hash) >= difficulty)
+        .next()
+        .map(|(nonce, _)| nonce)
 }
 
-fn count_leading_zeros(s: &str) -> usize {
-    s.chars().take_while(|&c| c == '0').count()
-}
+/// Mines the lowest number whose hash starts with five zeroes, then six.
+#[derive(Default)]
+pub struct Solver;
 
-fn is_valid_answer(answer: &str, difficulty: usize) -> bool {
-    let hash = to_md5_hex(answer);
-    count_leading_zeros(&hash) >= difficulty
-}
+impl Solution for Solver {
+    type Input = String;
 
-fn find_answer(secret_key: &str, difficulty: usize) -> Option<u64> {
-    use rayon::prelude::*;
+    fn parse(&self, input: &str) -> anyhow::Result<Self::Input> {
+        Ok(input.trim().to_string())
+    }
 
-    (1..u64::MAX)
-        .into_par_iter()
-        .map(|i| (i, format!("{}{}", secret_key, i)))
-        .find_first(|(_, candidate)| is_valid_answer(candidate.as_str(), difficulty))
-        .map(|result| result.0)
-}
+    fn part1(&self, secret_key: &Self::Input) -> anyhow::Result<Answer> {
+        let answer = find_answer(secret_key, 5).context("no answer found")?;
+        Ok(answer.into())
+    }
 
-pub fn main(input: &str) -> anyhow::Result<()> {
-    let (answer_part1, answer_part2) =
-        rayon::join(|| find_answer(input, 5), || find_answer(input, 6));
+    fn part2(&self, secret_key: &Self::Input) -> anyhow::Result<Answer> {
+        let answer = find_answer(secret_key, 6).context("no answer found")?;
+        Ok(answer.into())
+    }
+}
 
-    println!("[Part 1] Answer: {:?}", answer_part1);
-    println!("[Part 2] Answer: {:?}", answer_part2);
-    Ok(())
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use aoc_core::solve_part;
+
+    #[test]
+    fn test_examples() {
+        assert_eq!(
+            solve_part(&Solver, "abcdef", 1).unwrap(),
+            Answer::from(609043)
+        );
+        assert_eq!(
+            solve_part(&Solver, "pqrstuv", 1).unwrap(),
+            Answer::from(1048970)
+        );
+    }
 }
@@ -1,3 +1,8 @@
+//! Day 5: Doesn't He Have Intern-Elves For This?, telling naughty strings
+//! from nice ones.
+
+use aoc_core::{Answer, Solution};
+
 fn is_vowel(c: char) -> bool {
     "aeiou".contains(c)
 }
@@ -45,30 +50,49 @@ fn contains_sandwiched_character(s: &str) -> bool {
     false
 }
 
-fn is_nice_part1(s: &str) -> bool {
+/// Whether `s` has three vowels and a repeated letter but none of `ab`, `cd`,
+/// `pq` or `xy`.
+pub fn is_nice_part1(s: &str) -> bool {
     let bad_strings = ["ab", "cd", "pq", "xy"];
     let contains_three_vowels = s.chars().map(is_vowel).filter(|&b| b).count() >= 3;
     let contains_bad_string = bad_strings.iter().any(|bad_string| s.contains(bad_string));
     contains_three_vowels && contains_repetition(s) && !contains_bad_string
 }
 
-fn is_nice_part2(s: &str) -> bool {
+/// Whether `s` has a pair of letters twice and a letter repeated with one
+/// letter between them.
+pub fn is_nice_part2(s: &str) -> bool {
     contains_nonoverlapping_pair_twice(s) && contains_sandwiched_character(s)
 }
 
-pub fn main(input: &str) -> anyhow::Result<()> {
-    let nice_strings_part1 = input.lines().map(is_nice_part1).filter(|&b| b).count();
-    let nice_strings_part2 = input.lines().map(is_nice_part2).filter(|&b| b).count();
-    println!("[Part 1]: Number of nice strings: {}", nice_strings_part1);
-    println!("[Part 2]: Number of nice strings: {}", nice_strings_part2);
+/// Counts the nice strings by the first rules, then by the better ones.
+#[derive(Default)]
+pub struct Solver;
+
+impl Solution for Solver {
+    type Input = Vec<String>;
+
+    fn parse(&self, input: &str) -> anyhow::Result<Self::Input> {
+        Ok(input.lines().map(|line| line.to_string()).collect())
+    }
+
+    fn part1(&self, strings: &Self::Input) -> anyhow::Result<Answer> {
+        let nice_strings = strings.iter().filter(|s| is_nice_part1(s)).count();
+        Ok(nice_strings.into())
+    }
 
-    Ok(())
+    fn part2(&self, strings: &Self::Input) -> anyhow::Result<Answer> {
+        let nice_strings = strings.iter().filter(|s| is_nice_part2(s)).count();
+        Ok(nice_strings.into())
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    use aoc_core::solve_part;
+
     #[test]
     fn test_contains_nonoverlapping_pair_twice() {
         assert!(contains_nonoverlapping_pair_twice("xyxy"));
@@ -83,4 +107,12 @@ mod tests {
         assert!(contains_sandwiched_character("aaa"));
         assert!(!contains_sandwiched_character("abc"));
     }
+
+    #[test]
+    fn test_examples() {
+        let strings = "ugknbfddgicrmopn\naaa\njchzalrnumimnmhp\nhaegwjzuvuyypxyu\ndvszwmarrgswjxmb";
+        assert_eq!(solve_part(&Solver, strings, 1).unwrap(), Answer::from(2));
+        let strings = "qjhvhtzxzqqjkmpb\nxxyxx\nuurcxstgmygtbstg\nieodomkazucvgmuy";
+        assert_eq!(solve_part(&Solver, strings, 2).unwrap(), Answer::from(2));
+    }
 }
@@ -1,149 +1,352 @@
+//! Day 6: Probably a Fire Hazard, switching rectangles of lights in a grid.
+
+use std::fmt;
 use std::str::FromStr;
 
-#[derive(Debug, Clone, Copy)]
-enum Command {
+use aoc_core::grid::Grid;
+use aoc_core::parse::{self, Line, ParseError};
+use aoc_core::render::{self, Image, Renderer};
+use aoc_core::{params, Answer, Param, Solution};
+
+/// What an instruction does to the lights.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Command {
+    /// `turn on`, or turn the brightness up by 1.
     TurnOn,
+    /// `turn off`, or turn the brightness down by 1.
     TurnOff,
+    /// `toggle`, or turn the brightness up by 2.
     Toggle,
 }
 
-#[derive(Debug, Clone, Copy)]
-struct Coordinate {
-    x: usize,
-    y: usize,
+impl fmt::Display for Command {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::TurnOn => write!(f, "turn on"),
+            Self::TurnOff => write!(f, "turn off"),
+            Self::Toggle => write!(f, "toggle"),
+        }
+    }
+}
+
+/// A light, given like `0,999`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Coordinate {
+    /// The column, from 0 on the left.
+    pub x: usize,
+    /// The row, from 0 at the top.
+    pub y: usize,
+}
+
+/// Parses the coordinate `part`, a slice of `line`.
+fn parse_coordinate(line: &Line, part: &str) -> Result<Coordinate, ParseError> {
+    let (x, y) = line.split_once(part, ",", "a coordinate like `0,999`")?;
+    Ok(Coordinate {
+        x: line.parse(x, "an x coordinate")?,
+        y: line.parse(y, "a y coordinate")?,
+    })
 }
 
 impl FromStr for Coordinate {
-    type Err = anyhow::Error;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        match s.split_once(',') {
-            Some((x, y)) => Ok(Self {
-                x: x.parse()?,
-                y: y.parse()?,
-            }),
-            None => Err(anyhow::anyhow!("invalid coordinate: {}", s)),
-        }
+        parse_coordinate(&Line::new(1, s), s)
     }
 }
 
-#[derive(Debug, Clone)]
-struct Rectangle {
-    bottom_left: Coordinate,
-    top_right: Coordinate,
+impl fmt::Display for Coordinate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{},{}", self.x, self.y)
+    }
+}
+
+/// The lights between two opposite corners, both included, given like
+/// `0,0 through 999,999`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rectangle {
+    /// The corner with the lowest coordinates.
+    pub bottom_left: Coordinate,
+    /// The corner with the highest coordinates.
+    pub top_right: Coordinate,
+}
+
+/// Parses the rectangle `part`, a slice of `line`.
+fn parse_rectangle(line: &Line, part: &str) -> Result<Rectangle, ParseError> {
+    let (bottom_left, top_right) =
+        line.split_once(part, " through ", "`<corner> through <corner>`")?;
+    Ok(Rectangle {
+        bottom_left: parse_coordinate(line, bottom_left)?,
+        top_right: parse_coordinate(line, top_right)?,
+    })
 }
 
 impl FromStr for Rectangle {
-    type Err = anyhow::Error;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        match s.split_once(" through ") {
-            Some((bottom_left, top_right)) => Ok(Self {
-                bottom_left: bottom_left.parse()?,
-                top_right: top_right.parse()?,
-            }),
-            None => Err(anyhow::anyhow!("invalid rectangle: {}", s)),
-        }
+        parse_rectangle(&Line::new(1, s), s)
     }
 }
 
-#[derive(Debug, Clone)]
-struct Instruction {
-    command: Command,
-    rectangle: Rectangle,
+impl fmt::Display for Rectangle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} through {}", self.bottom_left, self.top_right)
+    }
+}
+
+/// An instruction, given like `turn on 0,0 through 999,999`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Instruction {
+    /// What happens to the lights.
+    pub command: Command,
+    /// Which lights it happens to.
+    pub rectangle: Rectangle,
+}
+
+/// Parses an instruction from a line of the instructions.
+pub fn parse_instruction(line: Line) -> Result<Instruction, ParseError> {
+    let commands = [
+        ("turn on ", Command::TurnOn),
+        ("turn off ", Command::TurnOff),
+        ("toggle ", Command::Toggle),
+    ];
+    let (command, rectangle) = commands
+        .into_iter()
+        .find_map(|(prefix, command)| Some((command, line.text.strip_prefix(prefix)?)))
+        .ok_or_else(|| line.mismatch("`turn on`, `turn off` or `toggle`"))?;
+    Ok(Instruction {
+        command,
+        rectangle: parse_rectangle(&line, rectangle)?,
+    })
 }
 
 impl FromStr for Instruction {
-    type Err = anyhow::Error;
+    type Err = ParseError;
 
     fn from_str(line: &str) -> Result<Self, Self::Err> {
-        if let Some(remaining) = line.strip_prefix("turn on ") {
-            Ok(Self {
-                command: Command::TurnOn,
-                rectangle: remaining.parse()?,
-            })
-        } else if let Some(remaining) = line.strip_prefix("turn off ") {
-            Ok(Self {
-                command: Command::TurnOff,
-                rectangle: remaining.parse()?,
-            })
-        } else if let Some(remaining) = line.strip_prefix("toggle ") {
-            Ok(Self {
-                command: Command::Toggle,
-                rectangle: remaining.parse()?,
-            })
-        } else {
-            Err(anyhow::anyhow!("Invalid instruction: {}", line))
-        }
+        parse_instruction(Line::new(1, line))
     }
 }
 
-fn apply_instructions<T, F>(grid: &mut [[T; 1000]; 1000], instructions: &[Instruction], eval: F)
+impl fmt::Display for Instruction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.command, self.rectangle)
+    }
+}
+
+fn apply_instructions<T, F>(
+    grid: &mut Grid<T>,
+    instructions: &[Instruction],
+    eval: F,
+) -> anyhow::Result<()>
 where
     F: Fn(&mut T, &Instruction),
 {
     for instruction in instructions {
-        for row in grid
-            .iter_mut()
-            .take(instruction.rectangle.top_right.y + 1)
-            .skip(instruction.rectangle.bottom_left.y)
-        {
-            for cell in row
-                .iter_mut()
-                .take(instruction.rectangle.top_right.x + 1)
-                .skip(instruction.rectangle.bottom_left.x)
-            {
-                eval(cell, instruction)
-            }
+        let Rectangle {
+            bottom_left,
+            top_right,
+        } = instruction.rectangle;
+        if top_right.x >= grid.width() || top_right.y >= grid.height() {
+            anyhow::bail!(
+                "`{}` is outside of the {}x{} grid",
+                instruction,
+                grid.width(),
+                grid.height()
+            );
         }
+        let mut lights = grid.view_mut(bottom_left.x..=top_right.x, bottom_left.y..=top_right.y);
+        for cell in lights.cells_mut() {
+            eval(cell, instruction)
+        }
+    }
+    Ok(())
+}
+
+/// Counts the lit lights after following the instructions, then totals the
+/// brightness of the lights.
+pub struct Solver {
+    /// The width and height of the grid of lights.
+    size: usize,
+}
+
+impl Default for Solver {
+    fn default() -> Self {
+        Self { size: 1000 }
     }
 }
 
-fn part1(instructions: &[Instruction]) {
-    let mut grid = [[false; 1000]; 1000];
+impl Solver {
+    /// Returns which lights are lit after following the instructions as
+    /// understood at first, failing if one is outside of the grid.
+    pub fn lights(&self, instructions: &[Instruction]) -> anyhow::Result<Grid<bool>> {
+        let mut grid = Grid::new(self.size, self.size, false);
+
+        apply_instructions(
+            &mut grid,
+            instructions,
+            |cell, instruction| match instruction.command {
+                Command::TurnOn => *cell = true,
+                Command::TurnOff => *cell = false,
+                Command::Toggle => *cell = !*cell,
+            },
+        )?;
 
-    apply_instructions(
-        &mut grid,
-        instructions,
-        |cell, instruction| match instruction.command {
-            Command::TurnOn => *cell = true,
-            Command::TurnOff => *cell = false,
-            Command::Toggle => *cell = !*cell,
-        },
-    );
+        Ok(grid)
+    }
+
+    /// Returns the brightness of each light after following the instructions
+    /// in Ancient Nordic Elvish, failing if one is outside of the grid.
+    pub fn brightness(&self, instructions: &[Instruction]) -> anyhow::Result<Grid<u64>> {
+        let mut grid = Grid::new(self.size, self.size, 0u64);
+
+        apply_instructions(
+            &mut grid,
+            instructions,
+            |cell, instruction| match instruction.command {
+                Command::TurnOn => *cell += 1,
+                Command::TurnOff => *cell = cell.saturating_sub(1),
+                Command::Toggle => *cell += 2,
+            },
+        )?;
 
-    let lit_lights: usize = grid
-        .iter()
-        .map(|row| row.iter().filter(|&b| *b).count())
-        .sum();
-    println!("{} lights are lit", lit_lights);
+        Ok(grid)
+    }
 }
 
-fn part2(instructions: &[Instruction]) {
-    let mut grid = [[0u64; 1000]; 1000];
+impl Solution for Solver {
+    type Input = Vec<Instruction>;
 
-    apply_instructions(
-        &mut grid,
-        instructions,
-        |cell, instruction| match instruction.command {
-            Command::TurnOn => *cell += 1,
-            Command::TurnOff => *cell = cell.saturating_sub(1),
-            Command::Toggle => *cell += 2,
-        },
-    );
+    fn parse(&self, input: &str) -> anyhow::Result<Self::Input> {
+        Ok(parse::parse_lines(input, parse_instruction)?)
+    }
+
+    fn part1(&self, instructions: &Self::Input) -> anyhow::Result<Answer> {
+        let grid = self.lights(instructions)?;
+        let lit_lights = grid.cells().filter(|&b| *b).count();
+        Ok(lit_lights.into())
+    }
+
+    fn part2(&self, instructions: &Self::Input) -> anyhow::Result<Answer> {
+        let grid = self.brightness(instructions)?;
+        let brightness: u64 = grid.cells().sum();
+        Ok(brightness.into())
+    }
 
-    let brightness: u64 = grid.iter().map(|row| row.iter().sum::<u64>()).sum();
-    println!("total brightness {}", brightness);
+    fn render(&self, instructions: &Self::Input, renderer: &mut Renderer) -> anyhow::Result<()> {
+        let grid = self.lights(instructions)?;
+        let palette = [render::BLACK, render::YELLOW];
+        let image = Image::from_fn(self.size, self.size, &palette, |x, y| {
+            u8::from(grid[(x, y)])
+        });
+        renderer.png("part1", &image)?;
+
+        let grid = self.brightness(instructions)?;
+        let max = grid.cells().copied().max().unwrap_or(0).max(1);
+        let palette = render::gradient(render::YELLOW);
+        let image = Image::from_fn(self.size, self.size, &palette, |x, y| {
+            (grid[(x, y)] * 255 / max) as u8
+        });
+        renderer.png("part2", &image)
+    }
+
+    fn params(&self) -> Vec<Param> {
+        vec![Param::new(
+            "size",
+            "the width and height of the grid of lights",
+            self.size,
+        )]
+    }
+
+    fn set_param(&mut self, name: &str, value: &str) -> anyhow::Result<()> {
+        match name {
+            "size" => self.size = params::parse_value(name, value)?,
+            _ => anyhow::bail!("unknown parameter {}", name),
+        }
+        Ok(())
+    }
 }
 
-pub fn main(input: &str) -> anyhow::Result<()> {
-    let instructions: Vec<Instruction> = input
-        .lines()
-        .map(|line| line.parse())
-        .collect::<Result<Vec<_>, _>>()?;
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-    part1(&instructions);
-    part2(&instructions);
+    use proptest::prelude::*;
 
-    Ok(())
+    use aoc_core::solve_part;
+
+    #[test]
+    fn test_examples() {
+        let solver = Solver::default();
+        let instructions = "turn on 0,0 through 999,999
+toggle 0,0 through 999,0
+turn off 499,499 through 500,500";
+        assert_eq!(
+            solve_part(&solver, instructions, 1).unwrap(),
+            Answer::from(998996)
+        );
+        let instructions = "turn on 0,0 through 0,0\ntoggle 0,0 through 999,999";
+        assert_eq!(
+            solve_part(&solver, instructions, 2).unwrap(),
+            Answer::from(2000001)
+        );
+    }
+
+    #[test]
+    fn test_outside_of_grid() {
+        let solver = Solver { size: 10 };
+        let error = solve_part(&solver, "toggle 0,0 through 9,10", 1).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "`toggle 0,0 through 9,10` is outside of the 10x10 grid"
+        );
+    }
+
+    #[test]
+    fn test_parse_instruction() {
+        let error = parse_instruction(Line::new(2, "turn on 0,0 through 999;999")).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "line 2, column 21: expected a coordinate like `0,999`, found \"999;999\""
+        );
+        let error = parse_instruction(Line::new(3, "switch 0,0 through 9,9")).unwrap_err();
+        assert_eq!(error.column, 1);
+    }
+
+    fn coordinate() -> impl Strategy<Value = Coordinate> {
+        any::<(usize, usize)>().prop_map(|(x, y)| Coordinate { x, y })
+    }
+
+    fn instruction() -> impl Strategy<Value = Instruction> {
+        let command = prop_oneof![
+            Just(Command::TurnOn),
+            Just(Command::TurnOff),
+            Just(Command::Toggle)
+        ];
+        (command, coordinate(), coordinate()).prop_map(|(command, bottom_left, top_right)| {
+            Instruction {
+                command,
+                rectangle: Rectangle {
+                    bottom_left,
+                    top_right,
+                },
+            }
+        })
+    }
+
+    proptest! {
+        #[test]
+        fn test_instruction_round_trip(instruction in instruction()) {
+            prop_assert_eq!(
+                instruction.to_string().parse::<Instruction>().ok(),
+                Some(instruction)
+            );
+        }
+
+        #[test]
+        fn test_parse_instruction_never_panics(line in "\\PC*") {
+            let _ = line.parse::<Instruction>();
+        }
+    }
 }
@@ -1,7 +1,16 @@
+//! Day 7: Some Assembly Required, evaluating a circuit of wires and bitwise
+//! logic gates.
+
 use std::collections::HashMap;
 use std::collections::VecDeque;
+use std::fmt;
 use std::str::FromStr;
 
+use anyhow::Context;
+
+use aoc_core::parse::{self, Line, ParseError};
+use aoc_core::{Answer, Solution};
+
 #[derive(Debug)]
 enum Output {
     Value(u16),
@@ -24,10 +33,13 @@ trait Eval {
     fn eval(&self, wires: &HashMap<String, Instruction>, signals: &HashMap<String, u16>) -> Output;
 }
 
+/// Connects a signal to a wire, given like `x AND y -> d`.
 #[derive(Debug, Clone, PartialEq, Eq)]
-struct Instruction {
-    source: Source,
-    output: String,
+pub struct Instruction {
+    /// Where the signal comes from.
+    pub source: Source,
+    /// The name of the wire the signal is provided to.
+    pub output: String,
 }
 
 impl Eval for Instruction {
@@ -36,23 +48,35 @@ impl Eval for Instruction {
     }
 }
 
+/// Parses an instruction from a line of the circuit.
+pub fn parse_instruction(line: Line) -> Result<Instruction, ParseError> {
+    let (source, output) = line.split_once(line.text, " -> ", "`<signal> -> <wire>`")?;
+    Ok(Instruction {
+        source: parse_source(&line, source)?,
+        output: parse_wire(&line, output)?,
+    })
+}
+
 impl FromStr for Instruction {
-    type Err = anyhow::Error;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        match s.split_once(" -> ") {
-            Some((source, output)) => Ok(Instruction {
-                source: source.parse()?,
-                output: output.to_string(),
-            }),
-            None => Err(anyhow::anyhow!("invalid instruction: {}", s)),
-        }
+        parse_instruction(Line::new(1, s))
     }
 }
 
+impl fmt::Display for Instruction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} -> {}", self.source, self.output)
+    }
+}
+
+/// The signal provided to a wire.
 #[derive(Debug, Clone, PartialEq, Eq)]
-enum Source {
+pub enum Source {
+    /// The output of a gate.
     Gate(Gate),
+    /// Another wire, or a specific signal.
     Literal(Literal),
 }
 
@@ -65,21 +89,38 @@ impl Eval for Source {
     }
 }
 
+/// Parses the signal `part`, a slice of `line`.
+fn parse_source(line: &Line, part: &str) -> Result<Source, ParseError> {
+    if part.contains(' ') {
+        Ok(Source::Gate(parse_gate(line, part)?))
+    } else {
+        Ok(Source::Literal(parse_literal(line, part)?))
+    }
+}
+
 impl FromStr for Source {
-    type Err = anyhow::Error;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        if s.contains(' ') {
-            Ok(Self::Gate(s.parse()?))
-        } else {
-            Ok(Self::Literal(s.parse()?))
+        parse_source(&Line::new(1, s), s)
+    }
+}
+
+impl fmt::Display for Source {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Gate(gate) => write!(f, "{}", gate),
+            Self::Literal(literal) => write!(f, "{}", literal),
         }
     }
 }
 
+/// A wire, whose signal is used, or a specific signal.
 #[derive(Debug, Clone, PartialEq, Eq)]
-enum Literal {
+pub enum Literal {
+    /// The wire with the given name.
     Wire(String),
+    /// A specific signal.
     Value(u16),
 }
 
@@ -99,24 +140,53 @@ impl Eval for Literal {
     }
 }
 
+/// Checks that `part`, a slice of `line`, is the name of a wire, made of
+/// lowercase letters.
+fn parse_wire(line: &Line, part: &str) -> Result<String, ParseError> {
+    if part.is_empty() || !part.chars().all(|c| c.is_ascii_lowercase()) {
+        return Err(line.error(part, "a wire"));
+    }
+    Ok(part.to_string())
+}
+
+/// Parses the wire or signal `part`, a slice of `line`.
+fn parse_literal(line: &Line, part: &str) -> Result<Literal, ParseError> {
+    if !part.is_empty() && part.chars().all(|c| c.is_ascii_digit()) {
+        Ok(Literal::Value(line.parse(part, "a 16-bit signal")?))
+    } else {
+        Ok(Literal::Wire(parse_wire(line, part)?))
+    }
+}
+
 impl FromStr for Literal {
-    type Err = anyhow::Error;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        if s.chars().all(char::is_numeric) {
-            Ok(Self::Value(s.parse()?))
-        } else {
-            Ok(Self::Wire(s.to_string()))
+        parse_literal(&Line::new(1, s), s)
+    }
+}
+
+impl fmt::Display for Literal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Wire(name) => write!(f, "{}", name),
+            Self::Value(value) => write!(f, "{}", value),
         }
     }
 }
 
+/// A bitwise logic gate on 16-bit signals.
 #[derive(Debug, Clone, PartialEq, Eq)]
-enum Gate {
+pub enum Gate {
+    /// `NOT x`, the bitwise complement.
     Not(Literal),
+    /// `x AND y`, the bitwise and.
     And(Literal, Literal),
+    /// `x OR y`, the bitwise or.
     Or(Literal, Literal),
+    /// `x LSHIFT n`, shifting left by `n` bits.
     LShift(Literal, Literal),
+    /// `x RSHIFT n`, shifting right by `n` bits.
     RShift(Literal, Literal),
 }
 
@@ -149,31 +219,61 @@ impl Eval for Gate {
     }
 }
 
+/// Parses the gate `part`, a slice of `line`.
+fn parse_gate(line: &Line, part: &str) -> Result<Gate, ParseError> {
+    if let Some(operand) = part.strip_prefix("NOT ") {
+        return Ok(Gate::Not(parse_literal(line, operand)?));
+    }
+    let gates = [
+        (" AND ", Gate::And as fn(Literal, Literal) -> Gate),
+        (" OR ", Gate::Or),
+        (" LSHIFT ", Gate::LShift),
+        (" RSHIFT ", Gate::RShift),
+    ];
+    let (gate, (left, right)) = gates
+        .into_iter()
+        .find_map(|(operator, gate)| Some((gate, part.split_once(operator)?)))
+        .ok_or_else(|| line.error(part, "a gate like `x AND y` or `NOT x`"))?;
+    Ok(gate(
+        parse_literal(line, left)?,
+        parse_literal(line, right)?,
+    ))
+}
+
 impl FromStr for Gate {
-    type Err = anyhow::Error;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        if let Some(remaining) = s.strip_prefix("NOT ") {
-            Ok(Self::Not(remaining.parse()?))
-        } else if let Some((left, right)) = s.split_once(" AND ") {
-            Ok(Self::And(left.parse()?, right.parse()?))
-        } else if let Some((left, right)) = s.split_once(" OR ") {
-            Ok(Self::Or(left.parse()?, right.parse()?))
-        } else if let Some((left, right)) = s.split_once(" LSHIFT ") {
-            Ok(Self::LShift(left.parse()?, right.parse()?))
-        } else if let Some((left, right)) = s.split_once(" RSHIFT ") {
-            Ok(Self::RShift(left.parse()?, right.parse()?))
-        } else {
-            Err(anyhow::anyhow!("invalid gate: {}", s))
+        parse_gate(&Line::new(1, s), s)
+    }
+}
+
+impl fmt::Display for Gate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Not(operand) => write!(f, "NOT {}", operand),
+            Self::And(l, r) => write!(f, "{} AND {}", l, r),
+            Self::Or(l, r) => write!(f, "{} OR {}", l, r),
+            Self::LShift(l, r) => write!(f, "{} LSHIFT {}", l, r),
+            Self::RShift(l, r) => write!(f, "{} RSHIFT {}", l, r),
         }
     }
 }
 
-fn eval(wires: &HashMap<String, Instruction>, signals: &mut HashMap<String, u16>) {
+fn eval(
+    wires: &HashMap<String, Instruction>,
+    signals: &mut HashMap<String, u16>,
+) -> anyhow::Result<()> {
     let mut queue: VecDeque<String> = wires.keys().cloned().collect();
 
     while let Some(name) = queue.pop_front() {
-        let instruction = wires.get(name.as_str()).unwrap();
+        // Wires are evaluated once, which also keeps overridden signals.
+        if signals.contains_key(&name) {
+            continue;
+        }
+        let instruction = wires
+            .get(name.as_str())
+            .with_context(|| format!("no signal is provided to wire {}", name))?;
         let output = instruction.eval(wires, signals);
         match output {
             Output::Value(value) => {
@@ -187,40 +287,130 @@ fn eval(wires: &HashMap<String, Instruction>, signals: &mut HashMap<String, u16>
             }
         }
     }
+    Ok(())
 }
 
-fn print_signals(signals: &HashMap<String, u16>) {
-    let mut pairs: Vec<(String, u16)> = signals
-        .iter()
-        .map(|pair| (pair.0.clone(), *pair.1))
-        .collect();
-    pairs.sort_by(|a, b| a.0.cmp(&b.0));
-    for (name, value) in pairs {
-        println!("{}: {}", name, value);
+/// The instructions of a circuit by the wire they provide a signal to.
+pub type Circuit = HashMap<String, Instruction>;
+
+/// Parses the instructions of a circuit, one per line.
+pub fn parse_circuit(input: &str) -> Result<Circuit, ParseError> {
+    let instructions = parse::parse_lines(input, parse_instruction)?;
+    Ok(instructions
+        .into_iter()
+        .map(|instruction| (instruction.output.clone(), instruction))
+        .collect())
+}
+
+/// Returns the signal of `wire` once the circuit is evaluated, with the
+/// `signals` of some wires overriding their instructions.
+pub fn signal_of_wire(
+    circuit: &Circuit,
+    wire: &str,
+    mut signals: HashMap<String, u16>,
+) -> anyhow::Result<u16> {
+    eval(circuit, &mut signals)?;
+    signals
+        .get(wire)
+        .copied()
+        .with_context(|| format!("no signal is provided to wire {}", wire))
+}
+
+/// Evaluates the signal of wire `a`, then again with that signal overriding
+/// wire `b`.
+#[derive(Default)]
+pub struct Solver;
+
+impl Solution for Solver {
+    type Input = Circuit;
+
+    fn parse(&self, input: &str) -> anyhow::Result<Self::Input> {
+        Ok(parse_circuit(input)?)
+    }
+
+    fn part1(&self, circuit: &Self::Input) -> anyhow::Result<Answer> {
+        Ok(signal_of_wire(circuit, "a", HashMap::new())?.into())
+    }
+
+    fn part2(&self, circuit: &Self::Input) -> anyhow::Result<Answer> {
+        let wire_a = signal_of_wire(circuit, "a", HashMap::new())?;
+        let signals = HashMap::from([("b".to_string(), wire_a)]);
+        Ok(signal_of_wire(circuit, "a", signals)?.into())
     }
 }
 
-pub fn main(input: &str) -> anyhow::Result<()> {
-    let wires: HashMap<String, Instruction> = input
-        .lines()
-        .map(|line| line.parse::<Instruction>())
-        .map(|i| i.map(|instruction| (instruction.output.clone(), instruction)))
-        .collect::<Result<_, _>>()?;
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-    let mut signals: HashMap<String, u16> = HashMap::new();
+    use proptest::prelude::*;
 
-    eval(&wires, &mut signals);
+    use aoc_core::solve_part;
 
-    print_signals(&signals);
+    const CIRCUIT: &str = "123 -> x
+456 -> y
+x AND y -> d
+x OR y -> e
+x LSHIFT 2 -> f
+y RSHIFT 2 -> g
+NOT x -> h
+NOT y -> i
+d OR b -> c
+c RSHIFT 2 -> a
+e -> b";
 
-    if let Some(wire_a) = signals.get("a") {
-        println!("[Part 1] Signal provided to wire a: {}", wire_a);
+    #[test]
+    fn test_examples() {
+        // b is 507, then overridden by a.
+        assert_eq!(solve_part(&Solver, CIRCUIT, 1).unwrap(), Answer::from(126));
+        assert_eq!(solve_part(&Solver, CIRCUIT, 2).unwrap(), Answer::from(31));
+    }
 
-        let mut signals = HashMap::from([("b".to_string(), *wire_a)]);
-        eval(&wires, &mut signals);
-        let wire_a = signals.get("a").unwrap();
-        println!("[Part 2] Signal provided to wire a: {}", wire_a);
+    fn literal() -> impl Strategy<Value = Literal> {
+        prop_oneof![
+            "[a-z]{1,3}".prop_map(Literal::Wire),
+            any::<u16>().prop_map(Literal::Value),
+        ]
     }
 
-    Ok(())
+    fn source() -> impl Strategy<Value = Source> {
+        prop_oneof![
+            literal().prop_map(Source::Literal),
+            literal().prop_map(|operand| Source::Gate(Gate::Not(operand))),
+            (literal(), literal()).prop_map(|(l, r)| Source::Gate(Gate::And(l, r))),
+            (literal(), literal()).prop_map(|(l, r)| Source::Gate(Gate::Or(l, r))),
+            (literal(), literal()).prop_map(|(l, r)| Source::Gate(Gate::LShift(l, r))),
+            (literal(), literal()).prop_map(|(l, r)| Source::Gate(Gate::RShift(l, r))),
+        ]
+    }
+
+    proptest! {
+        #[test]
+        fn test_instruction_round_trip(source in source(), output in "[a-z]{1,3}") {
+            let instruction = Instruction { source, output };
+            prop_assert_eq!(
+                instruction.to_string().parse::<Instruction>().ok(),
+                Some(instruction)
+            );
+        }
+
+        #[test]
+        fn test_parse_instruction_never_panics(line in "\\PC*") {
+            let _ = line.parse::<Instruction>();
+        }
+    }
+
+    #[test]
+    fn test_parse_wire() {
+        assert!("x AND y -> d".parse::<Instruction>().is_ok());
+        assert!("x AND y z -> d".parse::<Instruction>().is_err());
+        assert!("123 -> ".parse::<Instruction>().is_err());
+        assert!("123 -> D".parse::<Instruction>().is_err());
+
+        let error = parse_circuit("123 -> x\nx AND 65536 -> y").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "line 2, column 7: expected a 16-bit signal, found \"65536\""
+        );
+    }
 }
@@ -1,45 +1,63 @@
-fn unquote(s: &str) -> String {
-    let mut result = String::new();
-    let mut chars = s
+//! Day 8: Matchsticks, escaping and unescaping string literals.
+
+use aoc_core::parse::{self, Line, ParseError};
+use aoc_core::{Answer, Solution};
+
+/// Returns the characters of the string literal on `line`, which is quoted
+/// and may escape `\\`, `\"` and characters like `\x27`.
+pub fn unquote(line: Line) -> Result<String, ParseError> {
+    let literal = line
+        .text
         .strip_prefix('"')
-        .unwrap()
-        .strip_suffix('"')
-        .unwrap()
-        .chars()
-        .enumerate();
-    while let Some((_, c)) = chars.next() {
-        match c {
-            '\\' => {
-                match chars.next() {
-                    Some((i, 'x')) => {
-                        // add two to `i` to account for:
-                        // - the initial "
-                        // - the 'x' which we want to skip
-                        let offset = i + 2;
-                        let hex_notation = &s[offset..offset + 2];
-                        let ordinal = u8::from_str_radix(hex_notation, 16).unwrap();
-                        result.push(ordinal as char);
-
-                        // skip
-                        chars.next();
-                        chars.next();
-                    }
-                    Some((_, c)) => {
-                        result.push(c);
-                    }
-                    _ => unreachable!("invalid escape sequence"),
-                }
+        .and_then(|text| text.strip_suffix('"'))
+        .ok_or_else(|| line.mismatch("a quoted string"))?;
+    let mut result = String::new();
+    let mut chars = literal.char_indices();
+    while let Some((start, c)) = chars.next() {
+        if c != '\\' {
+            result.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some((_, c @ ('\\' | '"'))) => result.push(c),
+            Some((x, 'x')) => {
+                let escape = literal.get(start..x + 3).unwrap_or(&literal[start..]);
+                let ordinal = literal
+                    .get(x + 1..x + 3)
+                    .filter(|hex| hex.bytes().all(|b| b.is_ascii_hexdigit()))
+                    .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+                    .ok_or_else(|| line.error(escape, "two hexadecimal digits after `\\x`"))?;
+                result.push(ordinal as char);
+                chars.nth(1);
             }
-            c => {
-                assert!(c != '\\');
-                result.push(c);
+            _ => {
+                let escape = literal[start..].get(..2).unwrap_or(&literal[start..]);
+                return Err(line.error(escape, "`\\\\`, `\\\"` or `\\x`"));
             }
         }
     }
-    result
+    Ok(result)
+}
+
+/// A string literal of the list, as written in the code and as the string it
+/// stands for.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Literal {
+    /// The literal as written, quotes and escape sequences included.
+    pub code: String,
+    /// The string it stands for.
+    pub value: String,
 }
 
-fn quote(s: &str) -> String {
+fn parse_literal(line: Line) -> Result<Literal, ParseError> {
+    Ok(Literal {
+        code: line.text.to_string(),
+        value: unquote(line)?,
+    })
+}
+
+/// Returns `s` as a string literal, quoted and with `"` and `\\` escaped.
+pub fn quote(s: &str) -> String {
     let mut result = String::new();
     result.push('"');
     for c in s.chars() {
@@ -62,60 +80,79 @@ fn quote(s: &str) -> String {
     result
 }
 
-pub fn main(input: &str) -> anyhow::Result<()> {
-    let mut original_char_count = 0;
-    let mut unquoted_char_count = 0;
-    let mut quoted_char_count = 0;
-    for line in input.lines() {
-        original_char_count += line.chars().count();
-        unquoted_char_count += unquote(line).chars().count();
-        quoted_char_count += quote(line).chars().count();
-    }
+/// Compares the characters of code of the string literals with those of their
+/// values, then with those of the code escaping them.
+#[derive(Default)]
+pub struct Solver;
 
-    println!(
-        "[Part 1] Answer: {}",
-        original_char_count - unquoted_char_count
-    );
+impl Solution for Solver {
+    type Input = Vec<Literal>;
 
-    println!(
-        "[Part 1] Answer: {}",
-        quoted_char_count - original_char_count
-    );
+    fn parse(&self, input: &str) -> anyhow::Result<Self::Input> {
+        Ok(parse::parse_lines(input, parse_literal)?)
+    }
+
+    fn part1(&self, literals: &Self::Input) -> anyhow::Result<Answer> {
+        let code_char_count: usize = literals.iter().map(|l| l.code.chars().count()).sum();
+        let value_char_count: usize = literals.iter().map(|l| l.value.chars().count()).sum();
+        Ok((code_char_count - value_char_count).into())
+    }
 
-    Ok(())
+    fn part2(&self, literals: &Self::Input) -> anyhow::Result<Answer> {
+        let code_char_count: usize = literals.iter().map(|l| l.code.chars().count()).sum();
+        let quoted_char_count: usize = literals
+            .iter()
+            .map(|l| quote(&l.code).chars().count())
+            .sum();
+        Ok((quoted_char_count - code_char_count).into())
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    use aoc_core::solve_part;
+
+    fn unquoted(literal: &str) -> String {
+        unquote(Line::new(1, literal)).unwrap()
+    }
+
     #[test]
     fn test_unquote() {
-        assert_eq!(unquote("\"\""), "");
-        assert_eq!(unquote("\"\"").len(), 0);
+        assert_eq!(unquoted("\"\""), "");
+        assert_eq!(unquoted("\"\"").len(), 0);
 
-        assert_eq!(unquote("\"abc\""), "abc");
-        assert_eq!(unquote("\"abc\"").len(), 3);
+        assert_eq!(unquoted("\"abc\""), "abc");
+        assert_eq!(unquoted("\"abc\"").len(), 3);
 
-        assert_eq!(unquote("\"aaa\\\"aaa\""), "aaa\"aaa");
-        assert_eq!(unquote("\"aaa\\\"aaa\"").len(), 7);
+        assert_eq!(unquoted("\"aaa\\\"aaa\""), "aaa\"aaa");
+        assert_eq!(unquoted("\"aaa\\\"aaa\"").len(), 7);
 
-        assert_eq!(unquote("\"\\x27\""), "\'");
-        assert_eq!(unquote("\"\\x27\"").len(), 1);
+        assert_eq!(unquoted("\"\\x27\""), "\'");
+        assert_eq!(unquoted("\"\\x27\"").len(), 1);
 
         // custom cases
         assert_eq!(
-            unquote("\"v\\xfb\\\"lgs\\\"kvjfywmut\\x9cr\""),
+            unquoted("\"v\\xfb\\\"lgs\\\"kvjfywmut\\x9cr\""),
             "vû\"lgs\"kvjfywmut\u{9c}r"
         );
         assert_eq!(
-            unquote("\"v\\xfb\\\"lgs\\\"kvjfywmut\\x9cr\"")
+            unquoted("\"v\\xfb\\\"lgs\\\"kvjfywmut\\x9cr\"")
                 .chars()
                 .count(),
             18
         );
     }
 
+    #[test]
+    fn test_unquote_errors() {
+        for (literal, column) in [("abc", 1), ("\"\\q\"", 2), ("\"a\\x4\"", 3), ("\"\\\"", 2)] {
+            let error = unquote(Line::new(1, literal)).unwrap_err();
+            assert_eq!(error.column, column, "{:?}", literal);
+        }
+    }
+
     #[test]
     fn test_quote() {
         assert_eq!(quote("\"\""), "\"\\\"\\\"\"");
@@ -130,4 +167,14 @@ mod tests {
         assert_eq!(quote("\"\\x27\""), "\"\\\"\\\\x27\\\"\"");
         assert_eq!(quote("\"\\x27\"").len(), 11);
     }
+
+    #[test]
+    fn test_examples() {
+        let strings = r#"""
+"abc"
+"aaa\"aaa"
+"\x27""#;
+        assert_eq!(solve_part(&Solver, strings, 1).unwrap(), Answer::from(12));
+        assert_eq!(solve_part(&Solver, strings, 2).unwrap(), Answer::from(19));
+    }
 }
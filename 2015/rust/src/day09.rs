@@ -1,17 +1,33 @@
+//! Day 9: All in a Single Night, finding the shortest and longest routes
+//! visiting every location.
+
 use std::collections::HashMap;
-use std::fmt;
 
-fn parse_distance(line: &str) -> (String, String, usize) {
-    let (cities, distance) = line.split_once(" = ").unwrap();
-    let (a, b) = cities.split_once(" to ").unwrap();
-    (a.to_string(), b.to_string(), distance.parse().unwrap())
+use anyhow::Context;
+
+use aoc_core::parse::{self, Line, ParseError};
+use aoc_core::{Answer, Solution};
+
+fn parse_distance(line: Line) -> Result<(String, String, usize), ParseError> {
+    let (cities, distance) =
+        line.split_once(line.text, " = ", "`<city> to <city> = <distance>`")?;
+    let (a, b) = line.split_once(cities, " to ", "`<city> to <city>`")?;
+    Ok((
+        a.to_string(),
+        b.to_string(),
+        line.parse(distance, "a distance")?,
+    ))
 }
 
+/// A route through some of the locations, in the order they are visited.
 #[derive(Debug, Clone)]
-struct Route {
-    current_location: String,
-    visited_locations: Vec<String>,
-    length: usize,
+pub struct Route {
+    /// The last location visited so far.
+    pub current_location: String,
+    /// Every location visited so far, from the first.
+    pub visited_locations: Vec<String>,
+    /// The sum of the distances between the visited locations.
+    pub length: usize,
 }
 
 impl Route {
@@ -38,31 +54,11 @@ impl Route {
     }
 }
 
-impl fmt::Display for Route {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let mut locations = self.visited_locations.iter().peekable();
-        while let Some(location) = locations.next() {
-            if locations.peek().is_some() {
-                write!(f, "{} -> ", location)?;
-            } else {
-                write!(f, "{}", location)?;
-            }
-        }
-        write!(f, " = {}", self.length)
-    }
-}
-
-pub fn main(input: &str) -> anyhow::Result<()> {
-    let mut graph: HashMap<String, HashMap<String, usize>> = HashMap::new();
-
-    for (city_a, city_b, distance) in input.lines().map(parse_distance) {
-        let neighbors = graph.entry(city_a.clone()).or_default();
-        neighbors.insert(city_b.clone(), distance);
-
-        let neighbors = graph.entry(city_b.clone()).or_default();
-        neighbors.insert(city_a.clone(), distance);
-    }
+/// The distances between the locations, both ways.
+pub type Graph = HashMap<String, HashMap<String, usize>>;
 
+/// Returns every route visiting each location once.
+pub fn find_completed_routes(graph: &Graph) -> Vec<Route> {
     let mut completed_routes: Vec<Route> = vec![];
     let mut routes: Vec<Route> = graph.keys().map(|city| Route::new(city)).collect();
     while let Some(route) = routes.pop() {
@@ -77,24 +73,66 @@ pub fn main(input: &str) -> anyhow::Result<()> {
             }
         }
     }
+    completed_routes
+}
+
+/// Finds the length of the shortest route visiting every location, then of
+/// the longest.
+#[derive(Default)]
+pub struct Solver;
+
+impl Solution for Solver {
+    type Input = Graph;
+
+    fn parse(&self, input: &str) -> anyhow::Result<Self::Input> {
+        let mut graph: Graph = HashMap::new();
+
+        for (city_a, city_b, distance) in parse::parse_lines(input, parse_distance)? {
+            let neighbors = graph.entry(city_a.clone()).or_default();
+            neighbors.insert(city_b.clone(), distance);
+
+            let neighbors = graph.entry(city_b.clone()).or_default();
+            neighbors.insert(city_a.clone(), distance);
+        }
+
+        Ok(graph)
+    }
+
+    fn part1(&self, graph: &Self::Input) -> anyhow::Result<Answer> {
+        let shortest_route = find_completed_routes(graph)
+            .into_iter()
+            .min_by(|r1, r2| r1.length.cmp(&r2.length))
+            .context("no route visits every location")?;
+        Ok(shortest_route.length.into())
+    }
 
-    println!("{} completed routes found:", completed_routes.len());
-    for route in &completed_routes {
-        println!("{route}");
+    fn part2(&self, graph: &Self::Input) -> anyhow::Result<Answer> {
+        let longest_route = find_completed_routes(graph)
+            .into_iter()
+            .max_by(|r1, r2| r1.length.cmp(&r2.length))
+            .context("no route visits every location")?;
+        Ok(longest_route.length.into())
     }
-    println!();
+}
 
-    let shortest_route = completed_routes
-        .iter()
-        .min_by(|r1, r2| r1.length.cmp(&r2.length))
-        .unwrap();
-    println!("Shortest route: {shortest_route}");
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-    let longest_route = completed_routes
-        .iter()
-        .max_by(|r1, r2| r1.length.cmp(&r2.length))
-        .unwrap();
-    println!("Longest route: {longest_route}");
+    use aoc_core::solve_part;
 
-    Ok(())
+    #[test]
+    fn test_examples() {
+        let distances = "London to Dublin = 464
+London to Belfast = 518
+Dublin to Belfast = 141";
+        assert_eq!(
+            solve_part(&Solver, distances, 1).unwrap(),
+            Answer::from(605)
+        );
+        assert_eq!(
+            solve_part(&Solver, distances, 2).unwrap(),
+            Answer::from(982)
+        );
+    }
 }
@@ -1,6 +1,14 @@
-fn look_and_say(s: &str) -> String {
+//! Day 10: Elves Look, Elves Say, playing the look-and-say game.
+
+use aoc_core::{Answer, Solution};
+
+/// Reads `s` aloud, e.g. `111221` as "three ones, two twos, and one one",
+/// giving `312211`.
+pub fn look_and_say(s: &str) -> String {
     let mut chars = s.chars();
-    let mut current_char = chars.next().unwrap();
+    let Some(mut current_char) = chars.next() else {
+        return String::new();
+    };
     let mut current_count = 1;
     let mut result = String::new();
     for c in chars {
@@ -18,7 +26,8 @@ fn look_and_say(s: &str) -> String {
     result
 }
 
-fn play(initial: &str, turns: usize) -> String {
+/// Applies look-and-say to `initial` `turns` times.
+pub fn play(initial: &str, turns: usize) -> String {
     let mut current = initial.to_string();
     for _ in 0..turns {
         current = look_and_say(current.as_str());
@@ -26,20 +35,33 @@ fn play(initial: &str, turns: usize) -> String {
     current
 }
 
-pub fn main(input: &str) -> anyhow::Result<()> {
-    let length = play(input, 40).len();
-    println!("Length after 40 turns: {length}");
+/// Plays look-and-say 40 times, then 50 times, giving the length of the
+/// result.
+#[derive(Default)]
+pub struct Solver;
+
+impl Solution for Solver {
+    type Input = String;
 
-    let length = play(input, 50).len();
-    println!("Length after 50 turns: {length}");
+    fn parse(&self, input: &str) -> anyhow::Result<Self::Input> {
+        Ok(input.trim().to_string())
+    }
+
+    fn part1(&self, initial: &Self::Input) -> anyhow::Result<Answer> {
+        Ok(play(initial, 40).len().into())
+    }
 
-    Ok(())
+    fn part2(&self, initial: &Self::Input) -> anyhow::Result<Answer> {
+        Ok(play(initial, 50).len().into())
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    use aoc_core::solve_part;
+
     #[test]
     fn test_look_and_say() {
         assert_eq!(look_and_say("1"), "11");
@@ -49,4 +71,10 @@ mod tests {
         assert_eq!(look_and_say("1211"), "111221");
         assert_eq!(look_and_say("111221"), "312211");
     }
+
+    #[test]
+    fn test_examples() {
+        assert_eq!(solve_part(&Solver, "1", 1).unwrap(), Answer::from(82350));
+        assert_eq!(solve_part(&Solver, "1", 2).unwrap(), Answer::from(1166642));
+    }
 }
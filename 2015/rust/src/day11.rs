@@ -1,3 +1,8 @@
+//! Day 11: Corporate Policy, finding the next password meeting the security
+//! requirements.
+
+use aoc_core::{Answer, Solution};
+
 fn increment_char(c: char) -> (bool, char) {
     if c == 'z' {
         (true, 'a')
@@ -56,13 +61,17 @@ fn contains_two_nonoverlapping_pairs(s: &str) -> bool {
     locations.len() >= 2
 }
 
-fn meets_security_requirements(password: &str) -> bool {
+/// Whether `password` has an increasing straight of three letters, two
+/// different pairs, and none of `i`, `o` or `l`.
+pub fn meets_security_requirements(password: &str) -> bool {
     includes_increasing_straight(password)
         && !contains_forbidden_chars(password)
         && contains_two_nonoverlapping_pairs(password)
 }
 
-fn next_password(password: &str) -> String {
+/// Increments `password` like a number in base 26 until it meets the security
+/// requirements.
+pub fn next_password(password: &str) -> String {
     let mut password = password.to_string();
     loop {
         password = increment_password(password.as_str());
@@ -73,20 +82,33 @@ fn next_password(password: &str) -> String {
     password
 }
 
-pub fn main(input: &str) -> anyhow::Result<()> {
-    let next = next_password(input);
-    let next2 = next_password(next.as_str());
+/// Finds Santa's next password, then the one after it.
+#[derive(Default)]
+pub struct Solver;
+
+impl Solution for Solver {
+    type Input = String;
 
-    println!("Next password: {next}");
-    println!("Next password: {next2}");
+    fn parse(&self, input: &str) -> anyhow::Result<Self::Input> {
+        Ok(input.trim().to_string())
+    }
+
+    fn part1(&self, password: &Self::Input) -> anyhow::Result<Answer> {
+        Ok(next_password(password).into())
+    }
 
-    Ok(())
+    fn part2(&self, password: &Self::Input) -> anyhow::Result<Answer> {
+        let next = next_password(password);
+        Ok(next_password(next.as_str()).into())
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    use aoc_core::solve_part;
+
     #[test]
     fn test_increment_char() {
         assert_eq!(increment_char('a'), (false, 'b'));
@@ -140,4 +162,20 @@ mod tests {
         assert_eq!(next_password("abcdefgh"), "abcdffaa");
         assert_eq!(next_password("ghijklmn"), "ghjaabcc");
     }
+
+    #[test]
+    fn test_examples() {
+        assert_eq!(
+            solve_part(&Solver, "abcdefgh", 1).unwrap(),
+            Answer::from("abcdffaa")
+        );
+        assert_eq!(
+            solve_part(&Solver, "ghijklmn", 1).unwrap(),
+            Answer::from("ghjaabcc")
+        );
+        assert_eq!(
+            solve_part(&Solver, "abcdefgh", 2).unwrap(),
+            Answer::from("abcdffbb")
+        );
+    }
 }
@@ -1,6 +1,12 @@
+//! Day 12: JSAbacusFramework.io, summing the numbers of a JSON document.
+
 use json::JsonValue;
 
-fn part1(document: &str) -> f64 {
+use aoc_core::parse::{self, Line, ParseError};
+use aoc_core::{Answer, Solution};
+
+/// Sums every number in `document`.
+pub fn sum_of_numbers(document: &JsonValue) -> f64 {
     fn sum(value: &json::JsonValue) -> f64 {
         match value {
             JsonValue::Number(_) => value.as_f64().unwrap(),
@@ -9,10 +15,12 @@ fn part1(document: &str) -> f64 {
             _ => 0.0,
         }
     }
-    sum(&json::parse(document).unwrap())
+    sum(document)
 }
 
-fn part2(document: &str) -> f64 {
+/// Sums the numbers in `document`, ignoring objects with a `"red"` value and
+/// everything in them.
+pub fn sum_of_numbers_without_red(document: &JsonValue) -> f64 {
     fn sum(value: &json::JsonValue) -> f64 {
         match value {
             JsonValue::Number(_) => value.as_f64().unwrap(),
@@ -28,26 +36,88 @@ fn part2(document: &str) -> f64 {
             _ => 0.0,
         }
     }
-    sum(&json::parse(document).unwrap())
+    sum(document)
+}
+
+/// Parses the JSON document, pointing at where it's malformed.
+pub fn parse_document(input: &str) -> Result<JsonValue, ParseError> {
+    json::parse(input).map_err(|error| match error {
+        json::Error::UnexpectedCharacter { ch, line, column } => ParseError {
+            line,
+            column,
+            text: ch.to_string(),
+            expected: "valid JSON".to_string(),
+        },
+        json::Error::UnexpectedEndOfJson => match parse::lines(input).last() {
+            Some(line) => line.error(&line.text[line.text.len()..], "the rest of the document"),
+            None => parse::end_of_input(input, "a JSON document"),
+        },
+        error => {
+            let line = parse::lines(input).next().unwrap_or(Line::new(1, ""));
+            line.mismatch(format!(
+                "valid JSON, but {}",
+                error.to_string().to_lowercase()
+            ))
+        }
+    })
 }
 
-pub fn main(input: &str) -> anyhow::Result<()> {
-    let sum_of_all_numbers = part1(input);
-    println!("Sum of all numbers: {sum_of_all_numbers}");
+/// Sums the numbers of the document, then those outside of the objects with a
+/// `red` property.
+#[derive(Default)]
+pub struct Solver;
 
-    let sum_of_all_numbers_wo_red = part2(input);
-    println!("Sum of all numbers without red: {sum_of_all_numbers_wo_red}");
+impl Solution for Solver {
+    type Input = JsonValue;
 
-    Ok(())
+    fn parse(&self, input: &str) -> anyhow::Result<Self::Input> {
+        Ok(parse_document(input)?)
+    }
+
+    fn part1(&self, document: &Self::Input) -> anyhow::Result<Answer> {
+        let sum_of_all_numbers = sum_of_numbers(document);
+        Ok((sum_of_all_numbers as i64).into())
+    }
+
+    fn part2(&self, document: &Self::Input) -> anyhow::Result<Answer> {
+        let sum_of_all_numbers_wo_red = sum_of_numbers_without_red(document);
+        Ok((sum_of_all_numbers_wo_red as i64).into())
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    use aoc_core::solve_part;
+
     #[test]
-    fn test_part2() {
-        assert_eq!(part2("[1,2,3]"), 6.0);
-        assert_eq!(part2("[1,{\"c\":\"red\",\"b\":2},3]"), 4.0);
+    fn test_sum_of_numbers_without_red() {
+        assert_eq!(
+            sum_of_numbers_without_red(&json::parse("[1,2,3]").unwrap()),
+            6.0
+        );
+        assert_eq!(
+            sum_of_numbers_without_red(&json::parse("[1,{\"c\":\"red\",\"b\":2},3]").unwrap()),
+            4.0
+        );
+    }
+
+    #[test]
+    fn test_parse_document() {
+        let error = parse_document("[1,\n{\"a\":?}]").unwrap_err();
+        assert_eq!((error.line, error.column, error.text.as_str()), (2, 6, "?"));
+        let error = parse_document("[1,2").unwrap_err();
+        assert_eq!((error.line, error.column), (1, 5));
+    }
+
+    #[test]
+    fn test_examples() {
+        for (document, sum) in [("[1,2,3]", 6), (r#"{"a":{"b":4},"c":-1}"#, 3), ("[]", 0)] {
+            assert_eq!(solve_part(&Solver, document, 1).unwrap(), Answer::from(sum));
+        }
+        for (document, sum) in [(r#"[1,{"c":"red","b":2},3]"#, 4), (r#"[1,"red",5]"#, 6)] {
+            assert_eq!(solve_part(&Solver, document, 2).unwrap(), Answer::from(sum));
+        }
     }
 }
@@ -1,40 +1,51 @@
+//! Day 13: Knights of the Dinner Table, seating people around a table to be
+//! as happy as possible.
+
 use std::collections::HashMap;
 
 use itertools::Itertools;
 use lazy_static::lazy_static;
 use regex::Regex;
 
-#[derive(Debug, PartialEq, Eq)]
-struct Preference {
-    person: String,
-    happiness_units: i64,
-    neighbor: String,
+use aoc_core::parse::{self, Line, ParseError};
+use aoc_core::{Answer, Solution};
+
+/// How happy sitting next to `neighbor` makes `person`, given like
+/// `Alice would gain 54 happiness units by sitting next to Bob.`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Preference {
+    /// Who is more or less happy.
+    pub person: String,
+    /// How much happier, or less happy if negative.
+    pub happiness_units: i64,
+    /// Who `person` sits next to.
+    pub neighbor: String,
 }
 
-fn parse_preference(line: &str) -> Preference {
+fn parse_preference(line: Line) -> Result<Preference, ParseError> {
     lazy_static! {
         static ref RE: Regex = Regex::new(
             r"^(\w+) would (gain|lose) (\d+) happiness units by sitting next to (\w+)\.$"
         )
         .unwrap();
     }
-    let captures = RE.captures(line).unwrap();
+    let captures = RE.captures(line.text).ok_or_else(|| {
+        line.mismatch("`<person> would gain|lose <n> happiness units by sitting next to <person>.`")
+    })?;
 
-    let person = captures.get(1).unwrap().as_str().to_string();
-    let sign = captures.get(2).unwrap().as_str();
-    let happiness_units: i64 = captures.get(3).unwrap().as_str().parse().unwrap();
-    let neighbor = captures.get(4).unwrap().as_str().to_string();
+    let person = captures[1].to_string();
+    let happiness_units: i64 = line.parse(&captures[3], "a number of happiness units")?;
+    let neighbor = captures[4].to_string();
 
-    let happiness_units = match sign {
+    let happiness_units = match &captures[2] {
         "gain" => happiness_units,
-        "lose" => -happiness_units,
-        _ => panic!("incorrect sign: {}", sign),
+        _ => -happiness_units,
     };
-    Preference {
+    Ok(Preference {
         person,
         happiness_units,
         neighbor,
-    }
+    })
 }
 
 fn evaluate_happiness(
@@ -71,7 +82,8 @@ fn evaluate_happiness(
     total_happiness
 }
 
-fn find_optimal_arrangement_happiness(preferences: &[Preference]) -> i64 {
+/// Returns the total change in happiness of the best seating arrangement.
+pub fn find_optimal_arrangement_happiness(preferences: &[Preference]) -> i64 {
     let mut preferences_by_person = HashMap::new();
     for preference in preferences {
         let entry = preferences_by_person
@@ -89,44 +101,103 @@ fn find_optimal_arrangement_happiness(preferences: &[Preference]) -> i64 {
         .unwrap()
 }
 
-pub fn main(input: &str) -> anyhow::Result<()> {
-    let mut preferences: Vec<Preference> = input.lines().map(parse_preference).collect();
+/// Finds the happiness of the happiest seating, then with you seated too.
+#[derive(Default)]
+pub struct Solver;
 
-    let optimal_total_change_in_happiness = find_optimal_arrangement_happiness(&preferences);
-    println!("Optimal total change in happiness: {optimal_total_change_in_happiness}");
+impl Solution for Solver {
+    type Input = Vec<Preference>;
+
+    fn parse(&self, input: &str) -> anyhow::Result<Self::Input> {
+        Ok(parse::parse_lines(input, parse_preference)?)
+    }
 
-    preferences.push(Preference {
-        person: "Me".to_string(),
-        happiness_units: 0,
-        neighbor: "Anyone".to_string(),
-    });
-    let optimal_total_change_in_happiness = find_optimal_arrangement_happiness(&preferences);
-    println!("Optimal total change in happiness with me: {optimal_total_change_in_happiness}");
+    fn part1(&self, preferences: &Self::Input) -> anyhow::Result<Answer> {
+        let optimal_total_change_in_happiness = find_optimal_arrangement_happiness(preferences);
+        Ok(optimal_total_change_in_happiness.into())
+    }
 
-    Ok(())
+    fn part2(&self, preferences: &Self::Input) -> anyhow::Result<Answer> {
+        let mut preferences = preferences.clone();
+        preferences.push(Preference {
+            person: "Me".to_string(),
+            happiness_units: 0,
+            neighbor: "Anyone".to_string(),
+        });
+        let optimal_total_change_in_happiness = find_optimal_arrangement_happiness(&preferences);
+        Ok(optimal_total_change_in_happiness.into())
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    use aoc_core::solve_part;
+
     #[test]
     fn test_parse_reference() {
         assert_eq!(
-            parse_preference("Alice would gain 54 happiness units by sitting next to Bob."),
-            Preference {
+            parse_preference(Line::new(
+                1,
+                "Alice would gain 54 happiness units by sitting next to Bob."
+            )),
+            Ok(Preference {
                 person: "Alice".to_string(),
                 happiness_units: 54,
                 neighbor: "Bob".to_string()
-            }
+            })
         );
         assert_eq!(
-            parse_preference("Alice would lose 79 happiness units by sitting next to Carol."),
-            Preference {
+            parse_preference(Line::new(
+                2,
+                "Alice would lose 79 happiness units by sitting next to Carol."
+            )),
+            Ok(Preference {
                 person: "Alice".to_string(),
                 happiness_units: -79,
                 neighbor: "Carol".to_string(),
-            }
+            })
+        );
+
+        let truncated = Line::new(3, "Alice would gain 54 happiness units");
+        let error = parse_preference(truncated).unwrap_err();
+        assert_eq!((error.line, error.column), (3, 1));
+        let error = parse_preference(Line::new(
+            4,
+            "Alice would gain 99999999999999999999 happiness units by sitting next to Bob.",
+        ))
+        .unwrap_err();
+        assert_eq!(
+            (error.column, error.text.as_str()),
+            (18, "99999999999999999999")
+        );
+    }
+
+    #[test]
+    fn test_examples() {
+        let preferences = "Alice would gain 54 happiness units by sitting next to Bob.
+Alice would lose 79 happiness units by sitting next to Carol.
+Alice would lose 2 happiness units by sitting next to David.
+Bob would gain 83 happiness units by sitting next to Alice.
+Bob would lose 7 happiness units by sitting next to Carol.
+Bob would lose 63 happiness units by sitting next to David.
+Carol would lose 62 happiness units by sitting next to Alice.
+Carol would gain 60 happiness units by sitting next to Bob.
+Carol would gain 55 happiness units by sitting next to David.
+David would gain 46 happiness units by sitting next to Alice.
+David would lose 7 happiness units by sitting next to Bob.
+David would gain 41 happiness units by sitting next to Carol.";
+        assert_eq!(
+            solve_part(&Solver, preferences, 1).unwrap(),
+            Answer::from(330)
+        );
+        // Part 2 has no worked example. This is a regression value computed
+        // by the current code: seating yourself between David and Alice, the
+        // least happy neighbors of the part 1 arrangement, loses their 44.
+        assert_eq!(
+            solve_part(&Solver, preferences, 2).unwrap(),
+            Answer::from(286)
         );
     }
 }
@@ -1,12 +1,22 @@
+//! Day 14: Reindeer Olympics, racing reindeers flying and resting in turns.
+
+use anyhow::Context;
 use lazy_static::lazy_static;
 use regex::Regex;
 
+use aoc_core::parse::{self, Line, ParseError};
+use aoc_core::{params, Answer, Param, Solution};
+
+/// How a reindeer flies, given like `Comet can fly 14 km/s for 10 seconds,
+/// but then must rest for 127 seconds.`
 #[derive(Debug, Clone)]
-struct ReindeerDescription {
-    name: String,
-    velocity: u32,
-    flying_time: u32,
-    resting_time: u32,
+pub struct ReindeerDescription {
+    /// How many km it flies each second.
+    pub velocity: u32,
+    /// How many seconds it can fly before resting.
+    pub flying_time: u32,
+    /// How many seconds it has to rest.
+    pub resting_time: u32,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -15,13 +25,17 @@ enum ReindeerState {
     Resting,
 }
 
+/// A reindeer taking part in the race.
 #[derive(Debug)]
-struct Reindeer {
-    description: ReindeerDescription,
+pub struct Reindeer {
+    /// How the reindeer flies and rests.
+    pub description: ReindeerDescription,
     state: ReindeerState,
     time_in_state: u32,
-    distance_travelled: u32,
-    points: u32,
+    /// How many km the reindeer flew so far.
+    pub distance_travelled: u32,
+    /// One point for each second the reindeer was in the lead.
+    pub points: u32,
 }
 
 impl Reindeer {
@@ -54,29 +68,31 @@ impl Reindeer {
     }
 }
 
-fn parse_description(s: &str) -> ReindeerDescription {
+fn parse_description(line: Line) -> Result<ReindeerDescription, ParseError> {
     lazy_static! {
         static ref RE: Regex = Regex::new(
             r"^(\w+) can fly (\d+) km/s for (\d+) seconds, but then must rest for (\d+) seconds\.$"
         )
         .unwrap();
     }
-    let captures = RE.captures(s).unwrap();
-    let name = captures.get(1).unwrap().as_str().to_string();
-    let velocity = captures.get(2).unwrap().as_str().parse().unwrap();
-    let flying_time = captures.get(3).unwrap().as_str().parse().unwrap();
-    let resting_time = captures.get(4).unwrap().as_str().parse().unwrap();
-
-    ReindeerDescription {
-        name,
+    let captures = RE.captures(line.text).ok_or_else(|| {
+        line.mismatch(
+            "`<name> can fly <n> km/s for <n> seconds, but then must rest for <n> seconds.`",
+        )
+    })?;
+    let velocity = line.parse(&captures[2], "a velocity")?;
+    let flying_time = line.parse(&captures[3], "a flying time")?;
+    let resting_time = line.parse(&captures[4], "a resting time")?;
+
+    Ok(ReindeerDescription {
         velocity,
         flying_time,
         resting_time,
-    }
+    })
 }
 
-fn race(reindeers: &mut [Reindeer]) {
-    for _ in 0..2503 {
+fn race(reindeers: &mut [Reindeer], seconds: u32) {
+    for _ in 0..seconds {
         for reindeer in reindeers.iter_mut() {
             reindeer.tick();
         }
@@ -84,7 +100,7 @@ fn race(reindeers: &mut [Reindeer]) {
             .iter()
             .map(|reindeer| reindeer.distance_travelled)
             .max()
-            .unwrap();
+            .unwrap_or(0);
         for reindeer in reindeers.iter_mut() {
             if reindeer.distance_travelled == max_distance_travelled {
                 reindeer.points += 1;
@@ -93,35 +109,85 @@ fn race(reindeers: &mut [Reindeer]) {
     }
 }
 
-pub fn main(input: &str) -> anyhow::Result<()> {
-    let mut reindeers: Vec<Reindeer> = input
-        .lines()
-        .map(parse_description)
-        .map(|description| Reindeer::new(&description))
-        .collect();
+/// Races the reindeers for `seconds`, returning where they got.
+pub fn race_reindeers(descriptions: &[ReindeerDescription], seconds: u32) -> Vec<Reindeer> {
+    let mut reindeers: Vec<Reindeer> = descriptions.iter().map(Reindeer::new).collect();
+    race(&mut reindeers, seconds);
+    reindeers
+}
 
-    race(&mut reindeers);
+/// Races the reindeers, giving the distance of the winner, then the points of
+/// the winner when the leaders score each second.
+pub struct Solver {
+    /// How long the race lasts.
+    seconds: u32,
+}
 
-    let winner_by_distance_travelled = reindeers
-        .iter()
-        .max_by(|r1, r2| r1.distance_travelled.cmp(&r2.distance_travelled))
-        .unwrap();
+impl Default for Solver {
+    fn default() -> Self {
+        Self { seconds: 2503 }
+    }
+}
 
-    println!(
-        "{} travelled the max distance {} km",
-        winner_by_distance_travelled.description.name,
-        winner_by_distance_travelled.distance_travelled
-    );
+impl Solution for Solver {
+    type Input = Vec<ReindeerDescription>;
 
-    let winner_by_points = reindeers
-        .iter()
-        .max_by(|r1, r2| r1.points.cmp(&r2.points))
-        .unwrap();
+    fn parse(&self, input: &str) -> anyhow::Result<Self::Input> {
+        Ok(parse::parse_lines(input, parse_description)?)
+    }
 
-    println!(
-        "{} scored the most points {}",
-        winner_by_points.description.name, winner_by_points.points
-    );
+    fn part1(&self, descriptions: &Self::Input) -> anyhow::Result<Answer> {
+        let winner_by_distance_travelled = race_reindeers(descriptions, self.seconds)
+            .into_iter()
+            .max_by(|r1, r2| r1.distance_travelled.cmp(&r2.distance_travelled))
+            .context("no reindeers are racing")?;
+        Ok(winner_by_distance_travelled.distance_travelled.into())
+    }
+
+    fn part2(&self, descriptions: &Self::Input) -> anyhow::Result<Answer> {
+        let winner_by_points = race_reindeers(descriptions, self.seconds)
+            .into_iter()
+            .max_by(|r1, r2| r1.points.cmp(&r2.points))
+            .context("no reindeers are racing")?;
+        Ok(winner_by_points.points.into())
+    }
+
+    fn params(&self) -> Vec<Param> {
+        vec![Param::new(
+            "seconds",
+            "how many seconds the race lasts",
+            self.seconds,
+        )]
+    }
+
+    fn set_param(&mut self, name: &str, value: &str) -> anyhow::Result<()> {
+        match name {
+            "seconds" => self.seconds = params::parse_value(name, value)?,
+            _ => anyhow::bail!("unknown parameter {}", name),
+        }
+        Ok(())
+    }
+}
 
-    Ok(())
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use aoc_core::solve_part;
+
+    #[test]
+    fn test_examples() {
+        let descriptions =
+            "Comet can fly 14 km/s for 10 seconds, but then must rest for 127 seconds.
+Dancer can fly 16 km/s for 11 seconds, but then must rest for 162 seconds.";
+        let solver = Solver { seconds: 1000 };
+        assert_eq!(
+            solve_part(&solver, descriptions, 1).unwrap(),
+            Answer::from(1120)
+        );
+        assert_eq!(
+            solve_part(&solver, descriptions, 2).unwrap(),
+            Answer::from(689)
+        );
+    }
 }
@@ -1,88 +1,150 @@
+//! Day 15: Science for Hungry People, finding the best mix of ingredients
+//! for a cookie.
+
+use anyhow::Context;
 use lazy_static::lazy_static;
 use regex::Regex;
 
+use aoc_core::parse::{self, Line, ParseError};
+use aoc_core::{Answer, Solution};
+
+/// The properties of a teaspoon of an ingredient, given like
+/// `Cinnamon: capacity 2, durability 3, flavor -2, texture -1, calories 3`.
 #[derive(Debug)]
-struct Ingredient {
-    capacity: i64,
-    durability: i64,
-    flavor: i64,
-    texture: i64,
-    calories: i64,
+pub struct Ingredient {
+    /// How well the cookie absorbs milk.
+    pub capacity: i64,
+    /// How well the cookie stays intact when full of milk.
+    pub durability: i64,
+    /// How tasty the cookie is.
+    pub flavor: i64,
+    /// How the cookie feels.
+    pub texture: i64,
+    /// How many calories the cookie has.
+    pub calories: i64,
 }
 
-fn parse_ingredient(s: &str) -> Ingredient {
+fn parse_ingredient(line: Line) -> Result<Ingredient, ParseError> {
     lazy_static! {
         static ref RE: Regex = Regex::new(
             r"^(\w+): capacity (-?\d+), durability (-?\d+), flavor (-?\d+), texture (-?\d+), calories (\d+)$"
         ).unwrap();
     }
 
-    let captures = RE.captures(s).unwrap();
-    let capacity = captures.get(2).unwrap().as_str().parse().unwrap();
-    let durability = captures.get(3).unwrap().as_str().parse().unwrap();
-    let flavor = captures.get(4).unwrap().as_str().parse().unwrap();
-    let texture = captures.get(5).unwrap().as_str().parse().unwrap();
-    let calories = captures.get(6).unwrap().as_str().parse().unwrap();
-    Ingredient {
+    let captures = RE.captures(line.text).ok_or_else(|| {
+        line.mismatch(
+            "`<name>: capacity <n>, durability <n>, flavor <n>, texture <n>, calories <n>`",
+        )
+    })?;
+    let capacity = line.parse(&captures[2], "a capacity")?;
+    let durability = line.parse(&captures[3], "a durability")?;
+    let flavor = line.parse(&captures[4], "a flavor")?;
+    let texture = line.parse(&captures[5], "a texture")?;
+    let calories = line.parse(&captures[6], "a number of calories")?;
+    Ok(Ingredient {
         capacity,
         durability,
         flavor,
         texture,
         calories,
+    })
+}
+
+/// Calls `f` with every way to split `teaspoons` among `count` ingredients,
+/// appending the amounts to `amounts`.
+fn for_each_mix<F>(count: usize, teaspoons: i64, amounts: &mut Vec<i64>, f: &mut F)
+where
+    F: FnMut(&[i64]),
+{
+    if count == 1 {
+        amounts.push(teaspoons);
+        f(amounts);
+        amounts.pop();
+        return;
+    }
+    for amount in 0..=teaspoons {
+        amounts.push(amount);
+        for_each_mix(count - 1, teaspoons - amount, amounts, f);
+        amounts.pop();
     }
 }
 
-pub fn main(input: &str) -> anyhow::Result<()> {
-    let ingredients: Vec<Ingredient> = input.lines().map(parse_ingredient).collect();
-    assert_eq!(ingredients.len(), 4);
-
-    let mut max_total_score = i64::MIN;
-    let mut max_500_calories_score = i64::MIN;
-    for i in 0..100 {
-        for j in 0..(100 - i) {
-            for k in 0..(100 - i - j) {
-                let l = 100 - i - j - k;
-                assert!(i + j + k + l == 100);
-
-                let capacity = ingredients[0].capacity * i
-                    + ingredients[1].capacity * j
-                    + ingredients[2].capacity * k
-                    + ingredients[3].capacity * l;
-                let durability = ingredients[0].durability * i
-                    + ingredients[1].durability * j
-                    + ingredients[2].durability * k
-                    + ingredients[3].durability * l;
-                let flavor = ingredients[0].flavor * i
-                    + ingredients[1].flavor * j
-                    + ingredients[2].flavor * k
-                    + ingredients[3].flavor * l;
-                let texture = ingredients[0].texture * i
-                    + ingredients[1].texture * j
-                    + ingredients[2].texture * k
-                    + ingredients[3].texture * l;
-                let calories = ingredients[0].calories * i
-                    + ingredients[1].calories * j
-                    + ingredients[2].calories * k
-                    + ingredients[3].calories * l;
-
-                let total_score = {
-                    if capacity >= 0 && durability >= 0 && flavor >= 0 && texture >= 0 {
-                        capacity * durability * flavor * texture
-                    } else {
-                        0
-                    }
-                };
-                if total_score > max_total_score {
-                    max_total_score = total_score;
-                }
-                if calories == 500 && total_score > max_total_score {
-                    max_500_calories_score = total_score;
-                }
-            }
+/// Finds the total score of the highest scoring cookie. If `calories` is given,
+/// only cookies with exactly that many calories are considered.
+pub fn find_max_total_score(ingredients: &[Ingredient], calories: Option<i64>) -> Option<i64> {
+    let mut max_total_score = None;
+    for_each_mix(ingredients.len(), 100, &mut vec![], &mut |amounts| {
+        let total = |property: fn(&Ingredient) -> i64| -> i64 {
+            ingredients
+                .iter()
+                .zip(amounts)
+                .map(|(ingredient, amount)| property(ingredient) * amount)
+                .sum()
+        };
+
+        if calories.is_some_and(|calories| calories != total(|ingredient| ingredient.calories)) {
+            return;
         }
+
+        // A negative property makes the whole cookie score zero.
+        let total_score = [
+            total(|ingredient| ingredient.capacity),
+            total(|ingredient| ingredient.durability),
+            total(|ingredient| ingredient.flavor),
+            total(|ingredient| ingredient.texture),
+        ]
+        .into_iter()
+        .map(|property| property.max(0))
+        .product::<i64>();
+        max_total_score = max_total_score.max(Some(total_score));
+    });
+    max_total_score
+}
+
+/// Finds the score of the best cookie, then of the best one with 500
+/// calories.
+#[derive(Default)]
+pub struct Solver;
+
+impl Solution for Solver {
+    type Input = Vec<Ingredient>;
+
+    fn parse(&self, input: &str) -> anyhow::Result<Self::Input> {
+        let ingredients = parse::parse_lines(input, parse_ingredient)?;
+        anyhow::ensure!(!ingredients.is_empty(), "expected ingredients");
+        Ok(ingredients)
+    }
+
+    fn part1(&self, ingredients: &Self::Input) -> anyhow::Result<Answer> {
+        let total_score = find_max_total_score(ingredients, None).context("no cookie found")?;
+        Ok(total_score.into())
     }
 
-    println!("Total score of highest scoring cookie: {max_total_score}");
-    println!("Total score of highest scoring cookie with 500 calories: {max_500_calories_score}");
-    Ok(())
+    fn part2(&self, ingredients: &Self::Input) -> anyhow::Result<Answer> {
+        let total_score = find_max_total_score(ingredients, Some(500))
+            .context("no cookie has exactly 500 calories")?;
+        Ok(total_score.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use aoc_core::solve_part;
+
+    #[test]
+    fn test_examples() {
+        let ingredients =
+            "Butterscotch: capacity -1, durability -2, flavor 6, texture 3, calories 8
+Cinnamon: capacity 2, durability 3, flavor -2, texture -1, calories 3";
+        assert_eq!(
+            solve_part(&Solver, ingredients, 1).unwrap(),
+            Answer::from(62842880)
+        );
+        assert_eq!(
+            solve_part(&Solver, ingredients, 2).unwrap(),
+            Answer::from(57600000)
+        );
+    }
 }
@@ -1,16 +1,33 @@
+//! Day 16: Aunt Sue, finding which of the aunts sent a gift from what was
+//! detected on it.
+
 use std::collections::HashMap;
 
-fn parse_description(line: &str) -> HashMap<String, i32> {
-    let (_, properties) = line.split_once(": ").unwrap();
+use anyhow::Context;
+
+use aoc_core::parse::{self, Line, ParseError};
+use aoc_core::{params, Answer, Param, Solution};
+
+fn parse_description(line: Line) -> Result<HashMap<String, i32>, ParseError> {
+    let (sue, properties) =
+        line.split_once(line.text, ": ", "`Sue <n>: <property>: <count>, ...`")?;
+    let number = line.strip_prefix(sue, "Sue ", "`Sue <n>`")?;
+    line.parse::<u32>(number, "the number of a Sue")?;
     properties
         .split(", ")
-        .map(|property| property.split_once(": ").unwrap())
-        .map(|(k, v)| (k.to_string(), v.parse().unwrap()))
+        .map(|property| {
+            let (key, count) = line.split_once(property, ": ", "`<property>: <count>`")?;
+            Ok((key.to_string(), line.parse(count, "a count")?))
+        })
         .collect()
 }
 
-pub fn main(input: &str) -> anyhow::Result<()> {
-    let known_properties = HashMap::from([
+/// The properties detected by the MFCSAM, in the order they are printed.
+pub type Properties = Vec<(String, i32)>;
+
+/// The properties detected on the gift in the puzzle.
+pub fn default_known_properties() -> Properties {
+    [
         ("children", 3),
         ("cats", 7),
         ("samoyeds", 2),
@@ -21,34 +38,148 @@ pub fn main(input: &str) -> anyhow::Result<()> {
         ("trees", 3),
         ("cars", 2),
         ("perfumes", 1),
-    ]);
-    let sues: Vec<HashMap<String, i32>> = input.lines().map(parse_description).collect();
+    ]
+    .into_iter()
+    .map(|(key, value)| (key.to_string(), value))
+    .collect()
+}
 
-    for (i, sue) in sues.iter().enumerate() {
-        let is_part1_gifter = sue
-            .iter()
-            .all(|(key, value)| *known_properties.get(key.as_str()).unwrap() == *value);
+/// Parses properties given like `children: 3, cats: 7`.
+fn parse_properties(name: &str, value: &str) -> anyhow::Result<Properties> {
+    value
+        .split(',')
+        .map(|property| {
+            let (key, count) = property.split_once(':').with_context(|| {
+                format!(
+                    "expected `name: count` for parameter {}, got {:?}",
+                    name, property
+                )
+            })?;
+            Ok((key.trim().to_string(), params::parse_value(name, count)?))
+        })
+        .collect()
+}
+
+fn format_properties(properties: &Properties) -> String {
+    properties
+        .iter()
+        .map(|(key, value)| format!("{}: {}", key, value))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Whether `sue` matches the known properties exactly. Properties the MFCSAM
+/// didn't detect can't rule out a Sue.
+pub fn is_part1_gifter(sue: &HashMap<String, i32>, known_properties: &HashMap<&str, i32>) -> bool {
+    sue.iter().all(|(key, value)| {
+        known_properties
+            .get(key.as_str())
+            .is_none_or(|known_value| known_value == value)
+    })
+}
 
-        if is_part1_gifter {
-            println!("[Part 1]: Sue {} sent the gift", i + 1);
-            continue;
+/// Whether `sue` matches the known properties, knowing that the MFCSAM only
+/// reports lower bounds for cats and trees and upper bounds for pomeranians
+/// and goldfish.
+pub fn is_part2_gifter(sue: &HashMap<String, i32>, known_properties: &HashMap<&str, i32>) -> bool {
+    sue.iter().all(|(key, value)| {
+        let Some(known_value) = known_properties.get(key.as_str()) else {
+            return true;
+        };
+        match key.as_str() {
+            "cats" => value >= known_value,
+            "trees" => value >= known_value,
+            "pomeranians" => value <= known_value,
+            "goldfish" => value <= known_value,
+            _ => value == known_value,
         }
+    })
+}
+
+/// Finds the aunt matching what the MFCSAM detected, then the one matching
+/// once some of its readings are understood as ranges.
+pub struct Solver {
+    /// What the MFCSAM detected on the gift.
+    known_properties: Properties,
+}
 
-        let is_part2_gifter = sue.iter().all(|(key, value)| {
-            let known_value = known_properties.get(key.as_str()).unwrap();
-            match key.as_str() {
-                "cats" => value >= known_value,
-                "trees" => value >= known_value,
-                "pomeranians" => value <= known_value,
-                "goldfish" => value <= known_value,
-                _ => value == known_value,
-            }
-        });
-
-        if is_part2_gifter {
-            println!("[Part 2]: Sue {} sent the gift", i + 1);
+impl Default for Solver {
+    fn default() -> Self {
+        Self {
+            known_properties: default_known_properties(),
         }
     }
+}
 
-    Ok(())
+impl Solver {
+    fn known_properties(&self) -> HashMap<&str, i32> {
+        self.known_properties
+            .iter()
+            .map(|(key, value)| (key.as_str(), *value))
+            .collect()
+    }
+}
+
+impl Solution for Solver {
+    type Input = Vec<HashMap<String, i32>>;
+
+    fn parse(&self, input: &str) -> anyhow::Result<Self::Input> {
+        Ok(parse::parse_lines(input, parse_description)?)
+    }
+
+    fn part1(&self, sues: &Self::Input) -> anyhow::Result<Answer> {
+        let known_properties = self.known_properties();
+        let i = sues
+            .iter()
+            .position(|sue| is_part1_gifter(sue, &known_properties))
+            .context("no Sue sent the gift")?;
+        Ok((i + 1).into())
+    }
+
+    fn part2(&self, sues: &Self::Input) -> anyhow::Result<Answer> {
+        let known_properties = self.known_properties();
+        let i = sues
+            .iter()
+            .position(|sue| {
+                !is_part1_gifter(sue, &known_properties) && is_part2_gifter(sue, &known_properties)
+            })
+            .context("no Sue sent the gift")?;
+        Ok((i + 1).into())
+    }
+
+    fn params(&self) -> Vec<Param> {
+        vec![Param::new(
+            "known_properties",
+            "what the MFCSAM detected, like `children: 3, cats: 7`",
+            format_properties(&self.known_properties),
+        )]
+    }
+
+    fn set_param(&mut self, name: &str, value: &str) -> anyhow::Result<()> {
+        match name {
+            "known_properties" => self.known_properties = parse_properties(name, value)?,
+            _ => anyhow::bail!("unknown parameter {}", name),
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use aoc_core::solve_part;
+
+    #[test]
+    fn test_examples() {
+        // The statement lists no aunts, so these are made up and checked by
+        // hand against the ticker tape: Sue 2 matches exactly, and only Sue 3
+        // has more cats and fewer pomeranians than the tape.
+        let aunts = "Sue 1: cats: 8, trees: 1
+Sue 2: goldfish: 5, cars: 2
+Sue 3: cats: 8, pomeranians: 1";
+        let solver = Solver::default();
+        assert_eq!(solve_part(&solver, aunts, 1).unwrap(), Answer::from(2));
+        assert_eq!(solve_part(&solver, aunts, 2).unwrap(), Answer::from(3));
+    }
 }
@@ -1,15 +1,20 @@
+//! Day 17: No Such Thing as Too Much, filling containers with eggnog.
+
+use anyhow::Context;
 use itertools::Itertools;
 
-pub fn main(input: &str) -> anyhow::Result<()> {
-    let target_capacity = 150;
-    let containers: Vec<u64> = input
-        .lines()
-        .map(|line| line.parse())
-        .collect::<Result<_, _>>()?;
+use aoc_core::parse::{self, ParseError};
+use aoc_core::{params, Answer, Param, Solution};
+
+/// Containers given by their index and capacity.
+pub type Combination = Vec<(usize, u64)>;
 
+/// Returns every combination of `containers` holding exactly
+/// `target_capacity` liters.
+pub fn find_combinations(containers: &[u64], target_capacity: u64) -> Vec<Combination> {
     let mut combinations = vec![];
     for k in 1..containers.len() {
-        for combination in containers.clone().into_iter().enumerate().combinations(k) {
+        for combination in containers.iter().copied().enumerate().combinations(k) {
             let mut prefix = vec![];
             let mut total_capacity = 0;
             for container in &combination {
@@ -24,19 +29,88 @@ pub fn main(input: &str) -> anyhow::Result<()> {
             }
         }
     }
+    combinations
+}
 
-    println!("Possible different combinations: {}", combinations.len());
+/// Parses the capacities of the containers, one per line.
+pub fn parse_containers(input: &str) -> Result<Vec<u64>, ParseError> {
+    parse::parse_lines(input, |line| line.parse(line.text, "a capacity in liters"))
+}
 
-    let min_containers = combinations
-        .iter()
-        .map(|combination| combination.len())
-        .min()
-        .unwrap();
-    let combinations_with_min_containers = combinations
-        .iter()
-        .filter(|combination| combination.len() == min_containers)
-        .count();
-    println!("Possible containers with min container count ({min_containers}): {combinations_with_min_containers}");
+/// Counts the combinations of containers holding exactly the eggnog, then
+/// those using the fewest containers.
+pub struct Solver {
+    /// How much eggnog has to be stored.
+    liters: u64,
+}
+
+impl Default for Solver {
+    fn default() -> Self {
+        Self { liters: 150 }
+    }
+}
+
+impl Solution for Solver {
+    type Input = Vec<u64>;
+
+    fn parse(&self, input: &str) -> anyhow::Result<Self::Input> {
+        Ok(parse_containers(input)?)
+    }
+
+    fn part1(&self, containers: &Self::Input) -> anyhow::Result<Answer> {
+        Ok(find_combinations(containers, self.liters).len().into())
+    }
 
-    Ok(())
+    fn part2(&self, containers: &Self::Input) -> anyhow::Result<Answer> {
+        let combinations = find_combinations(containers, self.liters);
+        let min_containers = combinations
+            .iter()
+            .map(|combination| combination.len())
+            .min()
+            .context("no combination of containers fits the eggnog")?;
+        let combinations_with_min_containers = combinations
+            .iter()
+            .filter(|combination| combination.len() == min_containers)
+            .count();
+        Ok(combinations_with_min_containers.into())
+    }
+
+    fn params(&self) -> Vec<Param> {
+        vec![Param::new(
+            "liters",
+            "how many liters of eggnog to store",
+            self.liters,
+        )]
+    }
+
+    fn set_param(&mut self, name: &str, value: &str) -> anyhow::Result<()> {
+        match name {
+            "liters" => self.liters = params::parse_value(name, value)?,
+            _ => anyhow::bail!("unknown parameter {}", name),
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use aoc_core::solve_part;
+
+    #[test]
+    fn test_examples() {
+        let solver = Solver { liters: 25 };
+        assert_eq!(
+            solve_part(&solver, "20\n15\n10\n5\n5", 1).unwrap(),
+            Answer::from(4)
+        );
+        assert_eq!(
+            solve_part(&solver, "20\n15\n10\n5\n5", 2).unwrap(),
+            Answer::from(3)
+        );
+
+        let error = parse_containers("20\n15l").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 1));
+    }
 }
@@ -1,9 +1,19 @@
+//! Day 18: Like a GIF For Your Yard, animating a grid of lights with the
+//! rules of Conway's Game of Life.
+
 use std::fmt;
-use std::str::FromStr;
+use std::time::Duration;
+
+use aoc_core::grid::Grid;
+use aoc_core::render::{self, Image, Renderer};
+use aoc_core::{params, Answer, Param, Solution};
 
+/// A light of the grid, `#` when on and `.` when off.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
-enum Light {
+pub enum Light {
+    /// Drawn as `#`.
     On,
+    /// Drawn as `.`.
     Off,
 }
 
@@ -16,121 +26,97 @@ impl fmt::Display for Light {
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
-struct Grid<T>(Vec<Vec<T>>);
-
-impl<T> Grid<T> {
-    fn is_coordinate_in_grid(&self, coordinate: (isize, isize)) -> bool {
-        let size = self.0.len();
-        coordinate.0 >= 0
-            && (coordinate.0 as usize) < size
-            && coordinate.1 >= 0
-            && (coordinate.1 as usize) < size
+/// Parses the grid of lights, a line of `#` and `.` per row.
+pub fn parse_grid(input: &str) -> anyhow::Result<Grid<Light>> {
+    let grid = Grid::parse(
+        input,
+        |c| match c {
+            '#' => Some(Light::On),
+            '.' => Some(Light::Off),
+            _ => None,
+        },
+        "`#` or `.`",
+    )?;
+    if grid.height() == 0 {
+        anyhow::bail!("input is empty");
     }
+    Ok(grid)
 }
 
-impl Grid<Light> {
-    fn neighbors(&self) -> Grid<usize> {
-        let mut grid = vec![vec![0; self.0.len()]; self.0.len()];
-
-        for y in 0..self.0.len() {
-            for x in 0..self.0.len() {
-                let x = x as isize;
-                let y = y as isize;
-                let count = [
-                    (x, y - 1),     // top
-                    (x + 1, y - 1), // top right
-                    (x + 1, y),     // right
-                    (x + 1, y + 1), // bottom right
-                    (x, y + 1),     // bottom
-                    (x - 1, y + 1), // bottom left
-                    (x - 1, y),     // left
-                    (x - 1, y - 1), // top left
-                ]
-                .into_iter()
-                .filter(|c| self.is_coordinate_in_grid(*c))
-                .map(|c| self.0.get(c.1 as usize).unwrap().get(c.0 as usize).unwrap())
-                .filter(|light| **light == Light::On)
-                .count();
-                *grid
-                    .get_mut(y as usize)
-                    .unwrap()
-                    .get_mut(x as usize)
-                    .unwrap() = count;
-            }
-        }
-
-        Grid(grid)
-    }
+/// Counts the lights on in `grid`.
+pub fn lights_on(grid: &Grid<Light>) -> usize {
+    grid.cells().filter(|light| **light == Light::On).count()
 }
 
-impl FromStr for Grid<Light> {
-    type Err = anyhow::Error;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let lights: Vec<Vec<Light>> = s
-            .lines()
-            .map(|line| {
-                line.chars()
-                    .map(|c| match c {
-                        '#' => Light::On,
-                        '.' => Light::Off,
-                        _ => panic!("invalid input"),
-                    })
-                    .collect()
-            })
-            .collect();
-        if lights.is_empty() || lights.len() != lights.first().unwrap().len() {
-            return Err(anyhow::anyhow!("input is empty or not a square grid"));
+/// Returns the grid with the four corners on, as they are stuck in part 2.
+pub fn with_corners_on(grid: &Grid<Light>) -> Grid<Light> {
+    let mut grid = grid.clone();
+    let (right, bottom) = (
+        grid.width().saturating_sub(1),
+        grid.height().saturating_sub(1),
+    );
+    for (x, y) in [(0, 0), (right, 0), (0, bottom), (right, bottom)] {
+        if let Some(light) = grid.get_mut(x, y) {
+            *light = Light::On;
         }
-        Ok(Grid(lights))
     }
+    grid
 }
 
-impl<T: fmt::Display> fmt::Display for Grid<T> {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        for row in &self.0 {
-            for cell in row {
-                write!(f, "{}", cell)?;
-            }
-            writeln!(f)?;
-        }
-        Ok(())
-    }
+fn image(grid: &Grid<Light>) -> Image {
+    let palette = [render::BLACK, render::YELLOW];
+    Image::from_fn(grid.width(), grid.height(), &palette, |x, y| {
+        u8::from(grid[(x, y)] == Light::On)
+    })
 }
 
 fn step<F>(grid: &Grid<Light>, transition: F) -> Grid<Light>
 where
     F: Fn(Light, usize, bool) -> Light,
 {
-    let mut result = vec![vec![Light::Off; grid.0.len()]; grid.0.len()];
-    let neighbors = grid.neighbors();
-    for (y, row) in grid.0.iter().enumerate() {
-        for (x, light) in row.iter().enumerate() {
-            let neighboring_lights = *neighbors.0.get(y).unwrap().get(x).unwrap();
-            let is_corner = (x == 0 || x == grid.0.len() - 1) && (y == 0 || y == grid.0.len() - 1);
-            let new_state = transition(*light, neighboring_lights, is_corner);
-            *result.get_mut(y).unwrap().get_mut(x).unwrap() = new_state;
-        }
-    }
-    Grid(result)
+    let (right, bottom) = (grid.width() - 1, grid.height() - 1);
+    Grid::from_fn(grid.width(), grid.height(), |x, y| {
+        let neighboring_lights = grid
+            .neighbors8(x, y)
+            .filter(|neighbor| grid[*neighbor] == Light::On)
+            .count();
+        let is_corner = (x == 0 || x == right) && (y == 0 || y == bottom);
+        transition(grid[(x, y)], neighboring_lights, is_corner)
+    })
 }
 
-fn animate<F>(initial: &Grid<Light>, steps: usize, transition: F) -> Grid<Light>
+/// Animates the lights for `steps` steps, calling `on_step` with the initial
+/// grid and the grid after each step.
+pub fn animate<F, G>(
+    initial: &Grid<Light>,
+    steps: usize,
+    transition: F,
+    mut on_step: G,
+) -> Grid<Light>
 where
     F: Fn(Light, usize, bool) -> Light,
+    G: FnMut(&Grid<Light>),
 {
     let mut grid = initial.clone();
-    //    println!("Initial state:\n{}", grid);
+    log::trace!("Initial state:\n{}", grid);
+    on_step(&grid);
 
-    for _n in 1..=steps {
+    for n in 1..=steps {
         grid = step(&grid, &transition);
-        //        println!("After {n} step:\n{}", grid);
+        log::debug!("After step {}, {} lights are on", n, lights_on(&grid));
+        log::trace!("After step {}:\n{}", n, grid);
+        on_step(&grid);
     }
     grid
 }
 
-fn part1(light: Light, neighbors: usize, _is_corner: bool) -> Light {
+/// Returns the next state of a light from its state, the number of neighbors
+/// that are on and whether it is in a corner.
+pub type Transition = fn(Light, usize, bool) -> Light;
+
+/// The rules of the Game of Life: a light stays on with 2 or 3 neighbors on,
+/// and turns on with exactly 3.
+pub fn life(light: Light, neighbors: usize, _is_corner: bool) -> Light {
     match (light, neighbors) {
         (Light::On, 2 | 3) => Light::On,
         (Light::On, _) => Light::Off,
@@ -139,7 +125,8 @@ fn part1(light: Light, neighbors: usize, _is_corner: bool) -> Light {
     }
 }
 
-fn part2(light: Light, neighbors: usize, is_corner: bool) -> Light {
+/// The rules of the Game of Life with the corners stuck on.
+pub fn life_with_stuck_corners(light: Light, neighbors: usize, is_corner: bool) -> Light {
     match (light, neighbors, is_corner) {
         (_, _, true) => Light::On,
         (Light::On, 2 | 3, _) => Light::On,
@@ -149,24 +136,99 @@ fn part2(light: Light, neighbors: usize, is_corner: bool) -> Light {
     }
 }
 
-pub fn main(input: &str) -> anyhow::Result<()> {
-    let initial_grid: Grid<Light> = input.parse()?;
-
-    let grid = animate(&initial_grid, 100, part1);
-    let lights_on: usize = grid
-        .0
-        .iter()
-        .map(|row| row.iter().filter(|light| **light == Light::On).count())
-        .sum();
-    println!("[Part 1]: After 100 steps {lights_on} lights are on");
-
-    let grid = animate(&initial_grid, 100, part2);
-    let lights_on: usize = grid
-        .0
-        .iter()
-        .map(|row| row.iter().filter(|light| **light == Light::On).count())
-        .sum();
-    println!("[Part 2]: After 100 steps {lights_on} lights are on");
-
-    Ok(())
+/// Counts the lights on after animating the grid, then with its corners stuck
+/// on.
+pub struct Solver {
+    /// How many steps the lights are animated for.
+    steps: usize,
+}
+
+impl Default for Solver {
+    fn default() -> Self {
+        Self { steps: 100 }
+    }
+}
+
+impl Solution for Solver {
+    type Input = Grid<Light>;
+
+    fn parse(&self, input: &str) -> anyhow::Result<Self::Input> {
+        parse_grid(input)
+    }
+
+    fn part1(&self, initial_grid: &Self::Input) -> anyhow::Result<Answer> {
+        let grid = animate(initial_grid, self.steps, life, |_| {});
+        Ok(lights_on(&grid).into())
+    }
+
+    fn part2(&self, initial_grid: &Self::Input) -> anyhow::Result<Answer> {
+        let grid = animate(
+            &with_corners_on(initial_grid),
+            self.steps,
+            life_with_stuck_corners,
+            |_| {},
+        );
+        Ok(lights_on(&grid).into())
+    }
+
+    fn render(&self, initial_grid: &Self::Input, renderer: &mut Renderer) -> anyhow::Result<()> {
+        let transitions: [(&str, Grid<Light>, Transition); 2] = [
+            ("part1", initial_grid.clone(), life),
+            (
+                "part2",
+                with_corners_on(initial_grid),
+                life_with_stuck_corners,
+            ),
+        ];
+        for (name, initial_grid, transition) in transitions {
+            let mut frames = vec![];
+            let grid = animate(&initial_grid, self.steps, transition, |grid| {
+                frames.push(image(grid))
+            });
+            renderer.png(name, &image(&grid))?;
+            renderer.gif(name, &frames, Duration::from_millis(100))?;
+        }
+        Ok(())
+    }
+
+    fn params(&self) -> Vec<Param> {
+        vec![Param::new(
+            "steps",
+            "how many steps to animate the lights",
+            self.steps,
+        )]
+    }
+
+    fn set_param(&mut self, name: &str, value: &str) -> anyhow::Result<()> {
+        match name {
+            "steps" => self.steps = params::parse_value(name, value)?,
+            _ => anyhow::bail!("unknown parameter {}", name),
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use aoc_core::solve_part;
+
+    #[test]
+    fn test_examples() {
+        let lights = ".#.#.#
+...##.
+#....#
+..#...
+#.#..#
+####..";
+        assert_eq!(
+            solve_part(&Solver { steps: 4 }, lights, 1).unwrap(),
+            Answer::from(4)
+        );
+        assert_eq!(
+            solve_part(&Solver { steps: 5 }, lights, 2).unwrap(),
+            Answer::from(17)
+        );
+    }
 }
@@ -1,7 +1,16 @@
-use std::cmp::Ordering;
-use std::collections::{BinaryHeap, HashSet};
+//! Day 19: Medicine for Rudolph, replacing parts of molecules.
 
-fn apply_replacements(molecule: &str, replacements: &[(&str, &str)]) -> HashSet<String> {
+use std::collections::HashSet;
+
+use anyhow::Context;
+
+use aoc_core::parse::{self, ParseError};
+use aoc_core::search;
+use aoc_core::{Answer, Solution};
+
+/// Returns every molecule made by one replacement in `molecule`, each
+/// replacement being a pattern and what it is replaced with.
+pub fn apply_replacements(molecule: &str, replacements: &[(String, String)]) -> HashSet<String> {
     let mut molecules = HashSet::new();
     for (pattern, replacement) in replacements {
         for (index, _) in molecule.match_indices(pattern) {
@@ -17,86 +26,131 @@ fn apply_replacements(molecule: &str, replacements: &[(&str, &str)]) -> HashSet<
     molecules
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
-struct MoleculeCandidate {
-    steps: usize,
-    molecule: String,
-}
-
-impl Ord for MoleculeCandidate {
-    fn cmp(&self, other: &Self) -> Ordering {
-        // Prefer shorter molecules over longer ones
-        // fewer steps over more steps
-        // resolve ties by comparing molecules
-        other
-            .molecule
-            .len()
-            .cmp(&self.molecule.len())
-            .then_with(|| self.steps.cmp(&other.steps))
-            .then_with(|| self.molecule.cmp(&other.molecule))
-    }
-}
-
-impl PartialOrd for MoleculeCandidate {
-    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        Some(self.cmp(other))
-    }
-}
-
-fn find_molecule(
+/// Returns how many replacements turn `initial_molecule` into
+/// `final_molecule` along the first path found, trying the shortest molecules
+/// first. That finds a path quickly when the replacements shrink the molecule
+/// towards `final_molecule`, but not necessarily the shortest one.
+///
+/// It is the shortest when every path takes as many replacements, which holds
+/// for the puzzle inputs: their replacements form an unambiguous grammar in
+/// which each one adds a fixed number of elements, so the number of
+/// replacements only depends on the elements of the molecule.
+pub fn find_molecule(
     initial_molecule: &str,
     final_molecule: &str,
-    replacements: &[(&str, &str)],
+    replacements: &[(String, String)],
 ) -> Option<usize> {
-    let mut candidates = BinaryHeap::from([MoleculeCandidate {
-        steps: 0,
-        molecule: initial_molecule.to_string(),
-    }]);
-
-    while let Some(MoleculeCandidate { steps, molecule }) = candidates.pop() {
-        if molecule == final_molecule {
-            return Some(steps);
-        }
+    let result = search::best_first(
+        initial_molecule.to_string(),
+        |molecule| {
+            // Sorted so that ties are broken the same way on every run.
+            let mut molecules = Vec::from_iter(apply_replacements(molecule, replacements));
+            molecules.sort_unstable();
+            molecules.into_iter().map(|molecule| (molecule, 1))
+        },
+        |molecule| molecule.len(),
+        |molecule| molecule == final_molecule,
+    );
+    log::debug!(
+        "Tried {} of {} molecules found",
+        result.stats.expanded,
+        result.stats.discovered
+    );
+    result.path.map(|path| path.cost)
+}
 
-        for new_molecule in apply_replacements(&molecule, replacements) {
-            candidates.push(MoleculeCandidate {
-                steps: steps + 1,
-                molecule: new_molecule,
-            });
+/// The replacements the machine can do, given like `H => HO`, and the
+/// molecule of the medicine.
+#[derive(Debug)]
+pub struct Machine {
+    /// The patterns and what each can be replaced with.
+    pub replacements: Vec<(String, String)>,
+    /// The molecule to make.
+    pub medicine_molecule: String,
+}
+
+/// Parses the replacements, an empty line and the medicine molecule.
+pub fn parse_machine(input: &str) -> Result<Machine, ParseError> {
+    let mut lines = parse::lines(input);
+    let mut replacements = vec![];
+    for line in lines.by_ref() {
+        if line.text.is_empty() {
+            break;
         }
+        let (pattern, replacement) = line.split_once(
+            line.text,
+            " => ",
+            "`<pattern> => <replacement>` or an empty line",
+        )?;
+        replacements.push((pattern.to_string(), replacement.to_string()));
     }
-
-    None
+    let medicine_molecule = match lines.next() {
+        Some(line) if !line.text.trim().is_empty() => line.text.trim().to_string(),
+        Some(line) => return Err(line.mismatch("the medicine molecule")),
+        None => return Err(parse::end_of_input(input, "the medicine molecule")),
+    };
+    if let Some(line) = lines.find(|line| !line.text.trim().is_empty()) {
+        return Err(line.mismatch("the end of the input"));
+    }
+    Ok(Machine {
+        replacements,
+        medicine_molecule,
+    })
 }
 
-pub fn main(input: &str) -> anyhow::Result<()> {
-    let (replacements, medicine_molecule) = input.split_once("\n\n").unwrap();
-    let replacements: Vec<(&str, &str)> = replacements
-        .lines()
-        .map(|line| line.split_once(" => ").unwrap())
-        .collect();
-    let medicine_molecule = medicine_molecule.trim();
+/// Counts the molecules one replacement away from the medicine, then the
+/// replacements making the medicine from `e`.
+#[derive(Default)]
+pub struct Solver;
 
-    let molecules = apply_replacements(medicine_molecule, &replacements);
+impl Solution for Solver {
+    type Input = Machine;
 
-    println!(
-        "Number of distinct molecules that can be created: {}",
-        molecules.len()
-    );
+    fn parse(&self, input: &str) -> anyhow::Result<Self::Input> {
+        Ok(parse_machine(input)?)
+    }
+
+    fn part1(&self, machine: &Self::Input) -> anyhow::Result<Answer> {
+        let molecules = apply_replacements(&machine.medicine_molecule, &machine.replacements);
+        Ok(molecules.len().into())
+    }
 
-    // Start with the medicine molecule instead of "e" based on the assumptions there are fewer
-    // paths to explore that way and because it's easier to determine fitness.
-    println!(
-        "Fewest steps required to create medicine molecule: {:?}",
-        find_molecule(
-            medicine_molecule,
+    fn part2(&self, machine: &Self::Input) -> anyhow::Result<Answer> {
+        // Start with the medicine molecule instead of "e" based on the assumptions there are fewer
+        // paths to explore that way and because it's easier to determine fitness.
+        let steps = find_molecule(
+            &machine.medicine_molecule,
             "e",
-            &replacements
+            &machine
+                .replacements
                 .iter()
-                .map(|(pattern, replacement)| (*replacement, *pattern))
-                .collect::<Vec<_>>()
+                .map(|(pattern, replacement)| (replacement.clone(), pattern.clone()))
+                .collect::<Vec<_>>(),
         )
-    );
+        .context("medicine molecule cannot be created")?;
+        Ok(steps.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-    Ok(())
+    use aoc_core::solve_part;
+
+    const REPLACEMENTS: &str = "e => H
+e => O
+H => HO
+H => OH
+O => HH";
+
+    #[test]
+    fn test_examples() {
+        let machine = format!("{}\n\nHOH", REPLACEMENTS);
+        assert_eq!(solve_part(&Solver, &machine, 1).unwrap(), Answer::from(4));
+        assert_eq!(solve_part(&Solver, &machine, 2).unwrap(), Answer::from(3));
+        let machine = format!("{}\n\nHOHOHO", REPLACEMENTS);
+        assert_eq!(solve_part(&Solver, &machine, 1).unwrap(), Answer::from(7));
+        assert_eq!(solve_part(&Solver, &machine, 2).unwrap(), Answer::from(6));
+    }
 }
@@ -1,10 +1,18 @@
+//! Day 20: Infinite Elves and Infinite Houses, finding the first house to get
+//! enough presents.
+
+use anyhow::Context;
 use rayon::prelude::*;
 
-fn calculate_presents_part1(house: u32) -> u32 {
+use aoc_core::{Answer, Solution};
+
+/// The presents delivered to `house` by infinitely many elves, each elf
+/// delivering ten times its number to every multiple of it.
+pub fn calculate_presents_part1(house: u32) -> u32 {
     let present_factor = 10;
     let mut presents = 0;
     for elf in 1..=house {
-        let visits_house = house % elf == 0;
+        let visits_house = house.is_multiple_of(elf);
         if visits_house {
             presents += elf * present_factor;
         }
@@ -12,12 +20,14 @@ fn calculate_presents_part1(house: u32) -> u32 {
     presents
 }
 
-fn calculate_presents_part2(house: u32) -> u32 {
+/// The presents delivered to `house` by elves stopping after 50 houses and
+/// delivering eleven times their number.
+pub fn calculate_presents_part2(house: u32) -> u32 {
     let present_factor = 11;
     let max_visits = 50;
     let mut presents = 0;
     for elf in 1..=house {
-        let visits_house = (house % elf == 0)
+        let visits_house = house.is_multiple_of(elf)
             && ((elf..=house)
                 .step_by(elf as usize)
                 .take(max_visits)
@@ -31,29 +41,52 @@ fn calculate_presents_part2(house: u32) -> u32 {
     presents
 }
 
-pub fn main(input: &str) -> anyhow::Result<()> {
-    let expected_presents = input.trim().parse()?;
-    let house = (1..(expected_presents / 10))
-        .into_par_iter()
-        .find_first(|house| calculate_presents_part1(*house) >= expected_presents)
-        .unwrap();
-    println!(
-        "[Part 1]: House {} got {} presents. The first to have >= {} presents.",
-        house,
-        calculate_presents_part1(house),
-        expected_presents
-    );
-
-    let house = (1..(expected_presents / 10))
+/// Returns the first house getting at least `expected_presents`.
+pub fn find_first_house<F>(expected_presents: u32, calculate_presents: F) -> Option<u32>
+where
+    F: Fn(u32) -> u32 + Sync,
+{
+    (1..(expected_presents / 10))
         .into_par_iter()
-        .find_first(|house| calculate_presents_part2(*house) >= expected_presents)
-        .unwrap();
-    println!(
-        "[Part 2]: House {} got {} presents. The first to have >= {} presents.",
-        house,
-        calculate_presents_part2(house),
-        expected_presents
-    );
-
-    Ok(())
+        .find_first(|house| calculate_presents(*house) >= expected_presents)
+}
+
+/// Finds the first house getting at least the given number of presents, then
+/// when each elf stops after 50 houses.
+#[derive(Default)]
+pub struct Solver;
+
+impl Solution for Solver {
+    type Input = u32;
+
+    fn parse(&self, input: &str) -> anyhow::Result<Self::Input> {
+        Ok(input.trim().parse()?)
+    }
+
+    fn part1(&self, expected_presents: &Self::Input) -> anyhow::Result<Answer> {
+        let house = find_first_house(*expected_presents, calculate_presents_part1)
+            .context("no house gets enough presents")?;
+        Ok(house.into())
+    }
+
+    fn part2(&self, expected_presents: &Self::Input) -> anyhow::Result<Answer> {
+        let house = find_first_house(*expected_presents, calculate_presents_part2)
+            .context("no house gets enough presents")?;
+        Ok(house.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use aoc_core::solve_part;
+
+    #[test]
+    fn test_examples() {
+        // The eighth house is the first to get 150 presents, and still gets
+        // more than that once the elves stop after 50 houses.
+        assert_eq!(solve_part(&Solver, "150", 1).unwrap(), Answer::from(8));
+        assert_eq!(solve_part(&Solver, "150", 2).unwrap(), Answer::from(8));
+    }
 }
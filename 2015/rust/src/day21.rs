@@ -1,13 +1,23 @@
+//! Day 21: RPG Simulator 20XX, buying equipment to win a fight against a
+//! boss.
+
 use std::cmp::max;
 
 use anyhow::Context;
 use itertools::Itertools;
 
+use aoc_core::parse::{self, ParseError};
+use aoc_core::{Answer, Solution};
+
+/// The player or the boss, whose stats are given like `Hit Points: 100`.
 #[derive(Debug, Clone)]
-struct Character {
-    hit_points: i32,
-    damage: i32,
-    armor: i32,
+pub struct Character {
+    /// How much damage the character can take before losing.
+    pub hit_points: i32,
+    /// The damage of each attack, before the armor of the other character.
+    pub damage: i32,
+    /// How much damage of each attack is blocked.
+    pub armor: i32,
 }
 
 impl From<&Build> for Character {
@@ -81,26 +91,19 @@ const RINGS: [Item; 6] = [
     Item::new(80, 0, 3),
 ];
 
-fn parse_input(input: &str) -> anyhow::Result<Character> {
-    let mut stats = input.trim().split('\n');
-    let hit_points = stats
-        .next()
-        .context("input end before hit points")?
-        .strip_prefix("Hit Points: ")
-        .context("not hit points when expected")?
-        .parse()?;
-    let damage = stats
-        .next()
-        .context("input end before damage")?
-        .strip_prefix("Damage: ")
-        .context("no damage when expected")?
-        .parse()?;
-    let armor = stats
-        .next()
-        .context("input end before armor")?
-        .strip_prefix("Armor: ")
-        .context("no armor when expected")?
-        .parse()?;
+fn parse_input(input: &str) -> Result<Character, ParseError> {
+    let mut lines = parse::lines(input);
+    let mut stat = |name: &str| -> Result<i32, ParseError> {
+        let expected = format!("`{}: <n>`", name);
+        let line = lines
+            .next()
+            .ok_or_else(|| parse::end_of_input(input, &expected))?;
+        let value = line.strip_prefix(line.text, &format!("{}: ", name), &expected)?;
+        line.parse(value, "a number")
+    };
+    let hit_points = stat("Hit Points")?;
+    let damage = stat("Damage")?;
+    let armor = stat("Armor")?;
     Ok(Character {
         hit_points,
         damage,
@@ -108,38 +111,49 @@ fn parse_input(input: &str) -> anyhow::Result<Character> {
     })
 }
 
-fn play(mut player: Character, mut boss: Character) -> bool {
+/// Fights until one of them dies, returning whether the player wins. The
+/// player attacks first.
+pub fn play(mut player: Character, mut boss: Character) -> bool {
+    let mut rounds = 0;
     for round in 0.. {
         if round % 2 == 0 {
-            //            println!(
-            //                "The player deals {}-{} = {} damage; the boss goes down to {} hit points.",
-            //                player.damage,
-            //                boss.armor,
-            //                player.damage - boss.armor,
-            //                boss.hit_points - max(player.damage - boss.armor, 1)
-            //            );
             boss.hit_points -= max(player.damage - boss.armor, 1);
+            log::trace!(
+                "The player deals {}-{} = {} damage; the boss goes down to {} hit points.",
+                player.damage,
+                boss.armor,
+                player.damage - boss.armor,
+                boss.hit_points
+            );
         } else {
-            //            println!(
-            //                "The boss deals {}-{} = {} damage; the boss goes down to {} hit points.",
-            //                boss.damage,
-            //                player.armor,
-            //                boss.damage - player.armor,
-            //                player.hit_points - max(boss.damage - player.armor, 1)
-            //            );
             player.hit_points -= max(boss.damage - player.armor, 1);
+            log::trace!(
+                "The boss deals {}-{} = {} damage; the player goes down to {} hit points.",
+                boss.damage,
+                player.armor,
+                boss.damage - player.armor,
+                player.hit_points
+            );
         }
 
+        rounds = round + 1;
         if player.hit_points <= 0 || boss.hit_points <= 0 {
             break;
         }
     }
-    player.hit_points > 0
+    let won = player.hit_points > 0;
+    log::debug!(
+        "The player with {} damage and {} armor {} after {} rounds.",
+        player.damage,
+        player.armor,
+        if won { "wins" } else { "loses" },
+        rounds
+    );
+    won
 }
 
-pub fn main(input: &str) -> anyhow::Result<()> {
-    let enemy = parse_input(input)?;
-
+/// Returns all possible builds, ordered by their cost.
+fn builds() -> Vec<Build> {
     let mut builds = vec![];
     for weapon in WEAPONS {
         for armor in ARMOR {
@@ -153,25 +167,71 @@ pub fn main(input: &str) -> anyhow::Result<()> {
                     builds.push(Build {
                         weapon,
                         armor,
-                        rings: rings.into_iter().map(|item| *item).collect(),
+                        rings: rings.into_iter().copied().collect(),
                     });
                 }
             }
         }
     }
     builds.sort_by_key(|build| build.cost());
-    for build in &builds {
-        if play(build.into(), enemy.clone()) {
-            println!("Won fight with {} gold spent.", build.cost());
-            break;
-        }
+    builds
+}
+
+/// Finds the cheapest equipment winning against the boss, then the most
+/// expensive one losing.
+#[derive(Default)]
+pub struct Solver;
+
+impl Solution for Solver {
+    type Input = Character;
+
+    fn parse(&self, input: &str) -> anyhow::Result<Self::Input> {
+        Ok(parse_input(input)?)
     }
 
-    for build in builds.iter().rev() {
-        if !play(build.into(), enemy.clone()) {
-            println!("Lost fight with {} gold spent.", build.cost());
-            break;
-        }
+    fn part1(&self, enemy: &Self::Input) -> anyhow::Result<Answer> {
+        let build = builds()
+            .into_iter()
+            .find(|build| play(build.into(), enemy.clone()))
+            .context("no build wins the fight")?;
+        Ok(build.cost().into())
+    }
+
+    fn part2(&self, enemy: &Self::Input) -> anyhow::Result<Answer> {
+        let build = builds()
+            .into_iter()
+            .rev()
+            .find(|build| !play(build.into(), enemy.clone()))
+            .context("no build loses the fight")?;
+        Ok(build.cost().into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use aoc_core::solve_part;
+
+    #[test]
+    fn test_play() {
+        let player = Character {
+            hit_points: 8,
+            damage: 5,
+            armor: 5,
+        };
+        let boss = Character {
+            hit_points: 12,
+            damage: 7,
+            armor: 2,
+        };
+        assert!(play(player, boss));
+    }
+
+    #[test]
+    fn test_examples() {
+        let boss = "Hit Points: 100\nDamage: 8\nArmor: 2";
+        assert_eq!(solve_part(&Solver, boss, 1).unwrap(), Answer::from(91));
+        assert_eq!(solve_part(&Solver, boss, 2).unwrap(), Answer::from(158));
     }
-    Ok(())
 }
@@ -1,8 +1,15 @@
-use std::collections::VecDeque;
+//! Day 22: Wizard Simulator 20XX, casting spells to win a fight against a
+//! boss while spending the least mana.
+
+use std::mem;
 
 use anyhow::Context;
 
-static SPELLS: &'static [Spell] = &[
+use aoc_core::parse::{self, ParseError};
+use aoc_core::search;
+use aoc_core::{Answer, Solution};
+
+static SPELLS: &[Spell] = &[
     Spell {
         name: "Magic Missile",
         mana_cost: 53,
@@ -55,26 +62,27 @@ static SPELLS: &'static [Spell] = &[
     },
 ];
 
-#[derive(Debug, Clone)]
-struct Boss {
-    hit_points: u32,
-    damage: u32,
+/// The boss, whose stats are given like `Hit Points: 13`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Boss {
+    /// How much damage the boss can take before losing.
+    pub hit_points: u32,
+    /// The damage of each attack, before the armor of the player.
+    pub damage: u32,
 }
 
-fn parse(input: &str) -> anyhow::Result<Boss> {
-    let mut lines = input.lines();
-    let hit_points = lines
-        .next()
-        .context("input ends before hit points")?
-        .strip_prefix("Hit Points: ")
-        .context("unexpected data where hit points were expected")?
-        .parse()?;
-    let damage = lines
-        .next()
-        .context("input ends before damage")?
-        .strip_prefix("Damage: ")
-        .context("unexpected data where damage was expected")?
-        .parse()?;
+fn parse(input: &str) -> Result<Boss, ParseError> {
+    let mut lines = parse::lines(input);
+    let mut stat = |name: &str| -> Result<u32, ParseError> {
+        let expected = format!("`{}: <n>`", name);
+        let line = lines
+            .next()
+            .ok_or_else(|| parse::end_of_input(input, &expected))?;
+        let value = line.strip_prefix(line.text, &format!("{}: ", name), &expected)?;
+        line.parse(value, "a number")
+    };
+    let hit_points = stat("Hit Points")?;
+    let damage = stat("Damage")?;
 
     Ok(Boss { hit_points, damage })
 }
@@ -88,7 +96,7 @@ struct Spell {
     effect: Option<EffectDescription>,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 struct EffectDescription {
     duration: u32,
     armor: u32,
@@ -96,19 +104,23 @@ struct EffectDescription {
     mana: u32,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 struct Effect {
     name: &'static str,
     description: &'static EffectDescription,
     timer: u32,
 }
 
-#[derive(Debug, Clone)]
-struct Player {
-    hit_points: u32,
-    mana_points: u32,
+/// The wizard fighting the boss.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Player {
+    /// How much damage the player can take before losing.
+    pub hit_points: u32,
+    /// The mana left to cast spells with.
+    pub mana_points: u32,
     active_effects: Vec<Effect>,
-    spent_mana: u32,
+    /// The mana spent on spells since the start of the fight.
+    pub spent_mana: u32,
 }
 
 impl Player {
@@ -150,11 +162,16 @@ impl Outcome {
     }
 }
 
-#[derive(Debug, Clone)]
-struct Game {
-    player: Player,
-    boss: Boss,
-    player_turn_penalty: u32,
+/// A fight between the player and the boss.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Game {
+    /// The player, whose turn it is.
+    pub player: Player,
+    /// The boss.
+    pub boss: Boss,
+    /// The hit points the player loses at the start of each of their turns, 1
+    /// in hard mode.
+    pub player_turn_penalty: u32,
 }
 
 impl Game {
@@ -238,67 +255,139 @@ impl Game {
     }
 }
 
-fn find_least_mana_spent_game(initial_game: Game) -> Game {
-    let mut games = VecDeque::from([initial_game]);
-    let mut won_games = vec![];
-    let mut min_spent_mana = u32::MAX;
-    while let Some(game) = games.pop_front() {
-        if min_spent_mana != u32::MAX && game.player.spent_mana > min_spent_mana {
-            // mana spent is too high, let's stop pursuing this game any further
-            continue;
-        }
-        let castable_spells = SPELLS.iter().filter(|spell| game.player.can_cast(spell));
-        // if castable spells is empty, we implicity lose. in that case we do nothing.
-        for spell in castable_spells {
-            let mut game = game.clone();
-            match game.play_round(spell) {
-                Outcome::Win => {
-                    if game.player.spent_mana < min_spent_mana {
-                        min_spent_mana = game.player.spent_mana;
+/// Plays the sequences of spells from `initial_game`, cheapest first,
+/// returning the won game in which the player spent the least mana if any.
+pub fn find_least_mana_spent_game(mut initial_game: Game) -> Option<Game> {
+    // The spent mana is the cost of the path rather than part of the states,
+    // so that games reaching the same state for different mana are merged.
+    let initial_spent_mana = mem::take(&mut initial_game.player.spent_mana);
+    let result = search::dijkstra(
+        initial_game,
+        |game| {
+            // If no spell can be cast, the game is implicitly lost.
+            SPELLS
+                .iter()
+                .filter(|spell| game.player.can_cast(spell))
+                .filter_map(|spell| {
+                    let mut next = game.clone();
+                    match next.play_round(spell) {
+                        Outcome::Loss => None,
+                        Outcome::Win | Outcome::InProgress => {
+                            let cost = mem::take(&mut next.player.spent_mana);
+                            Some((next, cost))
+                        }
                     }
-                    won_games.push(game);
-                }
-                Outcome::Loss => {
-                    // :( Give up and do nothing
-                }
-                Outcome::InProgress => games.push_back(game),
-            };
-        }
-    }
-
-    won_games
-        .into_iter()
-        .min_by(|g1, g2| g1.player.spent_mana.cmp(&g2.player.spent_mana))
-        .unwrap()
+                })
+                .collect::<Vec<_>>()
+        },
+        |game| game.boss.hit_points == 0,
+    );
+    log::debug!(
+        "Played {} of {} games found",
+        result.stats.expanded,
+        result.stats.discovered
+    );
+    result.path.map(|path| {
+        let spent_mana = initial_spent_mana + path.cost;
+        let mut game = path.into_goal();
+        game.player.spent_mana = spent_mana;
+        game
+    })
 }
 
-pub fn main(input: &str) -> anyhow::Result<()> {
-    let boss = parse(input)?;
-    let player = Player {
+/// Returns the player at the start of a fight, with 50 hit points and 500
+/// mana.
+pub fn new_player() -> Player {
+    Player {
         hit_points: 50,
         mana_points: 500,
         active_effects: vec![],
         spent_mana: 0,
-    };
-    let part1_game = find_least_mana_spent_game(Game {
-        player: player.clone(),
-        boss: boss.clone(),
-        player_turn_penalty: 0,
-    });
-    println!(
-        "Least mana spent in won game: {}",
-        part1_game.player.spent_mana
-    );
+    }
+}
 
-    let part2_game = find_least_mana_spent_game(Game {
-        player,
-        boss,
-        player_turn_penalty: 1,
-    });
-    println!(
-        "Least mana spent in won game with penalty: {}",
-        part2_game.player.spent_mana
-    );
+/// Finds the least mana spent winning against the boss, then in hard mode.
+#[derive(Default)]
+pub struct Solver;
+
+impl Solution for Solver {
+    type Input = Boss;
+
+    fn parse(&self, input: &str) -> anyhow::Result<Self::Input> {
+        Ok(parse(input)?)
+    }
+
+    fn part1(&self, boss: &Self::Input) -> anyhow::Result<Answer> {
+        let game = find_least_mana_spent_game(Game {
+            player: new_player(),
+            boss: boss.clone(),
+            player_turn_penalty: 0,
+        })
+        .context("no sequence of spells wins the fight")?;
+        Ok(game.player.spent_mana.into())
+    }
+
+    fn part2(&self, boss: &Self::Input) -> anyhow::Result<Answer> {
+        let game = find_least_mana_spent_game(Game {
+            player: new_player(),
+            boss: boss.clone(),
+            player_turn_penalty: 1,
+        })
+        .context("no sequence of spells wins the fight")?;
+        Ok(game.player.spent_mana.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use aoc_core::solve_part;
 
-    Ok(())
+    #[test]
+    fn test_find_least_mana_spent_game() {
+        // The two fights worked through in the puzzle statement. The first
+        // is won with Poison, then Magic Missile once it has worn the boss
+        // down.
+        let game = Game {
+            player: Player {
+                hit_points: 10,
+                mana_points: 250,
+                ..new_player()
+            },
+            boss: Boss {
+                hit_points: 13,
+                damage: 8,
+            },
+            player_turn_penalty: 0,
+        };
+        assert_eq!(
+            find_least_mana_spent_game(game.clone())
+                .unwrap()
+                .player
+                .spent_mana,
+            226
+        );
+        // The second: Recharge, Shield, Drain, Poison, then Magic Missile.
+        let game = Game {
+            boss: Boss {
+                hit_points: 14,
+                ..game.boss
+            },
+            ..game
+        };
+        assert_eq!(
+            find_least_mana_spent_game(game).unwrap().player.spent_mana,
+            641
+        );
+    }
+
+    #[test]
+    fn test_examples() {
+        // The statement has no example for the default player, so these are
+        // regression values computed by the current code.
+        let boss = "Hit Points: 13\nDamage: 8";
+        assert_eq!(solve_part(&Solver, boss, 1).unwrap(), Answer::from(212));
+        assert_eq!(solve_part(&Solver, boss, 2).unwrap(), Answer::from(212));
+    }
 }
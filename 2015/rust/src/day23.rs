@@ -1,160 +1,209 @@
-use std::str::FromStr;
+//! Day 23: Opening the Turing Lock, running a program on a computer with two
+//! registers.
 
-use anyhow::Context;
+use std::fmt;
+use std::str::FromStr;
 
-#[derive(Debug)]
-struct Computer {
-    register_a: u32,
-    register_b: u32,
-    program_counter: isize,
-    instructions: Vec<Instruction>,
-}
+use aoc_core::machine::{self, Machine, Registers};
+use aoc_core::parse::{self, Line, ParseError};
+use aoc_core::{Answer, Solution};
 
-impl Computer {
-    fn run(&mut self) {
-        loop {
-            let program_counter = match usize::try_from(self.program_counter) {
-                Ok(program_counter) => program_counter,
-                Err(_) => break,
-            };
-            let instruction = match self.instructions.get(program_counter) {
-                Some(instruction) => instruction,
-                None => break,
-            };
-            match instruction {
-                Instruction::Half(Register::A) => {
-                    self.register_a = self.register_a / 2;
-                    self.program_counter += 1;
-                }
-                Instruction::Half(Register::B) => {
-                    self.register_b = self.register_b / 2;
-                    self.program_counter += 1;
-                }
-                Instruction::Triple(Register::A) => {
-                    self.register_a = self.register_a * 3;
-                    self.program_counter += 1;
-                }
-                Instruction::Triple(Register::B) => {
-                    self.register_b = self.register_b * 3;
-                    self.program_counter += 1;
-                }
-                Instruction::Increment(Register::A) => {
-                    self.register_a += 1;
-                    self.program_counter += 1;
-                }
-                Instruction::Increment(Register::B) => {
-                    self.register_b += 1;
-                    self.program_counter += 1;
-                }
-                Instruction::Jump(offset) => {
-                    self.program_counter = self.program_counter + offset;
-                }
-                Instruction::JumpIfEven(register, offset) => {
-                    let value = match register {
-                        Register::A => self.register_a,
-                        Register::B => self.register_b,
-                    };
-                    if value % 2 == 0 {
-                        self.program_counter = self.program_counter + offset;
-                    } else {
-                        self.program_counter += 1;
-                    }
-                }
-                Instruction::JumpIfOne(register, offset) => {
-                    let value = match register {
-                        Register::A => self.register_a,
-                        Register::B => self.register_b,
-                    };
-                    if value == 1 {
-                        self.program_counter = self.program_counter + offset;
-                    } else {
-                        self.program_counter += 1;
-                    }
-                }
-            }
-        }
-    }
-}
+/// The computer running the program, with its registers `a` and `b`.
+pub type Computer = Machine<Instruction>;
 
+/// A register of the computer, `a` or `b`.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
-enum Register {
+pub enum Register {
+    /// `a`, which starts at 0 or at 1 in part 2.
     A,
+    /// `b`, holding the answer.
     B,
 }
 
-impl FromStr for Register {
-    type Err = anyhow::Error;
+impl machine::Register for Register {
+    const ALL: &'static [Self] = &[Self::A, Self::B];
+
+    fn index(self) -> usize {
+        self as usize
+    }
 
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        match s {
-            "a" => Ok(Self::A),
-            "b" => Ok(Self::B),
-            _ => Err(anyhow::anyhow!("invalid register: {}", s)),
+    fn name(self) -> &'static str {
+        match self {
+            Self::A => "a",
+            Self::B => "b",
         }
     }
 }
 
-#[derive(Debug, Clone)]
-enum Instruction {
+impl fmt::Display for Register {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(machine::Register::name(*self))
+    }
+}
+
+/// An instruction of the program, given like `jie a, +4`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Instruction {
+    /// `hlf r`, halving the register.
     Half(Register),
+    /// `tpl r`, tripling the register.
     Triple(Register),
+    /// `inc r`, adding 1 to the register.
     Increment(Register),
+    /// `jmp offset`, jumping relative to the instruction.
     Jump(isize),
+    /// `jie r, offset`, jumping if the register is even.
     JumpIfEven(Register, isize),
+    /// `jio r, offset`, jumping if the register is one.
     JumpIfOne(Register, isize),
 }
 
+/// Parses an instruction like `jie a, +4`.
+pub fn parse_instruction(line: Line) -> Result<Instruction, ParseError> {
+    let register = |text: &str| machine::parse_register(&line, text);
+    let offset = |text: &str| line.parse(text, "an offset");
+    let (mnemonic, operands) = machine::split_instruction(&line);
+    Ok(match (mnemonic, &operands[..]) {
+        ("hlf", [r]) => Instruction::Half(register(r)?),
+        ("tpl", [r]) => Instruction::Triple(register(r)?),
+        ("inc", [r]) => Instruction::Increment(register(r)?),
+        ("jmp", [o]) => Instruction::Jump(offset(o)?),
+        ("jie", [r, o]) => Instruction::JumpIfEven(register(r)?, offset(o)?),
+        ("jio", [r, o]) => Instruction::JumpIfOne(register(r)?, offset(o)?),
+        _ => return Err(line.mismatch("an instruction like `inc a` or `jie a, +4`")),
+    })
+}
+
 impl FromStr for Instruction {
-    type Err = anyhow::Error;
+    type Err = ParseError;
 
     fn from_str(line: &str) -> Result<Self, Self::Err> {
-        match &line[..3] {
-            "hlf" => Ok(Self::Half(line[4..].parse()?)),
-            "tpl" => Ok(Self::Triple(line[4..].parse()?)),
-            "inc" => Ok(Self::Increment(line[4..].parse()?)),
-            "jmp" => Ok(Self::Jump(line[4..].parse()?)),
-            "jie" => {
-                let (register, offset) = line[4..]
-                    .split_once(", ")
-                    .context("invalid jie instruction")?;
-                Ok(Self::JumpIfEven(register.parse()?, offset.parse()?))
+        parse_instruction(Line::new(1, line))
+    }
+}
+
+impl machine::Instruction for Instruction {
+    type Register = Register;
+    type Value = u32;
+
+    const HALTS_BEFORE_START: bool = true;
+
+    fn execute(&self, registers: &mut Registers<Register, u32>) -> isize {
+        match *self {
+            Self::Half(register) => registers[register] /= 2,
+            Self::Triple(register) => registers[register] *= 3,
+            Self::Increment(register) => registers[register] += 1,
+            Self::Jump(offset) => return offset,
+            Self::JumpIfEven(register, offset) => {
+                if registers[register].is_multiple_of(2) {
+                    return offset;
+                }
             }
-            "jio" => {
-                let (register, offset) = line[4..]
-                    .split_once(", ")
-                    .context("invalid jio instruction")?;
-                Ok(Self::JumpIfOne(register.parse()?, offset.parse()?))
+            Self::JumpIfOne(register, offset) => {
+                if registers[register] == 1 {
+                    return offset;
+                }
             }
-            _ => Err(anyhow::anyhow!("invalid instruction: {}", line)),
         }
+        1
     }
 }
 
-pub fn main(input: &str) -> anyhow::Result<()> {
-    let instructions: Vec<Instruction> = input
-        .lines()
-        .map(|line| line.parse())
-        .collect::<Result<_, _>>()?;
-
-    let mut computer = Computer {
-        register_a: 0,
-        register_b: 0,
-        program_counter: 0,
-        instructions: instructions.clone(),
-    };
-    computer.run();
-    println!("[Part 1]: Value in register a: {}", computer.register_a);
-    println!("[Part 1]: Value in register b: {}", computer.register_b);
-
-    let mut computer = Computer {
-        register_a: 1,
-        register_b: 0,
-        program_counter: 0,
-        instructions: instructions.clone(),
-    };
-    computer.run();
-    println!("[Part 2]: Value in register a: {}", computer.register_a);
-    println!("[Part 2]: Value in register b: {}", computer.register_b);
-
-    Ok(())
+impl fmt::Display for Instruction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Half(register) => write!(f, "hlf {}", register),
+            Self::Triple(register) => write!(f, "tpl {}", register),
+            Self::Increment(register) => write!(f, "inc {}", register),
+            Self::Jump(offset) => write!(f, "jmp {:+}", offset),
+            Self::JumpIfEven(register, offset) => write!(f, "jie {}, {:+}", register, offset),
+            Self::JumpIfOne(register, offset) => write!(f, "jio {}, {:+}", register, offset),
+        }
+    }
+}
+
+/// Runs the program, giving register `b` at the end, then when `a` starts at
+/// 1.
+#[derive(Default)]
+pub struct Solver;
+
+impl Solution for Solver {
+    type Input = Vec<Instruction>;
+
+    fn parse(&self, input: &str) -> anyhow::Result<Self::Input> {
+        Ok(parse::parse_lines(input, parse_instruction)?)
+    }
+
+    fn part1(&self, instructions: &Self::Input) -> anyhow::Result<Answer> {
+        let mut computer = Computer::new(instructions.clone());
+        computer.run()?;
+        log::debug!("Executed {} instructions", computer.executed());
+        Ok(computer.register(Register::B).into())
+    }
+
+    fn part2(&self, instructions: &Self::Input) -> anyhow::Result<Answer> {
+        let mut computer = Computer::new(instructions.clone());
+        computer.set_register(Register::A, 1);
+        computer.run()?;
+        log::debug!("Executed {} instructions", computer.executed());
+        Ok(computer.register(Register::B).into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use proptest::prelude::*;
+
+    use aoc_core::solve_part;
+
+    #[test]
+    fn test_examples() {
+        // The jump is only taken in part 2, where a starts at 1.
+        let program = "jio a, +2\ninc b\ninc b";
+        assert_eq!(solve_part(&Solver, program, 1).unwrap(), Answer::from(2));
+        assert_eq!(solve_part(&Solver, program, 2).unwrap(), Answer::from(1));
+
+        // Jumping before the first instruction exits the program too.
+        let program = "inc b\njmp -5";
+        assert_eq!(solve_part(&Solver, program, 1).unwrap(), Answer::from(1));
+    }
+
+    fn register() -> impl Strategy<Value = Register> {
+        prop_oneof![Just(Register::A), Just(Register::B)]
+    }
+
+    fn instruction() -> impl Strategy<Value = Instruction> {
+        prop_oneof![
+            register().prop_map(Instruction::Half),
+            register().prop_map(Instruction::Triple),
+            register().prop_map(Instruction::Increment),
+            any::<isize>().prop_map(Instruction::Jump),
+            (register(), any::<isize>()).prop_map(|(r, offset)| Instruction::JumpIfEven(r, offset)),
+            (register(), any::<isize>()).prop_map(|(r, offset)| Instruction::JumpIfOne(r, offset)),
+        ]
+    }
+
+    proptest! {
+        #[test]
+        fn test_instruction_round_trip(instruction in instruction()) {
+            prop_assert_eq!(
+                instruction.to_string().parse::<Instruction>().ok(),
+                Some(instruction)
+            );
+        }
+
+        #[test]
+        fn test_parse_instruction_never_panics(line in "\\PC*") {
+            let _ = line.parse::<Instruction>();
+        }
+    }
+
+    #[test]
+    fn test_parse_short_lines() {
+        for line in ["", "jm", "inc", "éé", "jmpé"] {
+            assert!(line.parse::<Instruction>().is_err(), "{:?}", line);
+        }
+    }
 }
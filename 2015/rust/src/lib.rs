@@ -0,0 +1,57 @@
+//! Solutions to the puzzles of [Advent of Code 2015](https://adventofcode.com/2015).
+//!
+//! Each day has a module exposing the model its puzzle input is parsed into,
+//! the functions solving the puzzle, and a `Solver` implementing
+//! [`Solution`](aoc_core::Solution) for the `aoc` binary. The circuit of day 7
+//! can for example be evaluated with other wires overridden:
+//!
+//! ```
+//! use std::collections::HashMap;
+//!
+//! use aoc_2015::day07;
+//!
+//! let circuit = day07::parse_circuit("123 -> x\nx AND b -> a").unwrap();
+//! let signals = HashMap::from([("b".to_string(), 0xff)]);
+//! assert_eq!(day07::signal_of_wire(&circuit, "a", signals).unwrap(), 123);
+//! ```
+
+#![warn(missing_docs)]
+
+extern crate anyhow;
+extern crate aoc_core;
+extern crate itertools;
+extern crate json;
+extern crate log;
+extern crate rayon;
+extern crate regex;
+
+// The day modules and `DAYS`, generated by `build.rs` from the `dayNN.rs`
+// files in this directory.
+include!(concat!(env!("OUT_DIR"), "/days.rs"));
+
+/// Entry points for the fuzz targets in `fuzz/`, which feed arbitrary text to
+/// the parsers of the days.
+#[cfg(fuzzing)]
+pub mod fuzzing {
+    use aoc_core::parse::check_round_trip;
+
+    /// Checks that `text`, if it parses as a gift box of day 2, round-trips.
+    pub fn day02_gift_box(text: &str) {
+        check_round_trip(text, str::parse::<crate::day02::GiftBox>);
+    }
+
+    /// Checks that `text`, if it parses as an instruction of day 6, round-trips.
+    pub fn day06_instruction(text: &str) {
+        check_round_trip(text, str::parse::<crate::day06::Instruction>);
+    }
+
+    /// Checks that `text`, if it parses as an instruction of day 7, round-trips.
+    pub fn day07_instruction(text: &str) {
+        check_round_trip(text, str::parse::<crate::day07::Instruction>);
+    }
+
+    /// Checks that `text`, if it parses as an instruction of day 23, round-trips.
+    pub fn day23_instruction(text: &str) {
+        check_round_trip(text, str::parse::<crate::day23::Instruction>);
+    }
+}
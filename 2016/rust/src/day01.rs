@@ -1,120 +1,184 @@
+//! Day 1: No Time for a Taxicab, following turns on a city grid to find the
+//! distance to Easter Bunny HQ.
+
 use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
 
 use anyhow::Context;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-enum Direction {
-    North,
-    East,
-    South,
-    West,
-}
+use aoc_core::geometry::{Direction, Point, Turn};
+use aoc_core::parse::{Line, ParseError};
+use aoc_core::{Answer, Solution};
 
+/// Turning then walking a number of blocks, given like `L5`.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
-enum Turn {
-    Left,
-    Right,
+pub struct Instruction {
+    /// Which way to turn, `L` or `R`.
+    pub turn: Turn,
+    /// How many blocks to walk after turning.
+    pub blocks: i32,
 }
 
-#[derive(Debug)]
-struct Instruction {
-    turn: Turn,
-    blocks: i32,
+impl fmt::Display for Instruction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.turn {
+            Turn::Left => write!(f, "L{}", self.blocks),
+            Turn::Right => write!(f, "R{}", self.blocks),
+        }
+    }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-struct Location {
-    x: i32,
-    y: i32,
+/// Parses instructions separated by commas, like `R2, L3`.
+pub fn parse_instructions(input: &str) -> Result<Vec<Instruction>, ParseError> {
+    let line = Line::new(1, input);
+    line.text
+        .trim()
+        .split(", ")
+        .map(|part| parse_instruction(&line, part))
+        .collect()
 }
 
-impl Location {
-    fn new(x: i32, y: i32) -> Self {
-        Self { x, y }
-    }
-
-    fn distance_to_origin(&self) -> i32 {
-        self.x.abs() + self.y.abs()
-    }
+/// Parses the instruction `part`, a slice of `line`.
+fn parse_instruction(line: &Line, part: &str) -> Result<Instruction, ParseError> {
+    let (turn, blocks) = if let Some(blocks) = part.strip_prefix('L') {
+        (Turn::Left, blocks)
+    } else if let Some(blocks) = part.strip_prefix('R') {
+        (Turn::Right, blocks)
+    } else {
+        return Err(line.error(part, "an instruction like `L5` or `R2`"));
+    };
+    Ok(Instruction {
+        turn,
+        blocks: line.parse(blocks, "a number of blocks")?,
+    })
 }
 
-fn parse_input(input: &str) -> anyhow::Result<Vec<Instruction>> {
-    input.trim().split(", ").map(parse_instruction).collect()
-}
+impl FromStr for Instruction {
+    type Err = ParseError;
 
-fn parse_instruction(s: &str) -> anyhow::Result<Instruction> {
-    match (&s[0..1], &s[1..]) {
-        ("L", blocks) => Ok(Instruction {
-            turn: Turn::Left,
-            blocks: blocks
-                .parse()
-                .context(format!("invalid instruction: {:?}", s))?,
-        }),
-        ("R", blocks) => Ok(Instruction {
-            turn: Turn::Right,
-            blocks: blocks
-                .parse()
-                .context(format!("invalid instruction: {:?}", s))?,
-        }),
-        _ => Err(anyhow::anyhow!("invalid instruction: {:?}", s)),
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_instruction(&Line::new(1, s), s)
     }
 }
 
-fn follow_instructions(instructions: &[Instruction]) -> (i32, HashMap<Location, (u32, u32)>) {
-    let mut facing = Direction::North;
-    let mut location = Location::new(0, 0);
+/// Follows the instructions facing north from the origin, returning the
+/// distance to the final location along with the locations walked through.
+/// Each location maps to the step it was last visited at and how many times it
+/// was visited.
+pub fn follow_instructions(instructions: &[Instruction]) -> (i64, HashMap<Point, (u32, u32)>) {
+    let mut facing = Direction::Up;
+    let mut location = Point::ORIGIN;
     let mut visit_counter = 0;
     let mut visited_locations = HashMap::new();
 
     for instruction in instructions {
-        facing = match (facing, instruction.turn) {
-            (Direction::North, Turn::Left) => Direction::West,
-            (Direction::North, Turn::Right) => Direction::East,
-            (Direction::East, Turn::Left) => Direction::North,
-            (Direction::East, Turn::Right) => Direction::South,
-            (Direction::South, Turn::Left) => Direction::East,
-            (Direction::South, Turn::Right) => Direction::West,
-            (Direction::West, Turn::Left) => Direction::South,
-            (Direction::West, Turn::Right) => Direction::North,
-        };
+        facing = facing.turn(instruction.turn);
         for _ in 0..instruction.blocks {
-            location = match facing {
-                Direction::North => Location::new(location.x, location.y + 1),
-                Direction::East => Location::new(location.x + 1, location.y),
-                Direction::South => Location::new(location.x, location.y - 1),
-                Direction::West => Location::new(location.x - 1, location.y),
-            };
+            location += facing;
             let visits = visited_locations
                 .entry(location)
                 .or_insert((visit_counter, 0));
-            (*visits).0 = visit_counter;
-            (*visits).1 += 1;
+            visits.0 = visit_counter;
+            visits.1 += 1;
             visit_counter += 1;
         }
     }
 
-    (location.distance_to_origin(), visited_locations)
+    (
+        location.manhattan_distance(Point::ORIGIN),
+        visited_locations,
+    )
 }
 
-pub fn main(input: &str) -> anyhow::Result<()> {
-    let instructions = parse_input(input)?;
+/// Finds how far Easter Bunny HQ is at the end of the instructions, then at
+/// the first location visited twice.
+#[derive(Default)]
+pub struct Solver;
+
+impl Solution for Solver {
+    type Input = Vec<Instruction>;
+
+    fn parse(&self, input: &str) -> anyhow::Result<Self::Input> {
+        Ok(parse_instructions(input)?)
+    }
+
+    fn part1(&self, instructions: &Self::Input) -> anyhow::Result<Answer> {
+        let (distance, _) = follow_instructions(instructions);
+        Ok(distance.into())
+    }
+
+    fn part2(&self, instructions: &Self::Input) -> anyhow::Result<Answer> {
+        let (_, visited_locations) = follow_instructions(instructions);
+
+        let mut locations_visited_twice: Vec<(Point, (u32, u32))> = visited_locations
+            .into_iter()
+            .filter(|(_, (_, visits))| *visits == 2)
+            .collect();
+        locations_visited_twice.sort_by_key(|(_, (visit, _))| *visit);
+
+        let first_location_visited_twice = locations_visited_twice
+            .first()
+            .context("no location is visited twice")?
+            .0;
+        Ok(first_location_visited_twice
+            .manhattan_distance(Point::ORIGIN)
+            .into())
+    }
+}
 
-    let (distance, visited_locations) = follow_instructions(&instructions);
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use proptest::prelude::*;
+
+    use aoc_core::solve_part;
+
+    #[test]
+    fn test_examples() {
+        assert_eq!(solve_part(&Solver, "R2, L3", 1).unwrap(), Answer::from(5));
+        assert_eq!(
+            solve_part(&Solver, "R2, R2, R2", 1).unwrap(),
+            Answer::from(2)
+        );
+        assert_eq!(
+            solve_part(&Solver, "R5, L5, R5, R3", 1).unwrap(),
+            Answer::from(12)
+        );
+        assert_eq!(
+            solve_part(&Solver, "R8, R4, R4, R8", 2).unwrap(),
+            Answer::from(4)
+        );
+    }
 
-    println!("Distance from initial location: {distance}");
+    fn instruction() -> impl Strategy<Value = Instruction> {
+        let turn = prop_oneof![Just(Turn::Left), Just(Turn::Right)];
+        (turn, any::<i32>()).prop_map(|(turn, blocks)| Instruction { turn, blocks })
+    }
 
-    let mut locations_visited_twice: Vec<(Location, (u32, u32))> = visited_locations
-        .into_iter()
-        .filter(|(_, (_, visits))| *visits == 2)
-        .collect();
-    locations_visited_twice.sort_by(|location1, location2| (location1.1).0.cmp(&(location2.1).0));
+    proptest! {
+        #[test]
+        fn test_instruction_round_trip(instruction in instruction()) {
+            prop_assert_eq!(instruction.to_string().parse().ok(), Some(instruction));
+        }
 
-    let first_location_visited_twice = locations_visited_twice.first().unwrap().0;
+        #[test]
+        fn test_parse_instruction_never_panics(s in "\\PC*") {
+            let _ = s.parse::<Instruction>();
+        }
+    }
 
-    println!(
-        "Distance from first location visited twice: {}",
-        first_location_visited_twice.distance_to_origin()
-    );
+    #[test]
+    fn test_parse_short_instructions() {
+        for s in ["", "L", "é5", "X"] {
+            assert!(s.parse::<Instruction>().is_err(), "{:?}", s);
+        }
 
-    Ok(())
+        let error = parse_instructions("R2, X").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "line 1, column 5: expected an instruction like `L5` or `R2`, found \"X\""
+        );
+    }
 }
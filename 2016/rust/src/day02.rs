@@ -1,81 +1,115 @@
-static KEYPAD1: [[char; 3]; 3] = [['1', '2', '3'], ['4', '5', '6'], ['7', '8', '9']];
-
-static KEYPAD2: [[char; 5]; 5] = [
-    ['0', '0', '1', '0', '0'],
-    ['0', '2', '3', '4', '0'],
-    ['5', '6', '7', '8', '9'],
-    ['0', 'A', 'B', 'C', '0'],
-    ['0', '0', 'D', '0', '0'],
-];
-
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-enum Move {
-    Up,
-    Right,
-    Down,
-    Left,
+//! Day 2: Bathroom Security, moving a finger over a keypad to find the
+//! bathroom code.
+
+use anyhow::Context;
+
+use aoc_core::geometry::{Direction, Point};
+use aoc_core::grid::Grid;
+use aoc_core::parse::{self, ParseError};
+use aoc_core::{Answer, Solution};
+
+/// The keypad of part 1.
+pub const KEYPAD1: &str = "123\n456\n789";
+
+/// The keypad of part 2, spaces mark the places without a button.
+pub const KEYPAD2: &str = "  1  \n 234 \n56789\n ABC \n  D  ";
+
+/// A keypad, `None` where there is no button.
+pub type Keypad = Grid<Option<char>>;
+
+/// Parses a map of the buttons of a keypad, with spaces where there are none.
+pub fn parse_keypad(map: &str) -> Result<Keypad, ParseError> {
+    Grid::parse(
+        map,
+        |c| Some((c != ' ').then_some(c)),
+        "a button or a space",
+    )
 }
 
-fn parse_input(input: &str) -> anyhow::Result<Vec<Vec<Move>>> {
-    input
-        .trim()
-        .lines()
-        .map(|line| {
-            line.chars()
-                .map(|c| match c {
-                    'U' => Ok(Move::Up),
-                    'R' => Ok(Move::Right),
-                    'D' => Ok(Move::Down),
-                    'L' => Ok(Move::Left),
-                    _ => Err(anyhow::anyhow!("invalid character")),
+/// Parses the moves of the finger, given as `U`, `R`, `D` or `L`, a line for
+/// each button to press.
+pub fn parse_instructions(input: &str) -> Result<Vec<Vec<Direction>>, ParseError> {
+    parse::parse_lines(input.trim_end(), |line| {
+        line.text
+            .char_indices()
+            .map(|(i, c)| {
+                Direction::from_letter(c).ok_or_else(|| {
+                    line.error(&line.text[i..i + c.len_utf8()], "`U`, `R`, `D` or `L`")
                 })
-                .collect()
-        })
-        .collect()
+            })
+            .collect()
+    })
 }
 
-fn follow_instructions<const N: usize>(
-    keypad: [[char; N]; N],
-    initial_position: (usize, usize),
-    instructions: &[Vec<Move>],
-) -> String {
-    let (mut y, mut x) = initial_position;
+/// Moves from the button `5`, returning the code made of the buttons pressed
+/// at the end of each line. Moves towards a place without a button are
+/// ignored.
+pub fn follow_instructions(
+    keypad: &Keypad,
+    instructions: &[Vec<Direction>],
+) -> anyhow::Result<String> {
+    let (x, y) = keypad
+        .find(|button| *button == Some('5'))
+        .context("the keypad has no button 5")?;
+    let mut position = Point::new(x as i64, y as i64);
     let mut code = String::new();
     for button in instructions {
-        for m in button {
-            match m {
-                Move::Up if y > 0 && keypad[y - 1][x] != '0' => {
-                    y -= 1;
-                }
-                Move::Right if x < keypad.len() - 1 && keypad[y][x + 1] != '0' => {
-                    x += 1;
-                }
-                Move::Down if y < keypad.len() - 1 && keypad[y + 1][x] != '0' => {
-                    y += 1;
-                }
-                Move::Left if x > 0 && keypad[y][x - 1] != '0' => {
-                    x -= 1;
-                }
-                _ => {}
-            };
+        for direction in button {
+            if let Some(Some(_)) = keypad.get_point(position + *direction) {
+                position += *direction;
+            }
         }
 
-        code.push(keypad[y][x]);
+        code.extend(keypad.get_point(position).copied().flatten());
     }
-    code
+    Ok(code)
 }
 
-pub fn main(input: &str) -> anyhow::Result<()> {
-    let instructions = parse_input(input)?;
+/// Finds the bathroom code on the keypad imagined at first, then on the actual
+/// one.
+#[derive(Default)]
+pub struct Solver;
+
+impl Solution for Solver {
+    type Input = Vec<Vec<Direction>>;
 
-    println!(
-        "Code for imagined keypad layout: {}",
-        follow_instructions(KEYPAD1, (1, 1), &instructions)
-    );
-    println!(
-        "Code for actual keypad layout: {}",
-        follow_instructions(KEYPAD2, (2, 0), &instructions)
-    );
+    fn parse(&self, input: &str) -> anyhow::Result<Self::Input> {
+        Ok(parse_instructions(input)?)
+    }
 
-    Ok(())
+    fn part1(&self, instructions: &Self::Input) -> anyhow::Result<Answer> {
+        let keypad = parse_keypad(KEYPAD1)?;
+        Ok(follow_instructions(&keypad, instructions)?.into())
+    }
+
+    fn part2(&self, instructions: &Self::Input) -> anyhow::Result<Answer> {
+        let keypad = parse_keypad(KEYPAD2)?;
+        Ok(follow_instructions(&keypad, instructions)?.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use aoc_core::solve_part;
+
+    #[test]
+    fn test_examples() {
+        let instructions = "ULL\nRRDDD\nLURDL\nUUUUD";
+        assert_eq!(
+            solve_part(&Solver, instructions, 1).unwrap(),
+            Answer::from("1985")
+        );
+        assert_eq!(
+            solve_part(&Solver, instructions, 2).unwrap(),
+            Answer::from("5DB3")
+        );
+
+        let error = parse_instructions("ULL\nRRXD").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "line 2, column 3: expected `U`, `R`, `D` or `L`, found \"X\""
+        );
+    }
 }
@@ -1,24 +1,28 @@
-fn parse_input(input: &str) -> Vec<(u32, u32, u32)> {
-    input
-        .lines()
-        .map(|line| {
-            let (length1, remaining) = line.trim().split_once(' ').unwrap();
-            let (length2, remaining) = remaining.trim().split_once(' ').unwrap();
-            let length3 = remaining.trim();
-            (
-                length1.parse().unwrap(),
-                length2.parse().unwrap(),
-                length3.parse().unwrap(),
-            )
-        })
-        .collect()
+//! Day 3: Squares With Three Sides, counting the possible triangles among
+//! lists of side lengths.
+
+use aoc_core::parse::{self, Line, ParseError};
+use aoc_core::{Answer, Solution};
+
+fn parse_triangle(line: Line) -> Result<(u32, u32, u32), ParseError> {
+    match line.text.split_whitespace().collect::<Vec<_>>()[..] {
+        [length1, length2, length3] => Ok((
+            line.parse(length1, "a side length")?,
+            line.parse(length2, "a side length")?,
+            line.parse(length3, "a side length")?,
+        )),
+        _ => Err(line.mismatch("three side lengths")),
+    }
 }
 
-fn is_triangle((a, b, c): (u32, u32, u32)) -> bool {
+/// Whether the sides can make a triangle, i.e. each is shorter than the sum
+/// of the two others.
+pub fn is_triangle((a, b, c): (u32, u32, u32)) -> bool {
     (a + b > c) && (b + c > a) && (c + a > b)
 }
 
-fn regroup(triangles: &[(u32, u32, u32)]) -> Vec<(u32, u32, u32)> {
+/// Reads the triangles by columns, three rows at a time, as in part 2.
+pub fn regroup(triangles: &[(u32, u32, u32)]) -> Vec<(u32, u32, u32)> {
     let mut result = vec![];
     for i in (2..triangles.len()).step_by(3) {
         let x = triangles[i - 2];
@@ -31,21 +35,50 @@ fn regroup(triangles: &[(u32, u32, u32)]) -> Vec<(u32, u32, u32)> {
     result
 }
 
-pub fn main(input: &str) -> anyhow::Result<()> {
-    let triangles = parse_input(input);
-    let possible_triangle_count = triangles
-        .iter()
-        .filter(|triangle| is_triangle(**triangle))
-        .count();
+/// Counts the possible triangles given by rows, then by columns.
+#[derive(Default)]
+pub struct Solver;
 
-    println!("Possible triangles (horizontal): {possible_triangle_count}");
+impl Solution for Solver {
+    type Input = Vec<(u32, u32, u32)>;
 
-    let possible_triangle_count = regroup(&triangles)
-        .iter()
-        .filter(|triangle| is_triangle(**triangle))
-        .count();
+    fn parse(&self, input: &str) -> anyhow::Result<Self::Input> {
+        Ok(parse::parse_lines(input, parse_triangle)?)
+    }
 
-    println!("Possible triangles (vertical): {possible_triangle_count}");
+    fn part1(&self, triangles: &Self::Input) -> anyhow::Result<Answer> {
+        let possible_triangle_count = triangles
+            .iter()
+            .filter(|triangle| is_triangle(**triangle))
+            .count();
+        Ok(possible_triangle_count.into())
+    }
 
-    Ok(())
+    fn part2(&self, triangles: &Self::Input) -> anyhow::Result<Answer> {
+        let possible_triangle_count = regroup(triangles)
+            .iter()
+            .filter(|triangle| is_triangle(**triangle))
+            .count();
+        Ok(possible_triangle_count.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use aoc_core::solve_part;
+
+    #[test]
+    fn test_examples() {
+        assert_eq!(solve_part(&Solver, "5 10 25", 1).unwrap(), Answer::from(0));
+        let triangles = "101 301 501
+102 302 502
+103 303 503
+201 401 601
+202 402 602
+203 403 603";
+        assert_eq!(solve_part(&Solver, triangles, 1).unwrap(), Answer::from(3));
+        assert_eq!(solve_part(&Solver, triangles, 2).unwrap(), Answer::from(6));
+    }
 }
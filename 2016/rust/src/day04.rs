@@ -1,17 +1,35 @@
+//! Day 4: Security Through Obscurity, telling real rooms from decoys by
+//! their checksum and decrypting their names.
+
 use std::collections::HashMap;
 use std::fmt;
 
+use anyhow::Context;
+
+use aoc_core::parse::{self, Line, ParseError};
+use aoc_core::{Answer, Solution};
+
+/// A room given like `aaaaa-bbb-z-y-x-123[abxyz]`.
 #[derive(Debug)]
-struct Room {
-    encrypted_name: String,
-    sector_id: u32,
-    checksum: String,
+pub struct Room {
+    /// Lowercase letters separated by dashes.
+    pub encrypted_name: String,
+    /// The number the letters of the name are shifted by.
+    pub sector_id: u32,
+    /// The five most common letters of the name of a real room.
+    pub checksum: String,
 }
 
 impl Room {
-    fn name(&self) -> String {
+    /// Returns the decrypted name, shifted by the sector ID.
+    pub fn name(&self) -> String {
         decrypt(&self.encrypted_name, self.sector_id)
     }
+
+    /// Whether the checksum is right, i.e. the room isn't a decoy.
+    pub fn is_real(&self) -> bool {
+        is_real(self)
+    }
 }
 
 impl fmt::Display for Room {
@@ -24,21 +42,38 @@ impl fmt::Display for Room {
     }
 }
 
-fn parse_input(input: &str) -> Vec<Room> {
-    input.lines().map(parse_room).collect()
+/// Parses one room per line.
+pub fn parse_rooms(input: &str) -> Result<Vec<Room>, ParseError> {
+    parse::parse_lines(input, parse_room)
 }
 
-fn parse_room(line: &str) -> Room {
-    let (left, checksum) = line.strip_suffix(']').unwrap().split_once('[').unwrap();
-    let (encrypted_name, sector_id) = left.rsplit_once('-').unwrap();
-    Room {
+fn parse_room(line: Line) -> Result<Room, ParseError> {
+    const EXPECTED: &str = "`<encrypted-name>-<sector id>[<checksum>]`";
+    let left = line
+        .text
+        .strip_suffix(']')
+        .ok_or_else(|| line.mismatch(EXPECTED))?;
+    let (left, checksum) = line.split_once(left, "[", EXPECTED)?;
+    let (encrypted_name, sector_id) = left
+        .rsplit_once('-')
+        .ok_or_else(|| line.error(left, "`<encrypted-name>-<sector id>`"))?;
+    let invalid = encrypted_name
+        .char_indices()
+        .find(|(_, c)| *c != '-' && !c.is_ascii_lowercase());
+    if let Some((i, c)) = invalid {
+        let c = &encrypted_name[i..i + c.len_utf8()];
+        return Err(line.error(c, "a lowercase letter or a dash"));
+    }
+    Ok(Room {
         encrypted_name: encrypted_name.to_string(),
-        sector_id: sector_id.parse().unwrap(),
+        sector_id: line.parse(sector_id, "a sector ID")?,
         checksum: checksum.to_string(),
-    }
+    })
 }
 
-fn calculate_checksum(encrypted_name: &str) -> String {
+/// Returns the five most common letters of the name, ties broken
+/// alphabetically.
+pub fn calculate_checksum(encrypted_name: &str) -> String {
     let mut char_counts = HashMap::new();
     for c in encrypted_name.chars() {
         if c == '-' {
@@ -72,30 +107,59 @@ fn rotate_char(mut c: char, n: u32) -> char {
     c
 }
 
-fn decrypt(encrypted_name: &str, n: u32) -> String {
+/// Shifts each letter of the name `n` times through the alphabet.
+pub fn decrypt(encrypted_name: &str, n: u32) -> String {
     encrypted_name
         .chars()
         .map(|c| if c == '-' { c } else { rotate_char(c, n) })
         .collect()
 }
 
-pub fn main(input: &str) -> anyhow::Result<()> {
-    let rooms = parse_input(input);
-    let real_rooms: Vec<&Room> = rooms.iter().filter(|room| is_real(room)).collect();
+/// Sums the sector IDs of the real rooms, then finds the one storing the North
+/// Pole objects.
+#[derive(Default)]
+pub struct Solver;
 
-    println!(
-        "Sum of sector IDs of the real rooms: {}",
-        real_rooms.iter().map(|room| room.sector_id).sum::<u32>()
-    );
+impl Solution for Solver {
+    type Input = Vec<Room>;
+
+    fn parse(&self, input: &str) -> anyhow::Result<Self::Input> {
+        Ok(parse_rooms(input)?)
+    }
 
-    println!(
-        "Sector ID of northpole-object-storage: {}",
-        rooms
+    fn part1(&self, rooms: &Self::Input) -> anyhow::Result<Answer> {
+        let sector_ids: u32 = rooms
+            .iter()
+            .filter(|room| room.is_real())
+            .map(|room| room.sector_id)
+            .sum();
+        Ok(sector_ids.into())
+    }
+
+    fn part2(&self, rooms: &Self::Input) -> anyhow::Result<Answer> {
+        let room = rooms
             .iter()
             .find(|room| room.name() == "northpole-object-storage")
-            .unwrap()
-            .sector_id
-    );
+            .context("northpole-object-storage not found")?;
+        Ok(room.sector_id.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-    Ok(())
+    use aoc_core::solve_part;
+
+    #[test]
+    fn test_examples() {
+        let rooms = "aaaaa-bbb-z-y-x-123[abxyz]
+a-b-c-d-e-f-g-h-987[abcde]
+not-a-real-room-404[oarel]
+totally-real-room-200[decoy]";
+        assert_eq!(solve_part(&Solver, rooms, 1).unwrap(), Answer::from(1514));
+        // "northpole-object-storage", encrypted with its sector ID.
+        let rooms = "aaaaa-bbb-z-y-x-123[abxyz]\nmnqsgonkd-naidbs-rsnqzfd-27[abcde]";
+        assert_eq!(solve_part(&Solver, rooms, 2).unwrap(), Answer::from(27));
+    }
 }
@@ -1,63 +1,85 @@
-use std::io::Write;
+//! Day 5: How About a Nice Game of Chess?, finding a door password in the
+//! MD5 hashes starting with five zeroes.
 
-use md5::{Digest, Md5};
+use aoc_core::hash::{hex_digit, leading_zeros, Md5Hash, Miner};
+use aoc_core::{Answer, Solution};
 
-fn to_md5_hex(s: &str) -> String {
-    use std::fmt::Write;
-    let mut hasher = Md5::new();
-    hasher.update(s.as_bytes());
-    let result = hasher.finalize();
+/// Returns the indexes from `start` on whose hashes, appended to the door ID,
+/// start with five zeroes, along with those hashes.
+fn interesting_hashes(miner: &Miner, start: u64) -> impl Iterator<Item = (u64, Md5Hash)> + '_ {
+    miner.mine(start, |hash| leading_zeros(hash) >= 5)
+}
 
-    let mut hash = String::new();
-    for byte in result {
-        write!(&mut hash, "{:02x}", byte).unwrap();
-    }
-    hash
+fn to_hex_char(digit: u8) -> char {
+    char::from_digit(digit.into(), 16).unwrap()
 }
 
-fn find_first_door_password(door_id: &str) {
-    let mut password = String::new();
-    let mut indixes = 0..;
-    while password.len() < 8 {
-        let s = format!("{}{}", door_id, indixes.next().unwrap());
-        let h = to_md5_hex(s.as_str());
-        if &h[0..5] == "00000" {
-            let c = h.chars().nth(5).unwrap();
-            password.push(c);
-            print!("\rFirst door password: {}", password);
-            std::io::stdout().flush().unwrap();
-        }
-    }
-    println!();
+/// Returns the password made of the sixth hexadecimal digit of the first eight
+/// hashes.
+pub fn find_first_door_password(door_id: &str) -> String {
+    let miner = Miner::new(door_id);
+    interesting_hashes(&miner, 0)
+        .take(8)
+        .map(|(_, hash)| to_hex_char(hex_digit(&hash, 5)))
+        .collect()
 }
 
-fn find_second_door_password(door_id: &str) {
-    let mut password = vec!['_', '_', '_', '_', '_', '_', '_', '_'];
-    let mut indixes = 1..;
-    while password.iter().any(|c| *c == '_') {
-        let s = format!("{}{}", door_id, indixes.next().unwrap());
-        let h = to_md5_hex(s.as_str());
-        if &h[0..5] == "00000" {
-            if let Some(position) = h.chars().nth(5).unwrap().to_digit(10) {
-                if let Some(c) = password.get_mut(position as usize) {
-                    if *c == '_' {
-                        *c = h.chars().nth(6).unwrap();
-                    }
-                }
+/// Returns the password where the sixth digit of the hashes gives the position
+/// of the seventh, keeping the first one found for each position.
+pub fn find_second_door_password(door_id: &str) -> String {
+    let miner = Miner::new(door_id);
+    let mut password = ['_'; 8];
+    let mut hashes = interesting_hashes(&miner, 1);
+    while password.contains(&'_') {
+        let (nonce, hash) = hashes.next().expect("the hits never run out");
+        let position = hex_digit(&hash, 5);
+        if let Some(c) = password.get_mut(usize::from(position)) {
+            if *c == '_' {
+                *c = to_hex_char(hex_digit(&hash, 6));
+                log::debug!("Found the digit at {} with {}", position, nonce);
             }
-            print!(
-                "\rSecond door password: {}",
-                password.iter().collect::<String>()
-            );
-            std::io::stdout().flush().unwrap();
         }
     }
-    println!();
+    password.iter().collect()
 }
 
-pub fn main(input: &str) -> anyhow::Result<()> {
-    find_first_door_password(input);
-    find_second_door_password(input);
+/// Finds the password of the first door, then of the second one, whose hashes
+/// also give the position of each character.
+#[derive(Default)]
+pub struct Solver;
 
-    Ok(())
+impl Solution for Solver {
+    type Input = String;
+
+    fn parse(&self, input: &str) -> anyhow::Result<Self::Input> {
+        Ok(input.trim().to_string())
+    }
+
+    fn part1(&self, door_id: &Self::Input) -> anyhow::Result<Answer> {
+        Ok(find_first_door_password(door_id).into())
+    }
+
+    fn part2(&self, door_id: &Self::Input) -> anyhow::Result<Answer> {
+        Ok(find_second_door_password(door_id).into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use aoc_core::solve_part;
+
+    #[test]
+    #[ignore = "hashes millions of strings, run it with `cargo test --release -- --ignored`"]
+    fn test_examples() {
+        assert_eq!(
+            solve_part(&Solver, "abc", 1).unwrap(),
+            Answer::from("18f47a30")
+        );
+        assert_eq!(
+            solve_part(&Solver, "abc", 2).unwrap(),
+            Answer::from("05ace8e3")
+        );
+    }
 }
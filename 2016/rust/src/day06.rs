@@ -1,52 +1,84 @@
+//! Day 6: Signals and Noise, recovering a message from repeated noisy
+//! copies.
+
 use std::collections::HashMap;
 
-pub fn main(input: &str) -> anyhow::Result<()> {
-    let messages: Vec<String> = input.lines().map(|line| line.to_string()).collect();
+use aoc_core::{Answer, Solution};
 
-    let mut counts: Vec<HashMap<char, usize>> = messages
-        .first()
-        .unwrap()
-        .chars()
-        .map(|_| HashMap::new())
-        .collect();
+/// Counts the occurrences of each character in every column of the messages.
+pub fn count_characters(messages: &[String]) -> Vec<HashMap<char, usize>> {
+    let mut counts: Vec<HashMap<char, usize>> = match messages.first() {
+        Some(message) => message.chars().map(|_| HashMap::new()).collect(),
+        None => vec![],
+    };
 
-    for message in &messages {
+    for message in messages {
         for (c, column_counts) in message.chars().zip(counts.iter_mut()) {
             *column_counts.entry(c).or_insert(0) += 1;
         }
     }
 
-    let error_corrected_message_max: String = counts
-        .iter()
-        .map(|column_counts| {
-            column_counts
-                .iter()
-                .max_by_key(|(_, count)| *count)
-                .unwrap()
-                .0
-        })
-        .collect();
-
-    println!(
-        "Error-corrected message (max): {}",
-        error_corrected_message_max
-    );
-
-    let error_corrected_message_min: String = counts
-        .iter()
-        .map(|column_counts| {
-            column_counts
-                .iter()
-                .min_by_key(|(_, count)| *count)
-                .unwrap()
-                .0
-        })
-        .collect();
-
-    println!(
-        "Error-corrected message (min): {}",
-        error_corrected_message_min
-    );
-
-    Ok(())
+    counts
+}
+
+/// Recovers the message from the most common letters, then from the least
+/// common.
+#[derive(Default)]
+pub struct Solver;
+
+impl Solution for Solver {
+    type Input = Vec<String>;
+
+    fn parse(&self, input: &str) -> anyhow::Result<Self::Input> {
+        Ok(input.lines().map(|line| line.to_string()).collect())
+    }
+
+    fn part1(&self, messages: &Self::Input) -> anyhow::Result<Answer> {
+        let error_corrected_message_max: String = count_characters(messages)
+            .iter()
+            .map(|column_counts| {
+                column_counts
+                    .iter()
+                    .max_by_key(|(_, count)| *count)
+                    .unwrap()
+                    .0
+            })
+            .collect();
+        Ok(error_corrected_message_max.into())
+    }
+
+    fn part2(&self, messages: &Self::Input) -> anyhow::Result<Answer> {
+        let error_corrected_message_min: String = count_characters(messages)
+            .iter()
+            .map(|column_counts| {
+                column_counts
+                    .iter()
+                    .min_by_key(|(_, count)| *count)
+                    .unwrap()
+                    .0
+            })
+            .collect();
+        Ok(error_corrected_message_min.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use aoc_core::solve_part;
+
+    #[test]
+    fn test_examples() {
+        let messages = "eedadn\ndrvtee\neandsr\nraavrd\natevrs\ntsrnev\nsdttsa\nrasrtv
+nssdts\nntnada\nsvetve\ntesnvt\nvntsnd\nvrdear\ndvrsen\nenarar";
+        assert_eq!(
+            solve_part(&Solver, messages, 1).unwrap(),
+            Answer::from("easter")
+        );
+        assert_eq!(
+            solve_part(&Solver, messages, 2).unwrap(),
+            Answer::from("advent")
+        );
+    }
 }
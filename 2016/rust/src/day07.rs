@@ -1,5 +1,10 @@
+//! Day 7: Internet Protocol Version 7, finding the IPv7 addresses that
+//! support TLS and SSL.
+
 use std::collections::HashSet;
 
+use aoc_core::{Answer, Solution};
+
 fn has_abba(s: &str) -> bool {
     for i in 4..=s.len() {
         let sequence: Vec<char> = s[i - 4..i].chars().collect();
@@ -10,13 +15,22 @@ fn has_abba(s: &str) -> bool {
     false
 }
 
+/// A part of an IPv7 address, hypernet sequences being the ones within square
+/// brackets.
 #[derive(Debug)]
-enum Sequence {
+pub enum Sequence {
+    /// A sequence outside of square brackets.
     Supernet(String),
+    /// A sequence within square brackets.
     Hypernet(String),
 }
 
-fn parse_ipv7_address(mut address: &str) -> Vec<Sequence> {
+/// Splits an address like `abba[mnop]qrst` into its sequences.
+///
+/// # Panics
+///
+/// If a square bracket isn't closed.
+pub fn parse_ipv7_address(mut address: &str) -> Vec<Sequence> {
     let mut result = vec![];
     let mut in_hypernet = false;
     loop {
@@ -38,7 +52,8 @@ fn parse_ipv7_address(mut address: &str) -> Vec<Sequence> {
     result
 }
 
-fn supports_tls(address: &[Sequence]) -> bool {
+/// Whether a supernet sequence has an ABBA and no hypernet sequence has one.
+pub fn supports_tls(address: &[Sequence]) -> bool {
     let mut result = false;
     for sequence in address {
         match sequence {
@@ -52,7 +67,8 @@ fn supports_tls(address: &[Sequence]) -> bool {
     result
 }
 
-fn supports_ssl(address: &[Sequence]) -> bool {
+/// Whether an ABA of a supernet sequence has its BAB in a hypernet sequence.
+pub fn supports_ssl(address: &[Sequence]) -> bool {
     let mut abas = HashSet::new();
     let mut babs = HashSet::new();
     for sequence in address {
@@ -79,32 +95,52 @@ fn get_babs(s: &str) -> HashSet<(char, char)> {
     get_abas(s).into_iter().map(|(b, a)| (a, b)).collect()
 }
 
-pub fn main(input: &str) -> anyhow::Result<()> {
-    let addresses: Vec<Vec<Sequence>> = input.lines().map(parse_ipv7_address).collect();
+/// Counts the addresses supporting TLS, then those supporting SSL.
+#[derive(Default)]
+pub struct Solver;
 
-    let addresses_supporting_tls = addresses
-        .iter()
-        .filter(|address| supports_tls(address))
-        .count();
-    println!("{} addresses support TLS", addresses_supporting_tls);
+impl Solution for Solver {
+    type Input = Vec<Vec<Sequence>>;
 
-    let addresses_supporting_ssl = addresses
-        .iter()
-        .filter(|address| supports_ssl(address))
-        .count();
-    println!("{} addresses support SSL", addresses_supporting_ssl);
+    fn parse(&self, input: &str) -> anyhow::Result<Self::Input> {
+        Ok(input.lines().map(parse_ipv7_address).collect())
+    }
+
+    fn part1(&self, addresses: &Self::Input) -> anyhow::Result<Answer> {
+        let addresses_supporting_tls = addresses
+            .iter()
+            .filter(|address| supports_tls(address))
+            .count();
+        Ok(addresses_supporting_tls.into())
+    }
 
-    Ok(())
+    fn part2(&self, addresses: &Self::Input) -> anyhow::Result<Answer> {
+        let addresses_supporting_ssl = addresses
+            .iter()
+            .filter(|address| supports_ssl(address))
+            .count();
+        Ok(addresses_supporting_ssl.into())
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    use aoc_core::solve_part;
+
     #[test]
     fn test_has_abba() {
         assert!(has_abba("xabba"));
         assert!(has_abba("abbax"));
         assert!(has_abba("abba"));
     }
+
+    #[test]
+    fn test_examples() {
+        let addresses = "abba[mnop]qrst\nabcd[bddb]xyyx\naaaa[qwer]tyui\nioxxoj[asdfgh]zxcvbn";
+        assert_eq!(solve_part(&Solver, addresses, 1).unwrap(), Answer::from(2));
+        let addresses = "aba[bab]xyz\nxyx[xyx]xyx\naaa[kek]eke\nzazbz[bzb]cdb";
+        assert_eq!(solve_part(&Solver, addresses, 2).unwrap(), Answer::from(3));
+    }
 }
@@ -1,117 +1,250 @@
-use std::collections::VecDeque;
+//! Day 8: Two-Factor Authentication, drawing on a small screen by lighting
+//! and rotating pixels.
+
 use std::fmt;
+use std::time::Duration;
 
-const SCREEN_WIDTH: usize = 50;
-const SCREEN_HEIGHT: usize = 6;
+use aoc_core::grid::Grid;
+use aoc_core::parse::{self, Line, ParseError};
+use aoc_core::render::{self, Image, Renderer};
+use aoc_core::{params, Answer, Param, Solution};
 
+/// The screen, whose pixels are lit when `true`.
 #[derive(Debug)]
-struct Screen {
-    pixels: [[bool; SCREEN_WIDTH]; SCREEN_HEIGHT],
+pub struct Screen {
+    /// The pixels, from the top left.
+    pub pixels: Grid<bool>,
 }
 
 impl Screen {
-    fn new() -> Self {
+    fn new(width: usize, height: usize) -> Self {
         Self {
-            pixels: [[false; SCREEN_WIDTH]; SCREEN_HEIGHT],
+            pixels: Grid::new(width, height, false),
         }
     }
 
-    fn turn_on(&mut self, width: usize, height: usize) {
-        for row in 0..height {
-            for pixel in 0..width {
-                self.pixels[row][pixel] = true;
-            }
-        }
+    fn image(&self) -> Image {
+        let palette = [render::BLACK, render::GREEN];
+        Image::from_fn(
+            self.pixels.width(),
+            self.pixels.height(),
+            &palette,
+            |x, y| u8::from(self.pixels[(x, y)]),
+        )
     }
 
-    fn rotate_row(&mut self, row: usize, k: usize) {
-        let mut rotated: VecDeque<bool> = self.pixels[row].iter().copied().collect();
-        rotated.rotate_right(k);
-        for (i, pixel) in rotated.into_iter().enumerate() {
-            self.pixels[row][i] = pixel;
-        }
+    /// Counts the lit pixels.
+    pub fn lit_pixels(&self) -> usize {
+        self.pixels.cells().filter(|pixel| **pixel).count()
     }
+}
 
-    fn rotate_column(&mut self, column: usize, k: usize) {
-        let mut rotated: VecDeque<bool> = self.pixels.iter().map(|row| row[column]).collect();
-        rotated.rotate_right(k);
-        for (row, pixel) in rotated.into_iter().enumerate() {
-            self.pixels[row][column] = pixel;
-        }
+impl fmt::Display for Screen {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let pixels = self.pixels.map(|pixel| if *pixel { '#' } else { ' ' });
+        write!(f, "{}", pixels)
     }
+}
 
-    fn lit_pixels(&self) -> usize {
-        self.pixels
-            .iter()
-            .map(|row| row.iter().filter(|pixel| **pixel).count())
-            .sum()
+/// An operation on the screen, given like `rect 3x2`, `rotate row y=0 by 4` or
+/// `rotate column x=1 by 1`.
+#[derive(Debug)]
+pub enum Instruction {
+    /// Lights the top left `width` by `height` pixels.
+    Rect {
+        /// How many columns are lit.
+        width: usize,
+        /// How many rows are lit.
+        height: usize,
+    },
+    /// Shifts a row `k` pixels to the right, wrapping around.
+    RotateRow {
+        /// The row, from 0 at the top.
+        row: usize,
+        /// How many pixels the row is shifted by.
+        k: usize,
+    },
+    /// Shifts a column `k` pixels down, wrapping around.
+    RotateColumn {
+        /// The column, from 0 on the left.
+        column: usize,
+        /// How many pixels the column is shifted by.
+        k: usize,
+    },
+}
+
+fn parse_instruction(line: Line) -> Result<Instruction, ParseError> {
+    if let Some(size) = line.text.strip_prefix("rect ") {
+        let (width, height) = line.split_once(size, "x", "`<width>x<height>`")?;
+        Ok(Instruction::Rect {
+            width: line.parse(width, "a width")?,
+            height: line.parse(height, "a height")?,
+        })
+    } else if let Some(rotation) = line.text.strip_prefix("rotate row y=") {
+        let (row, k) = line.split_once(rotation, " by ", "`<row> by <n>`")?;
+        Ok(Instruction::RotateRow {
+            row: line.parse(row, "a row")?,
+            k: line.parse(k, "a number of pixels")?,
+        })
+    } else if let Some(rotation) = line.text.strip_prefix("rotate column x=") {
+        let (column, k) = line.split_once(rotation, " by ", "`<column> by <n>`")?;
+        Ok(Instruction::RotateColumn {
+            column: line.parse(column, "a column")?,
+            k: line.parse(k, "a number of pixels")?,
+        })
+    } else {
+        Err(line.mismatch(
+            "`rect <width>x<height>`, `rotate row y=<row> by <n>` or \
+             `rotate column x=<column> by <n>`",
+        ))
     }
 }
 
-impl fmt::Display for Screen {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        for row in self.pixels {
-            for pixel in row {
-                if pixel {
-                    write!(f, "#")?;
-                } else {
-                    write!(f, " ")?;
-                }
+/// Parses one instruction per line.
+pub fn parse_instructions(input: &str) -> Result<Vec<Instruction>, ParseError> {
+    parse::parse_lines(input, parse_instruction)
+}
+
+/// Follows the instructions on a screen of `width` by `height` pixels, calling
+/// `on_step` with the screen after each instruction.
+pub fn run<F>(
+    instructions: &[Instruction],
+    width: usize,
+    height: usize,
+    mut on_step: F,
+) -> anyhow::Result<Screen>
+where
+    F: FnMut(&Screen),
+{
+    let mut screen = Screen::new(width, height);
+    for instruction in instructions {
+        match *instruction {
+            Instruction::Rect {
+                width: rect_width,
+                height: rect_height,
+            } if rect_width <= width && rect_height <= height => screen
+                .pixels
+                .view_mut(..rect_width, ..rect_height)
+                .fill(true),
+            Instruction::RotateRow { row, k } if row < height => screen.pixels.rotate_row(row, k),
+            Instruction::RotateColumn { column, k } if column < width => {
+                screen.pixels.rotate_column(column, k)
             }
-            writeln!(f)?;
+            _ => anyhow::bail!(
+                "{:?} is outside of the {}x{} screen",
+                instruction,
+                width,
+                height
+            ),
         }
-        write!(f, "")
+        log::trace!("After {:?}:\n{}", instruction, screen);
+        on_step(&screen);
     }
+    log::debug!("{} pixels are lit", screen.lit_pixels());
+    Ok(screen)
 }
 
-#[derive(Debug)]
-enum Instruction {
-    Rect { width: usize, height: usize },
-    RotateRow { row: usize, k: usize },
-    RotateColumn { column: usize, k: usize },
+/// Counts the lit pixels after following the instructions, then draws the
+/// screen.
+pub struct Solver {
+    /// How many pixels wide the screen is.
+    width: usize,
+    /// How many pixels tall the screen is.
+    height: usize,
 }
 
-fn parse_input(input: &str) -> Vec<Instruction> {
-    let mut instructions = vec![];
-    for line in input.lines() {
-        if line.starts_with("rect") {
-            let (width, height) = line.split_once(' ').unwrap().1.split_once('x').unwrap();
-            instructions.push(Instruction::Rect {
-                width: width.parse().unwrap(),
-                height: height.parse().unwrap(),
-            });
-        } else if line.starts_with("rotate row") {
-            let (row, k) = line.split_once('=').unwrap().1.split_once(" by ").unwrap();
-            instructions.push(Instruction::RotateRow {
-                row: row.parse().unwrap(),
-                k: k.parse().unwrap(),
-            });
-        } else if line.starts_with("rotate column") {
-            let (column, k) = line.split_once('=').unwrap().1.split_once(" by ").unwrap();
-            instructions.push(Instruction::RotateColumn {
-                column: column.parse().unwrap(),
-                k: k.parse().unwrap(),
-            });
-        } else {
-            panic!("invalid line: {:?}", line)
+impl Default for Solver {
+    fn default() -> Self {
+        Self {
+            width: 50,
+            height: 6,
         }
     }
-    instructions
 }
 
-pub fn main(input: &str) -> anyhow::Result<()> {
-    let instructions = parse_input(input);
-    let mut screen = Screen::new();
-    for instruction in instructions {
-        match instruction {
-            Instruction::Rect { width, height } => screen.turn_on(width, height),
-            Instruction::RotateRow { row, k } => screen.rotate_row(row, k),
-            Instruction::RotateColumn { column, k } => screen.rotate_column(column, k),
+impl Solution for Solver {
+    type Input = Vec<Instruction>;
+
+    fn parse(&self, input: &str) -> anyhow::Result<Self::Input> {
+        Ok(parse_instructions(input)?)
+    }
+
+    fn part1(&self, instructions: &Self::Input) -> anyhow::Result<Answer> {
+        Ok(run(instructions, self.width, self.height, |_| {})?
+            .lit_pixels()
+            .into())
+    }
+
+    fn part2(&self, instructions: &Self::Input) -> anyhow::Result<Answer> {
+        // The code is displayed on the screen in capital letters.
+        Ok(run(instructions, self.width, self.height, |_| {})?
+            .to_string()
+            .into())
+    }
+
+    fn render(&self, instructions: &Self::Input, renderer: &mut Renderer) -> anyhow::Result<()> {
+        let mut frames = vec![Screen::new(self.width, self.height).image()];
+        let screen = run(instructions, self.width, self.height, |screen| {
+            frames.push(screen.image())
+        })?;
+        renderer.png("code", &screen.image())?;
+        renderer.gif("code", &frames, Duration::from_millis(50))
+    }
+
+    fn params(&self) -> Vec<Param> {
+        vec![
+            Param::new("width", "how many pixels wide the screen is", self.width),
+            Param::new("height", "how many pixels tall the screen is", self.height),
+        ]
+    }
+
+    fn set_param(&mut self, name: &str, value: &str) -> anyhow::Result<()> {
+        match name {
+            "width" => self.width = params::parse_value(name, value)?,
+            "height" => self.height = params::parse_value(name, value)?,
+            _ => anyhow::bail!("unknown parameter {}", name),
         }
-        println!("{}", screen);
+        Ok(())
     }
+}
 
-    println!("Lit pixels: {}", screen.lit_pixels());
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-    Ok(())
+    use aoc_core::solve_part;
+
+    #[test]
+    fn test_examples() {
+        let instructions = "rect 3x2
+rotate column x=1 by 1
+rotate row y=0 by 4
+rotate column x=1 by 1";
+        let solver = Solver {
+            width: 7,
+            height: 3,
+        };
+        assert_eq!(
+            solve_part(&solver, instructions, 1).unwrap(),
+            Answer::from(6)
+        );
+        assert_eq!(
+            solve_part(&solver, instructions, 2).unwrap(),
+            Answer::from(" #  # #\n# #    \n #     \n")
+        );
+    }
+
+    #[test]
+    fn test_outside_of_screen() {
+        let solver = Solver {
+            width: 7,
+            height: 3,
+        };
+        let error = solve_part(&solver, "rect 8x2", 1).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Rect { width: 8, height: 2 } is outside of the 7x3 screen"
+        );
+        assert!(solve_part(&solver, "rotate row y=3 by 1", 1).is_err());
+    }
 }
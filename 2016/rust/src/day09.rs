@@ -1,87 +1,145 @@
-fn parse_marker(marker: &str) -> (usize, usize) {
-    let (length, times) = marker.split_once('x').unwrap();
-    (length.parse().unwrap(), times.parse().unwrap())
+//! Day 9: Explosives in Cyberspace, decompressing a file with markers
+//! repeating the data following them.
+
+use aoc_core::parse::{Line, ParseError};
+use aoc_core::{Answer, Solution};
+
+/// Parses the marker at the start of `text`, like `(2x3)`, returning the
+/// length and the number of repetitions, and the text following it.
+fn parse_marker<'a>(line: &Line<'a>, text: &'a str) -> Result<(usize, usize, &'a str), ParseError> {
+    let (marker, rest) = line.split_once(&text[1..], ")", "a marker ending with `)`")?;
+    let (length, times) = line.split_once(marker, "x", "`<length>x<times>`")?;
+    Ok((
+        line.parse(length, "a length")?,
+        line.parse(times, "a number of repetitions")?,
+        rest,
+    ))
 }
 
-fn decompress_v1(compressed: &str) -> String {
+/// Decompresses `text`, a slice of `line`, decompressing the repeated data
+/// too if `recursive`.
+fn decompress(line: &Line, text: &str, recursive: bool) -> Result<String, ParseError> {
     let mut decompressed = String::new();
-    let mut chars = compressed.chars();
-    while let Some(c) = chars.next() {
-        if c == '(' {
-            let marker: String = chars.by_ref().take_while(|c| *c != ')').collect();
-            let (length, times) = parse_marker(marker.as_str());
-            let data: String = chars.by_ref().take(length).collect();
-            for _ in 0..times {
-                decompressed.push_str(data.as_str());
-            }
+    let mut rest = text;
+    while let Some(start) = rest.find('(') {
+        decompressed.push_str(&rest[..start]);
+        let (length, times, after_marker) = parse_marker(line, &rest[start..])?;
+        let end = after_marker
+            .char_indices()
+            .nth(length)
+            .map_or(after_marker.len(), |(end, _)| end);
+        let (data, after_data) = after_marker.split_at(end);
+        let data = if recursive {
+            decompress(line, data, true)?
         } else {
-            decompressed.push(c);
-        }
+            data.to_string()
+        };
+        decompressed.push_str(&data.repeat(times));
+        rest = after_data;
     }
-    decompressed
+    decompressed.push_str(rest);
+    Ok(decompressed)
 }
 
-fn decompress_v2(compressed: &str) -> String {
-    let mut decompressed = String::new();
-    let mut chars = compressed.chars();
-    while let Some(c) = chars.next() {
-        if c == '(' {
-            let marker: String = chars.by_ref().take_while(|c| *c != ')').collect();
-            let (length, times) = parse_marker(marker.as_str());
-            let data: String = chars.by_ref().take(length).collect();
-            let decompressed_data = decompress_v2(data.as_str());
-            for _ in 0..times {
-                decompressed.push_str(decompressed_data.as_str());
-            }
-        } else {
-            decompressed.push(c);
-        }
-    }
-    decompressed
+/// Decompresses markers like `(2x3)`, which repeat the next 2 characters 3
+/// times, leaving markers within the repeated data as they are. Fails if a
+/// marker isn't two numbers separated by `x`.
+pub fn decompress_v1(compressed: &str) -> Result<String, ParseError> {
+    decompress(&Line::new(1, compressed), compressed, false)
 }
 
-pub fn main(input: &str) -> anyhow::Result<()> {
-    println!(
-        "Decompressed (version 1) length: {}",
-        decompress_v1(input.trim()).chars().count()
-    );
+/// Decompresses markers like [`decompress_v1`], then the markers within the
+/// repeated data too.
+pub fn decompress_v2(compressed: &str) -> Result<String, ParseError> {
+    decompress(&Line::new(1, compressed), compressed, true)
+}
 
-    println!(
-        "Decompressed (version 2) length: {}",
-        decompress_v2(input.trim()).chars().count()
-    );
+/// Gives the decompressed length of the file, then with the markers in the
+/// repeated data decompressed too.
+#[derive(Default)]
+pub struct Solver;
 
-    Ok(())
+impl Solution for Solver {
+    type Input = String;
+
+    fn parse(&self, input: &str) -> anyhow::Result<Self::Input> {
+        Ok(input.trim().to_string())
+    }
+
+    fn part1(&self, compressed: &Self::Input) -> anyhow::Result<Answer> {
+        Ok(decompress_v1(compressed)?.chars().count().into())
+    }
+
+    fn part2(&self, compressed: &Self::Input) -> anyhow::Result<Answer> {
+        Ok(decompress_v2(compressed)?.chars().count().into())
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    use aoc_core::solve_part;
+
     #[test]
     fn test_decompress_v1() {
-        assert_eq!(decompress_v1("ADVENT"), "ADVENT");
-        assert_eq!(decompress_v1("ADVENT"), "ADVENT");
-        assert_eq!(decompress_v1("A(1x5)BC"), "ABBBBBC");
-        assert_eq!(decompress_v1("(3x3)XYZ"), "XYZXYZXYZ");
-        assert_eq!(decompress_v1("A(2x2)BCD(2x2)EFG"), "ABCBCDEFEFG");
-        assert_eq!(decompress_v1("(6x1)(1x3)A"), "(1x3)A");
-        assert_eq!(decompress_v1("X(8x2)(3x3)ABCY"), "X(3x3)ABC(3x3)ABCY");
+        assert_eq!(decompress_v1("ADVENT").unwrap(), "ADVENT");
+        assert_eq!(decompress_v1("ADVENT").unwrap(), "ADVENT");
+        assert_eq!(decompress_v1("A(1x5)BC").unwrap(), "ABBBBBC");
+        assert_eq!(decompress_v1("(3x3)XYZ").unwrap(), "XYZXYZXYZ");
+        assert_eq!(decompress_v1("A(2x2)BCD(2x2)EFG").unwrap(), "ABCBCDEFEFG");
+        assert_eq!(decompress_v1("(6x1)(1x3)A").unwrap(), "(1x3)A");
+        assert_eq!(
+            decompress_v1("X(8x2)(3x3)ABCY").unwrap(),
+            "X(3x3)ABC(3x3)ABCY"
+        );
+
+        assert_eq!(decompress_v1("AB(3x").unwrap_err().column, 4);
+        assert_eq!(decompress_v1("(ax2)").unwrap_err().text, "a");
+        assert!(decompress_v1("(4x1)(ax)").is_ok());
+        assert!(decompress_v2("(4x1)(ax)").is_err());
     }
 
     #[test]
     fn test_decompress_v2() {
-        assert_eq!(decompress_v2("(3x3)XYZ"), "XYZXYZXYZ");
-        assert_eq!(decompress_v2("X(8x2)(3x3)ABCY"), "XABCABCABCABCABCABCY");
+        assert_eq!(decompress_v2("(3x3)XYZ").unwrap(), "XYZXYZXYZ");
+        assert_eq!(
+            decompress_v2("X(8x2)(3x3)ABCY").unwrap(),
+            "XABCABCABCABCABCABCY"
+        );
         assert_eq!(
-            decompress_v2("(27x12)(20x12)(13x14)(7x10)(1x12)A"),
-            std::iter::repeat('A').take(241920).collect::<String>()
+            decompress_v2("(27x12)(20x12)(13x14)(7x10)(1x12)A").unwrap(),
+            std::iter::repeat_n('A', 241920).collect::<String>()
         );
         assert_eq!(
             decompress_v2("(25x3)(3x3)ABC(2x3)XY(5x2)PQRSTX(18x9)(3x2)TWO(5x7)SEVEN")
+                .unwrap()
                 .chars()
                 .count(),
             445
         );
     }
+
+    #[test]
+    fn test_examples() {
+        for (compressed, length) in [("ADVENT", 6), ("A(1x5)BC", 7), ("A(2x2)BCD(2x2)EFG", 11)] {
+            assert_eq!(
+                solve_part(&Solver, compressed, 1).unwrap(),
+                Answer::from(length)
+            );
+        }
+        assert_eq!(
+            solve_part(&Solver, "X(8x2)(3x3)ABCY", 1).unwrap(),
+            Answer::from(18)
+        );
+        assert_eq!(
+            solve_part(&Solver, "X(8x2)(3x3)ABCY", 2).unwrap(),
+            Answer::from(20)
+        );
+        let compressed = "(25x3)(3x3)ABC(2x3)XY(5x2)PQRSTX(18x9)(3x2)TWO(5x7)SEVEN";
+        assert_eq!(
+            solve_part(&Solver, compressed, 2).unwrap(),
+            Answer::from(445)
+        );
+    }
 }
@@ -1,8 +1,22 @@
+//! Day 10: Balance Bots, following microchips passed between bots and into
+//! outputs.
+
 use std::collections::HashMap;
 
-type Microchip = u32;
+use anyhow::Context;
 
-#[derive(Debug)]
+use aoc_core::parse::{self, ParseError};
+use aoc_core::{params, Answer, Param, Solution};
+
+/// The value of a microchip.
+pub type Microchip = u32;
+
+/// A bot comparing two microchips, the lower first.
+pub type Comparison = (String, Microchip, Microchip);
+
+type Connections = HashMap<String, (Destination, Destination)>;
+
+#[derive(Debug, Clone)]
 struct Bot {
     values: Vec<Microchip>,
 }
@@ -13,22 +27,26 @@ enum Destination {
     Bot(String),
 }
 
-fn parse_input(
-    input: &str,
-) -> (
-    HashMap<String, Bot>,
-    HashMap<String, (Destination, Destination)>,
-) {
+/// The bots with the microchips they start with, and where they give them.
+#[derive(Debug)]
+pub struct Factory {
+    bots: HashMap<String, Bot>,
+    connections: Connections,
+}
+
+/// Parses lines like `value 5 goes to bot 2` and
+/// `bot 2 gives low to bot 1 and high to output 0`.
+pub fn parse_factory(input: &str) -> Result<Factory, ParseError> {
     let mut bots = HashMap::new();
     let mut connections = HashMap::new();
-    for line in input.lines() {
-        let words: Vec<&str> = line.split_whitespace().collect();
+    for line in parse::lines(input) {
+        let words: Vec<&str> = line.text.split_whitespace().collect();
         match words[..] {
             ["value", value, "goes", "to", "bot", bot] => {
                 let entry = bots
                     .entry(bot.to_string())
                     .or_insert(Bot { values: vec![] });
-                entry.values.push(value.parse().unwrap());
+                entry.values.push(line.parse(value, "a microchip value")?);
             }
             ["bot", bot, "gives", "low", "to", "bot", low, "and", "high", "to", "bot", high] => {
                 connections.insert(
@@ -67,15 +85,28 @@ fn parse_input(
                     ),
                 );
             }
-            _ => panic!("invalid instruction: {}", line),
+            _ => {
+                return Err(line.mismatch(
+                    "`value <n> goes to bot <bot>` or \
+                     `bot <bot> gives low to bot|output <n> and high to bot|output <n>`",
+                ))
+            }
         }
     }
-    (bots, connections)
+    Ok(Factory { bots, connections })
 }
 
-pub fn main(input: &str) -> anyhow::Result<()> {
+/// Passes microchips between bots until no bot holds two of them. Returns the
+/// comparisons made by the bots and the microchips placed in each output.
+///
+/// # Panics
+///
+/// If a bot holding two microchips doesn't say where to give them, or would be
+/// given a third microchip.
+pub fn run(factory: &Factory) -> (Vec<Comparison>, HashMap<String, Microchip>) {
     let mut outputs: HashMap<String, Microchip> = HashMap::new();
-    let (mut bots, connections) = parse_input(input);
+    let mut bots = factory.bots.clone();
+    let connections = &factory.connections;
     let mut comparisons = vec![];
 
     while let Some((name, bot)) = bots.iter_mut().find(|(_name, bot)| bot.values.len() == 2) {
@@ -110,16 +141,108 @@ pub fn main(input: &str) -> anyhow::Result<()> {
         }
     }
 
-    for (name, low, high) in comparisons {
-        if low == 17 && high == 61 {
-            println!("Comparison bot={name} low={low} high={high}");
+    (comparisons, outputs)
+}
+
+/// Finds the bot comparing the two microchips, then multiplies the values of
+/// the microchips in outputs 0, 1 and 2.
+pub struct Solver {
+    /// The lower value of the microchips compared by the bot to find.
+    low_chip: Microchip,
+    /// The higher value of the microchips compared by the bot to find.
+    high_chip: Microchip,
+}
+
+impl Default for Solver {
+    fn default() -> Self {
+        Self {
+            low_chip: 17,
+            high_chip: 61,
+        }
+    }
+}
+
+impl Solution for Solver {
+    type Input = Factory;
+
+    fn parse(&self, input: &str) -> anyhow::Result<Self::Input> {
+        Ok(parse_factory(input)?)
+    }
+
+    fn part1(&self, factory: &Self::Input) -> anyhow::Result<Answer> {
+        let (comparisons, _) = run(factory);
+        let (name, _, _) = comparisons
+            .into_iter()
+            .find(|(_, low, high)| *low == self.low_chip && *high == self.high_chip)
+            .with_context(|| {
+                format!(
+                    "no bot compares microchips {} and {}",
+                    self.low_chip, self.high_chip
+                )
+            })?;
+        Ok(name.into())
+    }
+
+    fn part2(&self, factory: &Self::Input) -> anyhow::Result<Answer> {
+        let (_, outputs) = run(factory);
+        let output = |name| {
+            outputs
+                .get(name)
+                .with_context(|| format!("output {} is empty", name))
+        };
+        Ok((output("0")? * output("1")? * output("2")?).into())
+    }
+
+    fn params(&self) -> Vec<Param> {
+        vec![
+            Param::new(
+                "low_chip",
+                "the lower microchip compared by the bot to find",
+                self.low_chip,
+            ),
+            Param::new(
+                "high_chip",
+                "the higher microchip compared by the bot to find",
+                self.high_chip,
+            ),
+        ]
+    }
+
+    fn set_param(&mut self, name: &str, value: &str) -> anyhow::Result<()> {
+        match name {
+            "low_chip" => self.low_chip = params::parse_value(name, value)?,
+            "high_chip" => self.high_chip = params::parse_value(name, value)?,
+            _ => anyhow::bail!("unknown parameter {}", name),
         }
+        Ok(())
     }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-    println!(
-        "Product of 0, 1 and 2 outputs: {}",
-        outputs.get("0").unwrap() * outputs.get("1").unwrap() * outputs.get("2").unwrap()
-    );
+    use aoc_core::solve_part;
 
-    Ok(())
+    #[test]
+    fn test_examples() {
+        let instructions = "value 5 goes to bot 2
+bot 2 gives low to bot 1 and high to bot 0
+value 3 goes to bot 1
+bot 1 gives low to output 1 and high to bot 0
+bot 0 gives low to output 2 and high to output 0
+value 2 goes to bot 2";
+        let solver = Solver {
+            low_chip: 2,
+            high_chip: 5,
+        };
+        assert_eq!(
+            solve_part(&solver, instructions, 1).unwrap(),
+            Answer::from("2")
+        );
+        assert_eq!(
+            solve_part(&solver, instructions, 2).unwrap(),
+            Answer::from(30)
+        );
+    }
 }
@@ -1,203 +1,247 @@
+//! Day 12: Leonardo's Monorail, running an assembunny program on a virtual
+//! machine with four registers.
+
+use std::fmt;
 use std::str::FromStr;
 
-use anyhow::Context;
+use aoc_core::machine::{self, Machine, Operand, Registers};
+use aoc_core::parse::{self, Line, ParseError};
+use aoc_core::{Answer, Solution};
 
-type Value = i32;
+/// The value held by a register.
+pub type Value = i32;
 
+/// A register of the virtual machine, `a` to `d`.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
-enum Register {
+pub enum Register {
+    /// `a`, holding the answer.
     A,
+    /// `b`.
     B,
+    /// `c`, which starts at 0 or at 1 in part 2.
     C,
+    /// `d`.
     D,
 }
 
-impl FromStr for Register {
-    type Err = anyhow::Error;
+impl machine::Register for Register {
+    const ALL: &'static [Self] = &[Self::A, Self::B, Self::C, Self::D];
 
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        match s {
-            "a" => Ok(Self::A),
-            "b" => Ok(Self::B),
-            "c" => Ok(Self::C),
-            "d" => Ok(Self::D),
-            _ => Err(anyhow::anyhow!("invalid register: {}", s)),
-        }
+    fn index(self) -> usize {
+        self as usize
     }
-}
 
-#[derive(Debug, Clone, Copy)]
-enum Literal {
-    Register(Register),
-    Value(Value),
+    fn name(self) -> &'static str {
+        match self {
+            Self::A => "a",
+            Self::B => "b",
+            Self::C => "c",
+            Self::D => "d",
+        }
+    }
 }
 
-impl FromStr for Literal {
-    type Err = anyhow::Error;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        Ok(match s {
-            "a" | "b" | "c" | "d" => Self::Register(s.parse()?),
-            value => Self::Value(value.parse()?),
-        })
+impl fmt::Display for Register {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(machine::Register::name(*self))
     }
 }
 
-#[derive(Debug, Clone)]
-enum Instruction {
+/// An operand given either as a register or as a value.
+pub type Literal = Operand<Register, Value>;
+
+/// An assembunny instruction, given like `cpy 41 a` or `jnz a 2`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Instruction {
+    /// `cpy x y`, copying a value or register into a register.
     Copy {
+        /// The value or register copied.
         source: Literal,
+        /// The register copied to.
         destination: Register,
     },
+    /// `inc x`, adding 1 to the register.
     Increase {
+        /// The register increased.
         register: Register,
     },
+    /// `dec x`, subtracting 1 from the register.
     Decrease {
+        /// The register decreased.
         register: Register,
     },
+    /// `jnz x y`, jumping relative to the instruction if the value or register
+    /// isn't zero.
     JumpIfNotZero {
+        /// The value or register checked.
         condition: Literal,
+        /// How many instructions to jump by.
         offset: i32,
     },
 }
 
-#[derive(Debug)]
-struct VM {
-    register_a: Value,
-    register_b: Value,
-    register_c: Value,
-    register_d: Value,
-    program_counter: usize,
-    instructions: Vec<Instruction>,
-}
-
-impl VM {
-    fn new(instructions: &[Instruction]) -> Self {
-        Self {
-            register_a: 0,
-            register_b: 0,
-            register_c: 0,
-            register_d: 0,
-            program_counter: 0,
-            instructions: instructions.to_vec(),
+impl fmt::Display for Instruction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Copy {
+                source,
+                destination,
+            } => write!(f, "cpy {} {}", source, destination),
+            Self::Increase { register } => write!(f, "inc {}", register),
+            Self::Decrease { register } => write!(f, "dec {}", register),
+            Self::JumpIfNotZero { condition, offset } => {
+                write!(f, "jnz {} {}", condition, offset)
+            }
         }
     }
+}
 
-    fn get_register(&self, register: Register) -> Value {
-        match register {
-            Register::A => self.register_a,
-            Register::B => self.register_b,
-            Register::C => self.register_c,
-            Register::D => self.register_d,
-        }
-    }
+impl machine::Instruction for Instruction {
+    type Register = Register;
+    type Value = Value;
 
-    fn get_register_mut(&mut self, register: Register) -> &mut Value {
-        match register {
-            Register::A => &mut self.register_a,
-            Register::B => &mut self.register_b,
-            Register::C => &mut self.register_c,
-            Register::D => &mut self.register_d,
+    fn execute(&self, registers: &mut Registers<Register, Value>) -> isize {
+        match *self {
+            Self::Copy {
+                source,
+                destination,
+            } => registers[destination] = source.value(registers),
+            Self::Increase { register } => registers[register] += 1,
+            Self::Decrease { register } => registers[register] -= 1,
+            Self::JumpIfNotZero { condition, offset } => {
+                if condition.value(registers) != 0 {
+                    return offset as isize;
+                }
+            }
         }
+        1
     }
+}
 
-    fn eval_literal(&self, literal: Literal) -> Value {
-        match literal {
-            Literal::Register(register) => self.get_register(register),
-            Literal::Value(value) => value,
-        }
-    }
+/// The virtual machine running a program, with its registers.
+pub type VM = Machine<Instruction>;
 
-    fn eval(&mut self) -> anyhow::Result<()> {
-        while let Some(instruction) = self.instructions.get(self.program_counter) {
-            match *instruction {
-                Instruction::Copy {
-                    source,
-                    destination,
-                } => {
-                    let value = self.eval_literal(source);
-                    let register = self.get_register_mut(destination);
-                    *register = value;
-                    self.program_counter += 1;
-                }
-                Instruction::Increase { register } => {
-                    *self.get_register_mut(register) += 1;
-                    self.program_counter += 1;
-                }
-                Instruction::Decrease { register } => {
-                    *self.get_register_mut(register) -= 1;
-                    self.program_counter += 1;
-                }
-                Instruction::JumpIfNotZero { condition, offset } => {
-                    if self.eval_literal(condition) == 0 {
-                        self.program_counter += 1;
-                    } else {
-                        self.program_counter = (if offset > 0 {
-                            self.program_counter.checked_add(offset as usize)
-                        } else {
-                            self.program_counter.checked_sub(offset.abs() as usize)
-                        })
-                        .context(format!(
-                            "instruction @ pc {} has invalid offset {}",
-                            self.program_counter, offset
-                        ))?;
-                    }
-                }
-            }
-        }
+/// Parses one instruction per line.
+pub fn parse_program(input: &str) -> Result<Vec<Instruction>, ParseError> {
+    parse::parse_lines(input, parse_instruction)
+}
 
-        Ok(())
-    }
+/// Parses an instruction like `cpy 41 a`.
+pub fn parse_instruction(line: Line) -> Result<Instruction, ParseError> {
+    let register = |text: &str| machine::parse_register(&line, text);
+    let literal = |text: &str| machine::parse_operand(&line, text);
+    let (mnemonic, operands) = machine::split_instruction(&line);
+    Ok(match (mnemonic, &operands[..]) {
+        ("cpy", [source, destination]) => Instruction::Copy {
+            source: literal(source)?,
+            destination: register(destination)?,
+        },
+        ("inc", [r]) => Instruction::Increase {
+            register: register(r)?,
+        },
+        ("dec", [r]) => Instruction::Decrease {
+            register: register(r)?,
+        },
+        ("jnz", [condition, offset]) => Instruction::JumpIfNotZero {
+            condition: literal(condition)?,
+            offset: line.parse(offset, "an offset")?,
+        },
+        _ => return Err(line.mismatch("an instruction like `cpy 41 a` or `jnz a 2`")),
+    })
 }
 
-fn parse_input(input: &str) -> anyhow::Result<Vec<Instruction>> {
-    let mut instructions = vec![];
-    for line in input.lines() {
-        instructions
-            .push(parse_instruction(line).context(format!("invalid instruction: {line:?}"))?);
+impl FromStr for Instruction {
+    type Err = ParseError;
+
+    fn from_str(line: &str) -> Result<Self, Self::Err> {
+        parse_instruction(Line::new(1, line))
     }
-    Ok(instructions)
 }
 
-fn parse_instruction(instruction: &str) -> anyhow::Result<Instruction> {
-    match instruction.split_whitespace().collect::<Vec<&str>>()[..] {
-        ["cpy", source, destination] => {
-            let source = match source {
-                "a" | "b" | "c" | "d" => Literal::Register(source.parse()?),
-                literal => Literal::Value(literal.parse()?),
-            };
-            Ok(Instruction::Copy {
-                source,
-                destination: destination.parse()?,
-            })
-        }
-        ["inc", register] => Ok(Instruction::Increase {
-            register: register.parse()?,
-        }),
-        ["dec", register] => Ok(Instruction::Decrease {
-            register: register.parse()?,
-        }),
-        ["jnz", register, offset] => Ok(Instruction::JumpIfNotZero {
-            condition: register.parse()?,
-            offset: offset.parse()?,
-        }),
-        _ => Err(anyhow::anyhow!("invalid instruction: {instruction:?}")),
+/// Runs the program, giving register `a` at the end, then when `c` starts at
+/// 1.
+#[derive(Default)]
+pub struct Solver;
+
+impl Solution for Solver {
+    type Input = Vec<Instruction>;
+
+    fn parse(&self, input: &str) -> anyhow::Result<Self::Input> {
+        Ok(parse_program(input)?)
+    }
+
+    fn part1(&self, instructions: &Self::Input) -> anyhow::Result<Answer> {
+        let mut vm = VM::new(instructions.clone());
+        vm.run()?;
+        log::debug!("Executed {} instructions", vm.executed());
+        Ok(vm.register(Register::A).into())
+    }
+
+    fn part2(&self, instructions: &Self::Input) -> anyhow::Result<Answer> {
+        let mut vm = VM::new(instructions.clone());
+        vm.set_register(Register::C, 1);
+        vm.run()?;
+        log::debug!("Executed {} instructions", vm.executed());
+        Ok(vm.register(Register::A).into())
     }
 }
 
-pub fn main(input: &str) -> anyhow::Result<()> {
-    let instructions = parse_input(input)?;
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-    let mut vm = VM::new(&instructions[..]);
-    vm.eval()?;
+    use proptest::prelude::*;
 
-    println!("Register a: {}", vm.register_a);
+    use aoc_core::solve_part;
 
-    let mut vm = VM::new(&instructions[..]);
-    vm.register_c = 1;
-    vm.eval()?;
+    #[test]
+    fn test_examples() {
+        let program = "cpy 41 a\ninc a\ninc a\ndec a\njnz a 2\ndec a";
+        assert_eq!(solve_part(&Solver, program, 1).unwrap(), Answer::from(42));
+        assert_eq!(solve_part(&Solver, program, 2).unwrap(), Answer::from(42));
 
-    println!("Register a with c initialized to 1: {}", vm.register_a);
+        // Jumping before the first instruction is an error in assembunny.
+        assert!(solve_part(&Solver, "inc a\njnz a -2", 1).is_err());
+    }
+
+    fn register() -> impl Strategy<Value = Register> {
+        prop_oneof![
+            Just(Register::A),
+            Just(Register::B),
+            Just(Register::C),
+            Just(Register::D)
+        ]
+    }
+
+    fn literal() -> impl Strategy<Value = Literal> {
+        prop_oneof![
+            register().prop_map(Literal::Register),
+            any::<Value>().prop_map(Literal::Value),
+        ]
+    }
+
+    fn instruction() -> impl Strategy<Value = Instruction> {
+        prop_oneof![
+            (literal(), register()).prop_map(|(source, destination)| Instruction::Copy {
+                source,
+                destination
+            }),
+            register().prop_map(|register| Instruction::Increase { register }),
+            register().prop_map(|register| Instruction::Decrease { register }),
+            (literal(), any::<i32>())
+                .prop_map(|(condition, offset)| Instruction::JumpIfNotZero { condition, offset }),
+        ]
+    }
+
+    proptest! {
+        #[test]
+        fn test_instruction_round_trip(instruction in instruction()) {
+            prop_assert_eq!(instruction.to_string().parse::<Instruction>().ok(), Some(instruction));
+        }
 
-    Ok(())
+        #[test]
+        fn test_parse_instruction_never_panics(line in "\\PC*") {
+            let _ = line.parse::<Instruction>();
+        }
+    }
 }
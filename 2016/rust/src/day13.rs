@@ -1,25 +1,54 @@
-use std::collections::{BinaryHeap, HashSet};
+//! Day 13: A Maze of Twisty Little Cubicles, finding the shortest path
+//! through an office whose walls are given by a formula.
 
-const TARGET: Coordinate = (31, 39);
+use std::fmt;
 
-type Coordinate = (usize, usize);
+use anyhow::Context;
 
+use aoc_core::grid::Grid;
+use aoc_core::render::{self, Image, Renderer};
+use aoc_core::search;
+use aoc_core::{params, Answer, Param, Solution};
+
+/// An `x`, `y` location of the office.
+pub type Coordinate = (usize, usize);
+
+/// A location of the office, drawn as `.` for open space and `#` for a wall.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
-enum Field {
+pub enum Field {
+    /// Drawn as `.`, which can be walked through.
     OpenSpace,
+    /// Drawn as `#`.
     Wall,
-    Visited,
 }
 
-fn evaluate_coordinate((x, y): Coordinate, favorite_number: usize) -> Field {
+impl fmt::Display for Field {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Field::OpenSpace => write!(f, "."),
+            Field::Wall => write!(f, "#"),
+        }
+    }
+}
+
+/// Tells whether a location is a wall, from the number of bits set in a
+/// formula of its coordinate and the designer's favorite number.
+pub fn evaluate_coordinate((x, y): Coordinate, favorite_number: usize) -> Field {
     let value = (x * x + 3 * x + 2 * x * y + y + y * y) + favorite_number;
-    if value.count_ones() % 2 == 0 {
+    if value.count_ones().is_multiple_of(2) {
         Field::OpenSpace
     } else {
         Field::Wall
     }
 }
 
+/// Returns the top left `width` by `height` locations of the office.
+pub fn office(favorite_number: usize, width: usize, height: usize) -> Grid<Field> {
+    Grid::from_fn(width, height, |x, y| {
+        evaluate_coordinate((x, y), favorite_number)
+    })
+}
+
 fn find_surrounding_open_spaces((x, y): Coordinate, favorite_number: usize) -> Vec<Coordinate> {
     let neighbors = [
         (Some(x), y.checked_sub(1)), // Up
@@ -38,135 +67,148 @@ fn find_surrounding_open_spaces((x, y): Coordinate, favorite_number: usize) -> V
     open_spaces
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
-struct Path(Vec<Coordinate>);
-
-impl Path {
-    fn new(initial: Coordinate) -> Self {
-        Self(vec![initial])
-    }
+/// The locations walked through, from `1,1` to the target.
+pub type Path = search::Path<Coordinate, usize>;
+
+/// How far past the target's coordinates paths may go. The office is
+/// unbounded, so the search has to give up somewhere when the target is walled
+/// in.
+const DETOUR_MARGIN: usize = 50;
+
+/// Returns the shortest path from `1,1` to `target`, if it can be reached
+/// without going further than twice the target's coordinates plus a margin.
+pub fn find_shortest_path_to_target(favorite_number: usize, target: Coordinate) -> Option<Path> {
+    let (max_x, max_y) = (2 * target.0 + DETOUR_MARGIN, 2 * target.1 + DETOUR_MARGIN);
+    let result = search::astar(
+        (1, 1),
+        |&coordinate| {
+            find_surrounding_open_spaces(coordinate, favorite_number)
+                .into_iter()
+                .filter(|&(x, y)| x <= max_x && y <= max_y)
+                .map(|neighbor| (neighbor, 1))
+        },
+        |&(x, y)| x.abs_diff(target.0) + y.abs_diff(target.1),
+        |&coordinate| coordinate == target,
+    );
+    log::debug!(
+        "Explored {} locations, found {}",
+        result.stats.expanded,
+        result.stats.discovered
+    );
+    result.path
+}
 
-    fn current_position(&self) -> Coordinate {
-        *self.0.last().unwrap()
-    }
+/// Finds the fewest steps from `1,1` to the target, the second part isn't
+/// solved yet.
+pub struct Solver {
+    /// The location to reach.
+    target: Coordinate,
+}
 
-    fn len(&self) -> usize {
-        self.0.len()
+impl Default for Solver {
+    fn default() -> Self {
+        Self { target: (31, 39) }
     }
+}
 
-    fn with_next(&self, coordinate: Coordinate) -> Self {
-        let mut coordinates = self.0.clone();
-        coordinates.push(coordinate);
-        Self(coordinates)
+impl Solver {
+    fn find_shortest_path(&self, favorite_number: usize) -> anyhow::Result<Path> {
+        if evaluate_coordinate(self.target, favorite_number) == Field::Wall {
+            anyhow::bail!("{},{} is a wall", self.target.0, self.target.1);
+        }
+        let path = find_shortest_path_to_target(favorite_number, self.target)
+            .with_context(|| format!("{},{} can't be reached", self.target.0, self.target.1))?;
+        if log::log_enabled!(log::Level::Trace) {
+            let (width, height) = office_size(&path);
+            let mut map = office(favorite_number, width, height).map(|field| field.to_string());
+            for &coordinate in &path.states {
+                map[coordinate] = "O".to_string();
+            }
+            log::trace!("The shortest path:\n{}", map);
+        }
+        Ok(path)
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
-struct ShortestPath(Path);
+/// The size of the part of the office to draw around `path`, leaving a margin
+/// so that the walls next to it show.
+fn office_size(path: &Path) -> (usize, usize) {
+    let width = path.states.iter().map(|(x, _)| x + 3).max().unwrap_or(0);
+    let height = path.states.iter().map(|(_, y)| y + 3).max().unwrap_or(0);
+    (width, height)
+}
 
-impl ShortestPath {
-    fn distance_to_target(&self) -> f64 {
-        distance(self.0.current_position(), TARGET)
-    }
+impl Solution for Solver {
+    type Input = usize;
 
-    fn with_next(&self, coordinate: Coordinate) -> Self {
-        Self(self.0.with_next(coordinate))
+    fn parse(&self, input: &str) -> anyhow::Result<Self::Input> {
+        Ok(input.trim().parse()?)
     }
-}
 
-fn distance((x1, y1): Coordinate, (x2, y2): Coordinate) -> f64 {
-    let x1 = x1 as f64;
-    let y1 = y1 as f64;
-    let x2 = x2 as f64;
-    let y2 = y2 as f64;
-    ((x2 - x1).powi(2) + (y2 - y1).powi(2)).sqrt()
-}
-
-impl Ord for ShortestPath {
-    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-        // Sort in such a way that the shortest path closest to TARGET wins.
-        other.0.len().cmp(&self.0.len()).then_with(|| {
-            self.distance_to_target()
-                .partial_cmp(&other.distance_to_target())
-                .unwrap()
-        })
+    fn part1(&self, favorite_number: &Self::Input) -> anyhow::Result<Answer> {
+        let shortest_path = self.find_shortest_path(*favorite_number)?;
+        Ok(shortest_path.steps().into())
     }
-}
 
-impl PartialOrd for ShortestPath {
-    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        Some(self.cmp(other))
+    fn part2(&self, _favorite_number: &Self::Input) -> anyhow::Result<Answer> {
+        anyhow::bail!("not solved yet")
     }
-}
 
-fn find_shortest_path_to_target(favorite_number: usize) -> Path {
-    let mut paths = BinaryHeap::from([ShortestPath(Path::new((1, 1)))]);
-    let mut shortest_path: Option<Path> = None;
-    let mut visited_coordinates = HashSet::from([(1, 1)]);
-    while let Some(path) = paths.pop() {
-        let current_position = path.0.current_position();
-        if current_position == TARGET {
-            match shortest_path {
-                Some(current_shortest_path) if path.0.len() < current_shortest_path.0.len() => {
-                    shortest_path = Some(path.0);
-                }
-                None => shortest_path = Some(path.0),
-                _ => {}
-            };
-        } else {
-            for neighbor in find_surrounding_open_spaces(current_position, favorite_number) {
-                if !visited_coordinates.contains(&neighbor) {
-                    paths.push(path.with_next(neighbor));
-                    visited_coordinates.insert(neighbor);
-                }
-            }
+    fn render(&self, favorite_number: &Self::Input, renderer: &mut Renderer) -> anyhow::Result<()> {
+        let path = self.find_shortest_path(*favorite_number)?;
+        let (width, height) = office_size(&path);
+        let office = office(*favorite_number, width, height);
+        let palette = [render::BLACK, render::GRAY, render::RED, render::GREEN];
+        let mut image = Image::from_fn(width, height, &palette, |x, y| match office[(x, y)] {
+            Field::OpenSpace => 0,
+            Field::Wall => 1,
+        });
+        for &(x, y) in &path.states {
+            image.set(x, y, 2);
         }
+        for (x, y) in [(1, 1), self.target] {
+            image.set(x, y, 3);
+        }
+        renderer.png("path", &image)
     }
-    shortest_path.unwrap()
-}
 
-fn map_size(path: &Path) -> (usize, usize) {
-    let width = path.0.iter().map(|(x, _)| *x).max().unwrap() + 1;
-    let height = path.0.iter().map(|(_, y)| *y).max().unwrap() + 1;
-    (width, height)
-}
+    fn params(&self) -> Vec<Param> {
+        vec![Param::new(
+            "target",
+            "the x,y coordinate to reach",
+            format!("{},{}", self.target.0, self.target.1),
+        )]
+    }
 
-fn create_map(width: usize, height: usize, favorite_number: usize) -> Vec<Vec<Field>> {
-    let mut map = vec![];
-    for y in 0..height {
-        let mut row = vec![];
-        for x in 0..width {
-            row.push(evaluate_coordinate((x, y), favorite_number));
+    fn set_param(&mut self, name: &str, value: &str) -> anyhow::Result<()> {
+        match name {
+            "target" => self.target = params::parse_pair(name, value)?,
+            _ => anyhow::bail!("unknown parameter {}", name),
         }
-        map.push(row);
+        Ok(())
     }
-    map
 }
 
-pub fn main(input: &str) -> anyhow::Result<()> {
-    let favorite_number = input.parse()?;
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-    let shortest_path = find_shortest_path_to_target(favorite_number);
-    let (width, height) = map_size(&shortest_path);
-    let mut map = create_map(width, height, favorite_number);
-    for (x, y) in &shortest_path.0 {
-        map[*y][*x] = Field::Visited;
-    }
+    use aoc_core::solve_part;
 
-    for row in map {
-        println!(
-            "{}",
-            row.iter()
-                .map(|field| match field {
-                    Field::OpenSpace => '.',
-                    Field::Wall => '#',
-                    Field::Visited => 'O',
-                })
-                .collect::<String>()
-        );
+    #[test]
+    fn test_examples() {
+        let solver = Solver { target: (7, 4) };
+        assert_eq!(solve_part(&solver, "10", 1).unwrap(), Answer::from(11));
     }
 
-    println!("Steps in shortest path: {}", shortest_path.0.len() - 1);
-
-    Ok(())
+    #[test]
+    fn test_unreachable_target() {
+        let wall = Solver { target: (1, 0) };
+        let error = solve_part(&wall, "10", 1).unwrap_err();
+        assert_eq!(error.to_string(), "1,0 is a wall");
+        // Walled in by 1,0, 3,0 and 2,1.
+        let enclosed = Solver { target: (2, 0) };
+        let error = solve_part(&enclosed, "10", 1).unwrap_err();
+        assert_eq!(error.to_string(), "2,0 can't be reached");
+    }
 }
@@ -1,3 +1,23 @@
-pub fn main(_input: &str) -> anyhow::Result<()> {
-    todo!()
+//! Day 15: Timing is Everything, not solved yet.
+
+use aoc_core::{Answer, Solution};
+
+/// Not solved yet, fails to parse any input.
+#[derive(Default)]
+pub struct Solver;
+
+impl Solution for Solver {
+    type Input = ();
+
+    fn parse(&self, _input: &str) -> anyhow::Result<Self::Input> {
+        anyhow::bail!("not solved yet")
+    }
+
+    fn part1(&self, _input: &Self::Input) -> anyhow::Result<Answer> {
+        anyhow::bail!("not solved yet")
+    }
+
+    fn part2(&self, _input: &Self::Input) -> anyhow::Result<Answer> {
+        anyhow::bail!("not solved yet")
+    }
 }
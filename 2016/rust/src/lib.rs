@@ -0,0 +1,42 @@
+//! Solutions to the puzzles of [Advent of Code 2016](https://adventofcode.com/2016).
+//!
+//! Each day has a module exposing the model its puzzle input is parsed into,
+//! the functions solving the puzzle, and a `Solver` implementing
+//! [`Solution`](aoc_core::Solution) for the `aoc` binary. The assembunny
+//! programs of day 12 can for example be run on their own:
+//!
+//! ```
+//! use aoc_2016::day12::{self, Register, VM};
+//!
+//! let program = day12::parse_program("cpy 41 a\ninc a").unwrap();
+//! let mut vm = VM::new(program);
+//! vm.run().unwrap();
+//! assert_eq!(vm.register(Register::A), 42);
+//! ```
+
+#![warn(missing_docs)]
+
+extern crate anyhow;
+extern crate aoc_core;
+extern crate log;
+
+// The day modules and `DAYS`, generated by `build.rs` from the `dayNN.rs`
+// files in this directory.
+include!(concat!(env!("OUT_DIR"), "/days.rs"));
+
+/// Entry points for the fuzz targets in `fuzz/`, which feed arbitrary text to
+/// the parsers of the days.
+#[cfg(fuzzing)]
+pub mod fuzzing {
+    use aoc_core::parse::check_round_trip;
+
+    /// Checks that `text`, if it parses as an instruction of day 1, round-trips.
+    pub fn day01_instruction(text: &str) {
+        check_round_trip(text, str::parse::<crate::day01::Instruction>);
+    }
+
+    /// Checks that `text`, if it parses as an instruction of day 12, round-trips.
+    pub fn day12_instruction(text: &str) {
+        check_round_trip(text, str::parse::<crate::day12::Instruction>);
+    }
+}
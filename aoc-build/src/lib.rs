@@ -0,0 +1,168 @@
+//! Keeps the days of a year registered: the build script of each year crate
+//! generates the module declarations and the list of solvers from the
+//! `dayNN.rs` files present, and `new-day` creates these files from a
+//! template.
+
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+
+/// Returns the numbers of the days in `src`, which have a `dayNN.rs` file, in
+/// ascending order.
+pub fn find_days(src: &Path) -> std::io::Result<Vec<usize>> {
+    let mut days = vec![];
+    for entry in std::fs::read_dir(src)? {
+        let name = entry?.file_name();
+        let day = name
+            .to_str()
+            .and_then(|name| name.strip_prefix("day"))
+            .and_then(|name| name.strip_suffix(".rs"))
+            .filter(|number| number.len() == 2)
+            .and_then(|number| number.parse().ok());
+        if let Some(day) = day {
+            days.push(day);
+        }
+    }
+    days.sort_unstable();
+    Ok(days)
+}
+
+/// Generates the declarations of the modules of `days` in `src` and the list
+/// of constructors of their solvers, `DAYS`. The days have to start at day 1 without gaps, as
+/// the position of a solver determines its day.
+pub fn registry(src: &Path, year: &str, days: &[usize]) -> Result<String, String> {
+    for (i, day) in days.iter().enumerate() {
+        if *day != i + 1 {
+            return Err(format!(
+                "day{:02}.rs is missing in {}, days have to be consecutive",
+                i + 1,
+                src.display()
+            ));
+        }
+    }
+
+    let mut code = String::new();
+    for day in days {
+        let path = src.join(format!("day{:02}.rs", day));
+        writeln!(code, "#[path = {:?}]", path.display().to_string()).unwrap();
+        writeln!(code, "pub mod day{:02};", day).unwrap();
+    }
+    writeln!(code).unwrap();
+    writeln!(
+        code,
+        "/// Creates the solvers of {}, the first element of which is day 1.",
+        year
+    )
+    .unwrap();
+    writeln!(
+        code,
+        "pub const DAYS: &[fn() -> Box<dyn aoc_core::Puzzle>] = &["
+    )
+    .unwrap();
+    for day in days {
+        writeln!(code, "    || Box::<day{:02}::Solver>::default(),", day).unwrap();
+    }
+    writeln!(code, "];").unwrap();
+    Ok(code)
+}
+
+/// Writes the registry of the days in the `src` directory of the crate being
+/// built to `days.rs` in `OUT_DIR`, to be used from a build script. The year is
+/// taken from the package name, e.g. `aoc-2016`.
+pub fn register_days() {
+    let manifest_dir = std::env::var_os("CARGO_MANIFEST_DIR").expect("CARGO_MANIFEST_DIR");
+    let out_dir = std::env::var_os("OUT_DIR").expect("OUT_DIR");
+    let package = std::env::var("CARGO_PKG_NAME").expect("CARGO_PKG_NAME");
+    let year = package.strip_prefix("aoc-").unwrap_or(&package);
+
+    let src = PathBuf::from(manifest_dir).join("src");
+    println!("cargo:rerun-if-changed={}", src.display());
+
+    let days = find_days(&src).expect("failed to list the days");
+    let code = registry(&src, year, &days).unwrap_or_else(|error| panic!("{}", error));
+    std::fs::write(Path::new(&out_dir).join("days.rs"), code).expect("failed to write days.rs");
+}
+
+/// The source of a new day, which compiles but isn't solved yet. Its header
+/// names the puzzle like the other days, `Day 14: One-Time Pad`, if the
+/// `title` is given.
+pub fn day_template(day: usize, title: Option<&str>) -> String {
+    let heading = match title {
+        Some(title) => format!("Day {}: {}", day, title),
+        None => format!("Day {}", day),
+    };
+    format!("//! {}, not solved yet.\n{}", heading, DAY_TEMPLATE_BODY)
+}
+
+/// What follows the header of a new day.
+const DAY_TEMPLATE_BODY: &str = r#"
+use aoc_core::{Answer, Solution};
+
+/// Not solved yet, both parts fail.
+#[derive(Default)]
+pub struct Solver;
+
+impl Solution for Solver {
+    type Input = String;
+
+    fn parse(&self, input: &str) -> anyhow::Result<Self::Input> {
+        Ok(input.to_string())
+    }
+
+    fn part1(&self, _input: &Self::Input) -> anyhow::Result<Answer> {
+        anyhow::bail!("not solved yet")
+    }
+
+    fn part2(&self, _input: &Self::Input) -> anyhow::Result<Answer> {
+        anyhow::bail!("not solved yet")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "";
+
+    #[test]
+    #[ignore = "not solved yet"]
+    fn test_part1() {
+        let input = Solver.parse(EXAMPLE).unwrap();
+        assert_eq!(Solver.part1(&input).unwrap(), Answer::Integer(0));
+    }
+
+    #[test]
+    #[ignore = "not solved yet"]
+    fn test_part2() {
+        let input = Solver.parse(EXAMPLE).unwrap();
+        assert_eq!(Solver.part2(&input).unwrap(), Answer::Integer(0));
+    }
+}
+"#;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_day_template() {
+        let template = day_template(14, Some("One-Time Pad"));
+        assert!(template.starts_with("//! Day 14: One-Time Pad, not solved yet.\n\nuse "));
+        let template = day_template(3, None);
+        assert!(template.starts_with("//! Day 3, not solved yet.\n\nuse "));
+    }
+
+    #[test]
+    fn test_registry() {
+        let src = Path::new("/src");
+        let code = registry(src, "2016", &[1, 2]).unwrap();
+        assert!(code.contains("#[path = \"/src/day01.rs\"]\npub mod day01;\n"));
+        assert!(code.contains("#[path = \"/src/day02.rs\"]\npub mod day02;\n"));
+        assert!(code.contains(
+            "    || Box::<day01::Solver>::default(),\n    \
+             || Box::<day02::Solver>::default(),\n];\n"
+        ));
+
+        assert!(registry(src, "2016", &[1, 3]).is_err());
+        assert!(registry(src, "2016", &[2]).is_err());
+    }
+}
@@ -0,0 +1,204 @@
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::Path;
+
+use anyhow::Context;
+use json::object;
+
+use crate::client::Outcome;
+
+/// A submitted answer, as recorded in the attempt log.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Attempt {
+    pub year: u16,
+    pub day: usize,
+    pub part: u8,
+    pub answer: String,
+    /// The outcome as returned by [`Outcome::as_str`].
+    pub outcome: String,
+    /// When the answer was submitted, in seconds since the Unix epoch.
+    pub time: u64,
+    /// When the next answer may be submitted, in seconds since the Unix epoch.
+    pub wait_until: Option<u64>,
+}
+
+impl Attempt {
+    fn is_for(&self, year: u16, day: usize, part: u8) -> bool {
+        self.year == year && self.day == day && self.part == part
+    }
+
+    fn to_json(&self) -> json::JsonValue {
+        object! {
+            "year" => self.year,
+            "day" => self.day,
+            "part" => self.part,
+            "answer" => self.answer.as_str(),
+            "outcome" => self.outcome.as_str(),
+            "time" => self.time,
+            "wait_until" => self.wait_until,
+        }
+    }
+
+    fn from_json(value: &json::JsonValue) -> Option<Self> {
+        Some(Self {
+            year: value["year"].as_u16()?,
+            day: value["day"].as_usize()?,
+            part: value["part"].as_u8()?,
+            answer: value["answer"].as_str()?.to_string(),
+            outcome: value["outcome"].as_str()?.to_string(),
+            time: value["time"].as_u64()?,
+            wait_until: value["wait_until"].as_u64(),
+        })
+    }
+}
+
+/// Every answer submitted so far, stored as one JSON object per line.
+#[derive(Debug, Default)]
+pub struct AttemptLog {
+    attempts: Vec<Attempt>,
+}
+
+impl AttemptLog {
+    /// Loads the log at `path`, which is empty if the file doesn't exist yet.
+    pub fn load(path: &Path) -> anyhow::Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let contents = std::fs::read_to_string(path)
+            .with_context(|| format!("failed to read attempts from {}", path.display()))?;
+        contents
+            .parse()
+            .with_context(|| format!("invalid attempt log {}", path.display()))
+    }
+
+    /// Appends `attempt` to the log at `path`.
+    pub fn append(path: &Path, attempt: &Attempt) -> anyhow::Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .with_context(|| format!("failed to open {}", path.display()))?;
+        writeln!(file, "{}", attempt.to_json().dump())
+            .with_context(|| format!("failed to write to {}", path.display()))
+    }
+
+    /// When the latest cooldown ends, in seconds since the Unix epoch.
+    pub fn wait_until(&self) -> Option<u64> {
+        self.attempts
+            .iter()
+            .filter_map(|attempt| attempt.wait_until)
+            .max()
+    }
+
+    /// Fails if submitting `answer` to `part` of `day` in `year` is pointless,
+    /// because the part is solved or the answer is known to be wrong.
+    pub fn check(&self, year: u16, day: usize, part: u8, answer: &str) -> anyhow::Result<()> {
+        let attempts = self
+            .attempts
+            .iter()
+            .filter(|attempt| attempt.is_for(year, day, part));
+        let number = answer.parse::<i64>().ok();
+        for attempt in attempts {
+            let correct = attempt.outcome == Outcome::Correct.as_str();
+            if correct && attempt.answer == answer {
+                anyhow::bail!("{} was already submitted and is correct", answer);
+            }
+            if correct {
+                anyhow::bail!("already solved, the answer is {}", attempt.answer);
+            }
+            let wrong = [Outcome::Incorrect, Outcome::TooHigh, Outcome::TooLow]
+                .iter()
+                .any(|outcome| attempt.outcome == outcome.as_str());
+            if wrong && attempt.answer == answer {
+                anyhow::bail!(
+                    "{} was already submitted and is {}",
+                    answer,
+                    attempt.outcome.replace('_', " ")
+                );
+            }
+
+            let (Some(number), Ok(previous)) = (number, attempt.answer.parse::<i64>()) else {
+                continue;
+            };
+            if attempt.outcome == Outcome::TooHigh.as_str() && number >= previous {
+                anyhow::bail!("{} is too high, {} already was", answer, previous);
+            }
+            if attempt.outcome == Outcome::TooLow.as_str() && number <= previous {
+                anyhow::bail!("{} is too low, {} already was", answer, previous);
+            }
+        }
+        Ok(())
+    }
+}
+
+impl std::str::FromStr for AttemptLog {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let attempts = s
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .map(|(i, line)| {
+                let value = json::parse(line).with_context(|| format!("line {}", i + 1))?;
+                Attempt::from_json(&value)
+                    .with_context(|| format!("line {}: missing or invalid fields", i + 1))
+            })
+            .collect::<anyhow::Result<_>>()?;
+        Ok(Self { attempts })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn attempt(part: u8, answer: &str, outcome: Outcome, wait_until: Option<u64>) -> Attempt {
+        Attempt {
+            year: 2015,
+            day: 1,
+            part,
+            answer: answer.to_string(),
+            outcome: outcome.as_str().to_string(),
+            time: 100,
+            wait_until,
+        }
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let attempts = [
+            attempt(1, "42", Outcome::TooHigh, Some(160)),
+            attempt(1, "12", Outcome::Correct, None),
+        ];
+        let contents: String = attempts
+            .iter()
+            .map(|attempt| attempt.to_json().dump() + "\n")
+            .collect();
+        let log: AttemptLog = contents.parse().unwrap();
+        assert_eq!(log.attempts, attempts);
+        assert_eq!(log.wait_until(), Some(160));
+    }
+
+    #[test]
+    fn test_check() {
+        let log = AttemptLog {
+            attempts: vec![
+                attempt(1, "232", Outcome::Correct, None),
+                attempt(2, "2000", Outcome::TooHigh, Some(160)),
+                attempt(2, "1000", Outcome::TooLow, Some(220)),
+                attempt(2, "1500", Outcome::Incorrect, Some(280)),
+            ],
+        };
+        assert!(log.check(2015, 1, 1, "232").is_err());
+        assert!(log.check(2015, 1, 1, "233").is_err());
+        assert!(log.check(2015, 1, 2, "2001").is_err());
+        assert!(log.check(2015, 1, 2, "1000").is_err());
+        assert!(log.check(2015, 1, 2, "1500").is_err());
+        assert!(log.check(2015, 1, 2, "1783").is_ok());
+        assert!(log.check(2015, 2, 1, "232").is_ok());
+    }
+}
@@ -0,0 +1,189 @@
+use std::path::Path;
+use std::time::Duration;
+
+use anyhow::Context;
+
+/// Summary of the durations measured over repeated runs.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Statistics {
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+    /// The sample standard deviation, zero for a single sample.
+    pub stddev: Duration,
+}
+
+impl Statistics {
+    /// Computes the statistics of `samples`, `None` if there are none.
+    pub fn new(samples: &[Duration]) -> Option<Self> {
+        let mut sorted = samples.to_vec();
+        sorted.sort();
+        let min = *sorted.first()?;
+
+        let middle = sorted.len() / 2;
+        let median = if sorted.len().is_multiple_of(2) {
+            (sorted[middle - 1] + sorted[middle]) / 2
+        } else {
+            sorted[middle]
+        };
+
+        let count = sorted.len() as f64;
+        let mean = sorted.iter().map(Duration::as_secs_f64).sum::<f64>() / count;
+        let variance = if sorted.len() > 1 {
+            sorted
+                .iter()
+                .map(|sample| (sample.as_secs_f64() - mean).powi(2))
+                .sum::<f64>()
+                / (count - 1.0)
+        } else {
+            0.0
+        };
+
+        Some(Self {
+            min,
+            median,
+            mean: Duration::from_secs_f64(mean),
+            stddev: Duration::from_secs_f64(variance.sqrt()),
+        })
+    }
+}
+
+/// Benchmark results saved by an earlier run, to compare later runs against.
+///
+/// The results are stored as TOML, with a table per year, day and step:
+///
+/// ```toml
+/// [2015.day04.part1]
+/// min_ns = 81234567
+/// median_ns = 83456789
+/// mean_ns = 83567890
+/// stddev_ns = 1234567
+/// ```
+#[derive(Debug, Default)]
+pub struct Baseline(toml::Table);
+
+impl Baseline {
+    pub fn load(path: &Path) -> anyhow::Result<Self> {
+        let contents = std::fs::read_to_string(path)
+            .with_context(|| format!("failed to read baseline from {}", path.display()))?;
+        contents
+            .parse()
+            .with_context(|| format!("invalid baseline in {}", path.display()))
+    }
+
+    /// Loads the baseline at `path`, or an empty one if there is no file yet.
+    pub fn load_or_default(path: &Path) -> anyhow::Result<Self> {
+        if path.exists() {
+            Self::load(path)
+        } else {
+            Ok(Self::default())
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> anyhow::Result<()> {
+        std::fs::write(path, self.0.to_string())
+            .with_context(|| format!("failed to write baseline to {}", path.display()))
+    }
+
+    /// Returns the median duration of `step` of `day` in `year`.
+    pub fn median(&self, year: u16, day: usize, step: &str) -> Option<Duration> {
+        let nanos = self
+            .0
+            .get(&year.to_string())?
+            .get(format!("day{:02}", day))?
+            .get(step)?
+            .get("median_ns")?
+            .as_integer()?;
+        Some(Duration::from_nanos(nanos.try_into().ok()?))
+    }
+
+    /// Records `statistics` for `step` of `day` in `year`, replacing earlier
+    /// results of the same step.
+    pub fn insert(&mut self, year: u16, day: usize, step: &str, statistics: &Statistics) {
+        let nanos = |duration: Duration| {
+            toml::Value::Integer(duration.as_nanos().try_into().unwrap_or(i64::MAX))
+        };
+        let mut results = toml::Table::new();
+        results.insert("min_ns".to_string(), nanos(statistics.min));
+        results.insert("median_ns".to_string(), nanos(statistics.median));
+        results.insert("mean_ns".to_string(), nanos(statistics.mean));
+        results.insert("stddev_ns".to_string(), nanos(statistics.stddev));
+
+        let mut table = &mut self.0;
+        for key in [year.to_string(), format!("day{:02}", day)] {
+            let entry = table
+                .entry(key)
+                .or_insert_with(|| toml::Value::Table(toml::Table::new()));
+            if !entry.is_table() {
+                *entry = toml::Value::Table(toml::Table::new());
+            }
+            table = entry.as_table_mut().unwrap();
+        }
+        table.insert(step.to_string(), toml::Value::Table(results));
+    }
+}
+
+impl std::str::FromStr for Baseline {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Self(s.parse()?))
+    }
+}
+
+/// Returns by how many percent `duration` is slower than `baseline`, negative
+/// if it is faster.
+pub fn change(baseline: Duration, duration: Duration) -> f64 {
+    (duration.as_secs_f64() / baseline.as_secs_f64() - 1.0) * 100.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_statistics() {
+        let millis = |values: &[u64]| -> Vec<Duration> {
+            values.iter().copied().map(Duration::from_millis).collect()
+        };
+
+        let statistics = Statistics::new(&millis(&[4, 2, 9, 5])).unwrap();
+        assert_eq!(statistics.min, Duration::from_millis(2));
+        assert_eq!(statistics.median, Duration::from_micros(4500));
+        assert_eq!(statistics.mean.as_micros(), 5000);
+        assert_eq!(statistics.stddev.as_micros(), 2943);
+
+        let statistics = Statistics::new(&millis(&[3])).unwrap();
+        assert_eq!(statistics.median, Duration::from_millis(3));
+        assert_eq!(statistics.stddev, Duration::ZERO);
+
+        assert_eq!(Statistics::new(&[]), None);
+    }
+
+    #[test]
+    fn test_baseline() {
+        let statistics = Statistics::new(&[Duration::from_millis(7)]).unwrap();
+        let mut baseline = Baseline::default();
+        baseline.insert(2015, 4, "part1", &statistics);
+        baseline.insert(2015, 4, "part2", &statistics);
+
+        let baseline: Baseline = baseline.0.to_string().parse().unwrap();
+        assert_eq!(
+            baseline.median(2015, 4, "part1"),
+            Some(Duration::from_millis(7))
+        );
+        assert_eq!(
+            baseline.median(2015, 4, "part2"),
+            Some(Duration::from_millis(7))
+        );
+        assert_eq!(baseline.median(2015, 4, "parse"), None);
+        assert_eq!(baseline.median(2016, 4, "part1"), None);
+    }
+
+    #[test]
+    fn test_change() {
+        let baseline = Duration::from_millis(100);
+        assert!((change(baseline, Duration::from_millis(125)) - 25.0).abs() < 1e-9);
+        assert!((change(baseline, Duration::from_millis(50)) + 50.0).abs() < 1e-9);
+    }
+}
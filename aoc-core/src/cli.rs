@@ -0,0 +1,1307 @@
+use std::io::{IsTerminal, Write};
+use std::panic::AssertUnwindSafe;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use anyhow::Context;
+use clap::{ArgAction, Args, Parser, Subcommand, ValueEnum};
+use json::JsonValue;
+
+use crate::attempts::{Attempt, AttemptLog};
+use crate::bench::{Baseline, Statistics};
+use crate::client::{Client, Outcome, DEFAULT_BASE_URL};
+use crate::config::Config;
+use crate::expected::ExpectedAnswers;
+use crate::input::{input_path, read_input, save_input};
+use crate::logger::{self, DayFilter};
+use crate::render::Renderer;
+use crate::table::Table;
+use crate::watch::Watcher;
+use crate::{report, Answer, Answers, Parts, Puzzle, Year};
+
+#[derive(Parser, Debug)]
+#[clap(name = "aoc", subcommand_negates_reqs = true)]
+struct Cli {
+    #[clap(subcommand)]
+    command: Option<Command>,
+
+    /// The directory containing the inputs, named like `2015/day07.input`
+    #[clap(
+        long,
+        global = true,
+        env = "AOC_INPUTS",
+        default_value = "inputs",
+        value_name = "DIR"
+    )]
+    inputs: PathBuf,
+
+    /// How to print the results
+    #[clap(long, global = true, value_enum, default_value = "text")]
+    format: Format,
+
+    /// Which parts to solve: `1`, `2` or `both`
+    #[clap(
+        long,
+        global = true,
+        value_parser = parse_parts,
+        default_value = "both",
+        value_name = "PART"
+    )]
+    part: Parts,
+
+    /// The config file with the session cookie, defaults to
+    /// `~/.config/aoc/config.toml`
+    #[clap(long, global = true, env = "AOC_CONFIG", value_name = "FILE")]
+    config: Option<PathBuf>,
+
+    /// Overrides a parameter of a day, given as `name=value` or as
+    /// `dayNN.name=value` to only apply to one day. The `params` command lists
+    /// the parameters of each day
+    #[clap(
+        long = "param",
+        global = true,
+        value_parser = parse_param,
+        value_name = "NAME=VALUE"
+    )]
+    params: Vec<ParamOverride>,
+
+    /// Logs what the days are doing to stderr, `-v` for steps and `-vv` for
+    /// every detail
+    #[clap(short, long, global = true, action = ArgAction::Count)]
+    verbose: u8,
+
+    /// Only logs the events of the given days, like `2015/day18` or `day18`
+    /// for that day of every year
+    #[clap(long = "log-day", global = true, value_parser, value_name = "DAY")]
+    log_days: Vec<DayFilter>,
+
+    #[clap(flatten)]
+    run: RunArgs,
+}
+
+// Arguments for running a single day, used when no subcommand is given.
+#[derive(Args, Debug)]
+struct RunArgs {
+    /// The year of the puzzle, e.g. `2015`
+    #[clap(required = true)]
+    year: Option<u16>,
+
+    /// The day to run, e.g. `day07`
+    #[clap(value_parser = parse_day, required = true)]
+    day: Option<usize>,
+
+    /// The file containing the puzzle input, `-` reads from stdin. Defaults
+    /// to the day's file in the inputs directory
+    input: Option<PathBuf>,
+}
+
+#[derive(Args, Debug)]
+struct BenchArgs {
+    /// The year to benchmark, all years if omitted
+    year: Option<u16>,
+
+    /// The day to benchmark, all days with an input if omitted
+    #[clap(value_parser = parse_day, requires = "year")]
+    day: Option<usize>,
+
+    /// How often each day is run for measuring
+    #[clap(long, default_value_t = 10, value_parser = clap::value_parser!(u32).range(1..))]
+    runs: u32,
+
+    /// How often each day is run before measuring
+    #[clap(long, default_value_t = 1)]
+    warmup: u32,
+
+    /// Saves the results to this TOML file, keeping the results of other days
+    #[clap(long, value_name = "FILE")]
+    save: Option<PathBuf>,
+
+    /// Compares the results to ones saved earlier with `--save`
+    #[clap(long, value_name = "FILE")]
+    baseline: Option<PathBuf>,
+
+    /// By how many percent a median may exceed the baseline before it is
+    /// reported as a regression
+    #[clap(long, default_value_t = 10.0, value_name = "PERCENT")]
+    threshold: f64,
+}
+
+#[derive(Args, Debug)]
+struct WatchArgs {
+    /// The year of the puzzle, e.g. `2015`
+    year: u16,
+
+    /// The day to run, e.g. `day07`
+    #[clap(value_parser = parse_day)]
+    day: usize,
+
+    /// The file containing the puzzle input. Defaults to the day's file in the
+    /// inputs directory
+    input: Option<PathBuf>,
+
+    /// A file with an example input, which is solved before the puzzle input
+    #[clap(long, value_name = "FILE")]
+    examples: Option<PathBuf>,
+
+    /// How often the files are checked for changes, in milliseconds
+    #[clap(
+        long,
+        default_value_t = 200,
+        value_name = "MS",
+        value_parser = clap::value_parser!(u64).range(1..)
+    )]
+    interval: u64,
+}
+
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+enum Format {
+    /// Human readable answers and tables
+    Text,
+    /// A single JSON document with the answers, timings and errors of each day
+    Json,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Runs every day that has an input and prints a table of the answers
+    /// and timings
+    All {
+        /// The year to run, all years if omitted
+        year: Option<u16>,
+    },
+    /// Runs every day that has an input and compares the answers to the
+    /// expected ones
+    Verify {
+        /// The year to verify, all years if omitted
+        year: Option<u16>,
+
+        /// The TOML file containing the expected answers
+        #[clap(long, default_value = "answers.toml")]
+        answers: PathBuf,
+    },
+    /// Runs a day or all days repeatedly and prints statistics of the timings
+    Bench(BenchArgs),
+    /// Downloads the puzzle input of a day into the inputs directory, unless
+    /// it was downloaded before
+    Fetch {
+        /// The year of the puzzle, e.g. `2015`
+        year: u16,
+
+        /// The day of the puzzle, e.g. `day07`
+        #[clap(value_parser = parse_day)]
+        day: usize,
+
+        /// The website to download from, defaults to the one in the config or
+        /// https://adventofcode.com
+        #[clap(long, env = "AOC_BASE_URL", value_name = "URL")]
+        base_url: Option<String>,
+    },
+    /// Solves a part of a day and submits the answer, unless it is known to
+    /// be wrong
+    Submit(SubmitArgs),
+    /// Draws how a day is solved as PNG images and animated GIFs, for days
+    /// with a visualization
+    Render {
+        /// The year of the puzzle, e.g. `2015`
+        year: u16,
+
+        /// The day to draw, e.g. `day18`
+        #[clap(value_parser = parse_day)]
+        day: usize,
+
+        /// The file containing the puzzle input, `-` reads from stdin.
+        /// Defaults to the day's file in the inputs directory
+        input: Option<PathBuf>,
+
+        /// The directory to write the images to, below a directory per year
+        #[clap(long, default_value = "images", value_name = "DIR")]
+        out: PathBuf,
+    },
+    /// Runs a day whenever its input or example file changes, showing the
+    /// answers and timings on a cleared screen. Changes to the source need a
+    /// rebuild, e.g. by running this command through `cargo watch`
+    Watch(WatchArgs),
+    /// Lists the parameters of each day, which can be overridden with
+    /// `--param`
+    Params {
+        /// The year to list, all years if omitted
+        year: Option<u16>,
+
+        /// The day to list, all days if omitted
+        #[clap(value_parser = parse_day, requires = "year")]
+        day: Option<usize>,
+    },
+    /// Creates a new day from a template, which the build registers
+    /// automatically
+    NewDay {
+        /// The year of the puzzle, e.g. `2015`
+        year: u16,
+
+        /// The day to create, the one after the last existing day if omitted
+        #[clap(value_parser = parse_day)]
+        day: Option<usize>,
+
+        /// The source directory of the year's crate, defaults to
+        /// `<YEAR>/rust/src`
+        #[clap(long, value_name = "DIR")]
+        src: Option<PathBuf>,
+
+        /// The title of the puzzle, e.g. `One-Time Pad`, written in the
+        /// header of the day
+        #[clap(long)]
+        title: Option<String>,
+    },
+}
+
+#[derive(Args, Debug)]
+struct SubmitArgs {
+    /// The year of the puzzle, e.g. `2015`
+    year: u16,
+
+    /// The day of the puzzle, e.g. `day07`
+    #[clap(value_parser = parse_day)]
+    day: usize,
+
+    /// The part to submit the answer to, `1` or `2`
+    // A distinct id, as `part` already names the global `--part` option.
+    #[clap(
+        id = "submit-part",
+        value_name = "PART",
+        value_parser = clap::value_parser!(u8).range(1..=2)
+    )]
+    part: u8,
+
+    /// Waits for the cooldown after an earlier submission to end instead of
+    /// failing
+    #[clap(long)]
+    wait: bool,
+
+    /// The log of submitted answers, defaults to `submissions.jsonl` in the
+    /// inputs directory
+    #[clap(long, value_name = "FILE")]
+    log: Option<PathBuf>,
+
+    /// The website to submit to, defaults to the one in the config or
+    /// https://adventofcode.com
+    #[clap(long, env = "AOC_BASE_URL", value_name = "URL")]
+    base_url: Option<String>,
+}
+
+/// Parses a day given as `dayNN` into its number.
+pub(crate) fn parse_day(s: &str) -> anyhow::Result<usize> {
+    let number = s
+        .strip_prefix("day")
+        .with_context(|| format!("expected a day like `day07`, got {:?}", s))?;
+    number
+        .parse()
+        .with_context(|| format!("invalid day number: {:?}", number))
+}
+
+fn parse_parts(s: &str) -> anyhow::Result<Parts> {
+    match s {
+        "1" => Ok(Parts::One),
+        "2" => Ok(Parts::Two),
+        "both" => Ok(Parts::Both),
+        _ => anyhow::bail!("expected `1`, `2` or `both`, got {:?}", s),
+    }
+}
+
+/// A parameter given on the command line.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct ParamOverride {
+    /// The only day the parameter applies to, if any.
+    day: Option<usize>,
+    name: String,
+    value: String,
+}
+
+/// Parses a parameter given as `name=value` or `dayNN.name=value`.
+fn parse_param(s: &str) -> anyhow::Result<ParamOverride> {
+    let (key, value) = s
+        .split_once('=')
+        .with_context(|| format!("expected `name=value`, got {:?}", s))?;
+    let (day, name) = match key.split_once('.') {
+        Some((day, name)) => (Some(parse_day(day)?), name),
+        None => (None, key),
+    };
+    Ok(ParamOverride {
+        day,
+        name: name.to_string(),
+        value: value.to_string(),
+    })
+}
+
+/// The available years along with the options shared by all commands.
+struct Session<'a> {
+    years: &'a [Year],
+    inputs: PathBuf,
+    format: Format,
+    parts: Parts,
+    config: Option<PathBuf>,
+    params: Vec<ParamOverride>,
+}
+
+impl<'a> Session<'a> {
+    /// Returns the solvers of `year`.
+    fn year(&self, year: u16) -> anyhow::Result<&'a Year> {
+        self.years
+            .iter()
+            .find(|candidate| candidate.year == year)
+            .with_context(|| {
+                let years: Vec<String> = self.years.iter().map(|y| y.year.to_string()).collect();
+                format!(
+                    "there are no solutions for {}, choose one of {}",
+                    year,
+                    years.join(", ")
+                )
+            })
+    }
+
+    /// Returns the solvers of `year`, or of all years if none is given.
+    fn select_years(&self, year: Option<u16>) -> anyhow::Result<&'a [Year]> {
+        match year {
+            Some(year) => Ok(std::slice::from_ref(self.year(year)?)),
+            None => Ok(self.years),
+        }
+    }
+
+    /// Creates the solver for `day` of `year`, with the parameters given on
+    /// the command line that the day declares.
+    fn puzzle(&self, year: &Year, day: usize) -> anyhow::Result<Box<dyn Puzzle>> {
+        let mut puzzle = year.puzzle(day)?;
+        let declared = puzzle.params();
+        for param in &self.params {
+            let applies = param.day.is_none_or(|only| only == day);
+            if applies && declared.iter().any(|p| p.name == param.name) {
+                puzzle
+                    .set_param(&param.name, &param.value)
+                    .with_context(|| format!("day{:02} of {}", day, year.year))?;
+            }
+        }
+        Ok(puzzle)
+    }
+
+    /// Fails if a parameter given on the command line isn't declared by any of
+    /// the selected days, either `day` or all days of `years`.
+    fn check_params(&self, years: &[Year], day: Option<usize>) -> anyhow::Result<()> {
+        for param in &self.params {
+            let declared = years.iter().any(|year| {
+                (1..=year.days.len())
+                    .filter(|candidate| day.is_none_or(|day| day == *candidate))
+                    .filter(|candidate| param.day.is_none_or(|only| only == *candidate))
+                    .filter_map(|candidate| year.puzzle(candidate).ok())
+                    .any(|puzzle| puzzle.params().iter().any(|p| p.name == param.name))
+            });
+            if !declared {
+                anyhow::bail!(
+                    "no selected day has a parameter {}, see the `params` command",
+                    param.name
+                );
+            }
+        }
+        Ok(())
+    }
+
+    /// Solves the selected parts of `day` of `year`.
+    fn solve(&self, year: &Year, day: usize, input: &str) -> anyhow::Result<Answers> {
+        self.puzzle(year, day)?.solve(input, self.parts)
+    }
+
+    /// Returns a client for the website at `base_url`, or the one in the
+    /// config if none is given.
+    fn client(&self, base_url: Option<&str>) -> anyhow::Result<Client> {
+        let config = Config::load(self.config.as_deref())?;
+        let base_url = base_url
+            .or(config.base_url.as_deref())
+            .unwrap_or(DEFAULT_BASE_URL);
+        Ok(Client::new(
+            base_url,
+            &config.session()?,
+            config.contact.as_deref(),
+        ))
+    }
+
+    /// Prints the JSON document describing the given `years`.
+    fn print_document(&self, years: Vec<JsonValue>) {
+        println!("{}", report::document(years).pretty(2));
+    }
+}
+
+/// Prints the answer to `part`, answers spanning multiple lines start on a
+/// line of their own.
+fn print_answer(part: u8, answer: &anyhow::Result<Answer>) {
+    match answer {
+        Ok(Answer::Text(text)) if text.contains('\n') => println!("Part {}:\n{}", part, text),
+        Ok(answer) => println!("Part {}: {}", part, answer),
+        Err(error) => println!("Part {}: error: {:#}", part, error),
+    }
+}
+
+/// Reads the input given on the command line, or the day's input in the
+/// inputs directory if none was given.
+fn read_day_input(
+    session: &Session,
+    year: &Year,
+    day: usize,
+    input: Option<&Path>,
+) -> anyhow::Result<String> {
+    if let Some(path) = input {
+        return read_input(path).with_context(|| format!("failed to read {}", path.display()));
+    }
+
+    let path = input_path(&session.inputs, year.year, day);
+    if !path.exists() {
+        anyhow::bail!(
+            "no input for day{:02} of {}, expected it at {} \
+             (pass a path, use --inputs or set AOC_INPUTS)",
+            day,
+            year.year,
+            path.display()
+        );
+    }
+    read_input(&path).with_context(|| format!("failed to read {}", path.display()))
+}
+
+fn run(session: &Session, year: u16, day: usize, input: Option<&Path>) -> anyhow::Result<()> {
+    let year = session.year(year)?;
+    year.puzzle(day)?;
+    session.check_params(std::slice::from_ref(year), Some(day))?;
+    let answers = read_day_input(session, year, day, input)
+        .and_then(|input| catch_panic(|| session.solve(year, day, input.as_str())));
+    let run = DayRun { day, answers };
+    let solved = run.is_solved();
+
+    match session.format {
+        Format::Text => {
+            let answers = run.answers?;
+            for (part, answer) in [(1, &answers.part1), (2, &answers.part2)] {
+                if let Some(answer) = answer {
+                    print_answer(part, answer);
+                }
+            }
+        }
+        Format::Json => {
+            let days = vec![report::day(day, &run.answers)];
+            session.print_document(vec![report::year(year.year, days, &[])]);
+        }
+    }
+    if !solved {
+        anyhow::bail!("failed to solve day{:02} of {}", day, year.year);
+    }
+
+    Ok(())
+}
+
+/// Formats an answer so that it fits into a single table cell.
+fn answer_cell(answer: &Answer) -> String {
+    match answer {
+        Answer::Text(text) if text.contains('\n') => "(multiple lines)".to_string(),
+        answer => answer.to_string(),
+    }
+}
+
+fn table_cell(answer: &Option<anyhow::Result<Answer>>) -> String {
+    match answer {
+        Some(Ok(answer)) => answer_cell(answer),
+        Some(Err(_)) => "error".to_string(),
+        None => "-".to_string(),
+    }
+}
+
+/// The result of running a single day on its input.
+struct DayRun {
+    day: usize,
+    answers: anyhow::Result<Answers>,
+}
+
+impl DayRun {
+    /// Whether the selected parts were solved without error.
+    fn is_solved(&self) -> bool {
+        matches!(&self.answers, Ok(answers) if answers.is_ok())
+    }
+}
+
+/// A day along with its puzzle input.
+type DayInput = (usize, String);
+
+/// Reads the inputs of all days of `year`, which exist in the inputs directory.
+/// Returns the days with their inputs and the days without input.
+fn read_day_inputs(session: &Session, year: &Year) -> anyhow::Result<(Vec<DayInput>, Vec<usize>)> {
+    let mut found = vec![];
+    let mut missing_inputs = vec![];
+    for day in 1..=year.days.len() {
+        let path = input_path(&session.inputs, year.year, day);
+        if !path.exists() {
+            missing_inputs.push(day);
+            continue;
+        }
+
+        let input =
+            read_input(&path).with_context(|| format!("failed to read {}", path.display()))?;
+        found.push((day, input));
+    }
+    Ok((found, missing_inputs))
+}
+
+/// Calls `f`, turning a panic into an error. A panicking day shouldn't prevent
+/// us from running all the others.
+fn catch_panic<T>(f: impl FnOnce() -> anyhow::Result<T>) -> anyhow::Result<T> {
+    std::panic::catch_unwind(AssertUnwindSafe(f))
+        .unwrap_or_else(|_| Err(anyhow::anyhow!("panicked")))
+}
+
+/// Runs each day of `year`, for which an input exists in the inputs directory.
+/// Returns the results and the days without input.
+fn run_days(session: &Session, year: &Year) -> anyhow::Result<(Vec<DayRun>, Vec<usize>)> {
+    let (found, missing_inputs) = read_day_inputs(session, year)?;
+    let runs = found
+        .into_iter()
+        .map(|(day, input)| {
+            let answers = catch_panic(|| session.solve(year, day, input.as_str()));
+            DayRun { day, answers }
+        })
+        .collect();
+    Ok((runs, missing_inputs))
+}
+
+/// Prints the days of each year without input, given as pairs of the year and
+/// its days.
+fn print_missing_inputs(session: &Session, missing_inputs: &[(u16, Vec<usize>)]) {
+    let mut printed_separator = false;
+    for (year, days) in missing_inputs {
+        if days.is_empty() {
+            continue;
+        }
+        if !printed_separator {
+            println!();
+            printed_separator = true;
+        }
+        let names: Vec<String> = days.iter().map(|day| format!("day{:02}", day)).collect();
+        let directory = session.inputs.join(year.to_string());
+        println!(
+            "No input in {} for: {}",
+            directory.display(),
+            names.join(", ")
+        );
+    }
+}
+
+fn print_problems(problems: &[String]) {
+    if !problems.is_empty() {
+        println!();
+        for problem in problems {
+            println!("{}", problem);
+        }
+    }
+}
+
+fn run_all(session: &Session, year: Option<u16>) -> anyhow::Result<()> {
+    let years = session.select_years(year)?;
+    session.check_params(years, None)?;
+
+    let mut table = Table::new(&["year", "day", "part 1", "part 2", "parse", "solve"]);
+    let mut results = vec![];
+    let mut missing_inputs = vec![];
+    let mut problems = vec![];
+    for year in years {
+        let (runs, missing) = run_days(session, year)?;
+        let days = runs
+            .iter()
+            .map(|run| report::day(run.day, &run.answers))
+            .collect();
+        results.push(report::year(year.year, days, &missing));
+        missing_inputs.push((year.year, missing));
+
+        for DayRun { day, answers } in runs {
+            let name = format!("day{:02}", day);
+            match answers {
+                Ok(answers) => {
+                    for (part, answer) in [(1, &answers.part1), (2, &answers.part2)] {
+                        if let Some(Err(error)) = answer {
+                            problems
+                                .push(format!("{} {} part {}: {:#}", year.year, name, part, error));
+                        }
+                    }
+                    table.push(vec![
+                        year.year.to_string(),
+                        name,
+                        table_cell(&answers.part1),
+                        table_cell(&answers.part2),
+                        format!("{:.1?}", answers.timings.parse),
+                        format!("{:.1?}", answers.timings.solve()),
+                    ]);
+                }
+                Err(error) => {
+                    problems.push(format!("{} {}: {:#}", year.year, name, error));
+                    let failed = "error".to_string();
+                    let missing = "-".to_string();
+                    table.push(vec![
+                        year.year.to_string(),
+                        name,
+                        failed.clone(),
+                        failed,
+                        missing.clone(),
+                        missing,
+                    ]);
+                }
+            }
+        }
+    }
+
+    match session.format {
+        Format::Text => {
+            print!("{}", table);
+            print_missing_inputs(session, &missing_inputs);
+            print_problems(&problems);
+        }
+        Format::Json => session.print_document(results),
+    }
+
+    Ok(())
+}
+
+/// The outcome of comparing the answer to one part with the expected answer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Verdict {
+    Pass,
+    Fail,
+    Missing,
+}
+
+impl Verdict {
+    fn as_str(self) -> &'static str {
+        match self {
+            Verdict::Pass => "pass",
+            Verdict::Fail => "fail",
+            Verdict::Missing => "missing",
+        }
+    }
+}
+
+/// Compares `answer` to `expected` and returns the verdict along with a
+/// description of the answer.
+fn verify_answer(expected: Option<String>, answer: &Result<Answer, String>) -> (Verdict, String) {
+    match (expected, answer) {
+        (Some(expected), Ok(answer)) if expected == answer.to_string() => {
+            (Verdict::Pass, answer_cell(answer))
+        }
+        (Some(expected), Ok(answer)) => (
+            Verdict::Fail,
+            format!(
+                "{} (expected {})",
+                answer_cell(answer),
+                answer_cell(&Answer::Text(expected))
+            ),
+        ),
+        (Some(_), Err(error)) => (Verdict::Fail, format!("error: {}", error)),
+        (None, Ok(answer)) => (Verdict::Missing, answer_cell(answer)),
+        (None, Err(error)) => (Verdict::Missing, format!("error: {}", error)),
+    }
+}
+
+fn verify(session: &Session, year: Option<u16>, answers: &Path) -> anyhow::Result<()> {
+    let years = session.select_years(year)?;
+    session.check_params(years, None)?;
+    let expected = ExpectedAnswers::load(answers)?;
+
+    let mut table = Table::new(&["year", "day", "part", "verdict", "answer"]);
+    let mut results = vec![];
+    let mut missing_inputs = vec![];
+    let mut verdicts = vec![];
+    for year in years {
+        let (runs, missing) = run_days(session, year)?;
+        let mut days = vec![];
+        for DayRun { day, answers } in runs {
+            let mut result = report::day(day, &answers);
+            let answers = match answers {
+                Ok(answers) => [answers.part1, answers.part2].map(|answer| {
+                    answer.map(|answer| answer.map_err(|error| format!("{:#}", error)))
+                }),
+                Err(error) => {
+                    let message = format!("{:#}", error);
+                    [1, 2].map(|part| session.parts.includes(part).then(|| Err(message.clone())))
+                }
+            };
+            let mut parts = vec![];
+            for (part, answer) in (1..=2).zip(answers) {
+                let Some(answer) = answer else {
+                    continue;
+                };
+                let expected = expected.get(year.year, day, part);
+                let mut part_result = report::part(part, answer.as_ref().map_err(Clone::clone));
+                part_result["expected"] = expected.clone().into();
+
+                let (verdict, description) = verify_answer(expected, &answer);
+                verdicts.push(verdict);
+                part_result["verdict"] = verdict.as_str().into();
+                parts.push(part_result);
+
+                let label = match verdict {
+                    Verdict::Fail => "FAIL",
+                    verdict => verdict.as_str(),
+                };
+                table.push(vec![
+                    year.year.to_string(),
+                    format!("day{:02}", day),
+                    part.to_string(),
+                    label.to_string(),
+                    description,
+                ]);
+            }
+            result["parts"] = parts.into();
+            days.push(result);
+        }
+        results.push(report::year(year.year, days, &missing));
+        missing_inputs.push((year.year, missing));
+    }
+
+    let count = |verdict| verdicts.iter().filter(|v| **v == verdict).count();
+    let failed = count(Verdict::Fail);
+    match session.format {
+        Format::Text => {
+            print!("{}", table);
+            print_missing_inputs(session, &missing_inputs);
+            println!();
+            println!(
+                "{} passed, {} failed, {} missing",
+                count(Verdict::Pass),
+                failed,
+                count(Verdict::Missing)
+            );
+        }
+        Format::Json => {
+            let mut document = report::document(results);
+            document["summary"] = json::object! {
+                "passed" => count(Verdict::Pass),
+                "failed" => failed,
+                "missing" => count(Verdict::Missing),
+            };
+            println!("{}", document.pretty(2));
+        }
+    }
+    if failed > 0 {
+        anyhow::bail!("{} answers do not match the expected answers", failed);
+    }
+
+    Ok(())
+}
+
+/// The steps of solving a puzzle, which are measured separately by `bench`.
+const STEPS: [&str; 3] = ["parse", "part1", "part2"];
+
+/// Solves `day` of `year` `warmup + runs` times and returns the statistics of
+/// each of the selected [`STEPS`] over the last `runs`.
+fn bench_day(
+    session: &Session,
+    year: &Year,
+    day: usize,
+    input: &str,
+    args: &BenchArgs,
+) -> anyhow::Result<Vec<(&'static str, Statistics)>> {
+    let mut samples = [vec![], vec![], vec![]];
+    for run in 0..args.warmup + args.runs {
+        let answers = session.solve(year, day, input)?;
+        answers.part1.transpose().context("part 1")?;
+        answers.part2.transpose().context("part 2")?;
+        if run >= args.warmup {
+            let timings = answers.timings;
+            samples[0].push(timings.parse);
+            samples[1].push(timings.part1);
+            samples[2].push(timings.part2);
+        }
+    }
+
+    let statistics = STEPS
+        .into_iter()
+        .zip(samples)
+        .enumerate()
+        .filter(|(part, _)| *part == 0 || session.parts.includes(*part as u8))
+        .map(|(_, (step, samples))| {
+            let statistics = Statistics::new(&samples).expect("at least one run");
+            (step, statistics)
+        })
+        .collect();
+    Ok(statistics)
+}
+
+fn bench(session: &Session, args: &BenchArgs) -> anyhow::Result<()> {
+    let years = session.select_years(args.year)?;
+    session.check_params(years, args.day)?;
+    let baseline = args.baseline.as_deref().map(Baseline::load).transpose()?;
+    let mut saved = args
+        .save
+        .as_deref()
+        .map(Baseline::load_or_default)
+        .transpose()?;
+
+    let mut table = Table::new(&[
+        "year", "day", "step", "min", "median", "mean", "stddev", "change",
+    ]);
+    let mut results = vec![];
+    let mut missing_inputs = vec![];
+    let mut problems = vec![];
+    let mut regressions = 0;
+    for year in years {
+        let (found, missing) = match args.day {
+            Some(day) => {
+                year.puzzle(day)?;
+                let input = read_day_input(session, year, day, None)?;
+                (vec![(day, input)], vec![])
+            }
+            None => read_day_inputs(session, year)?,
+        };
+
+        let mut days = vec![];
+        for (day, input) in found {
+            let name = format!("day{:02}", day);
+            let statistics = catch_panic(|| bench_day(session, year, day, input.as_str(), args));
+            let statistics = match statistics {
+                Ok(statistics) => statistics,
+                Err(error) => {
+                    let error = format!("{:#}", error);
+                    problems.push(format!("{} {}: {}", year.year, name, error));
+                    days.push(json::object! { "day" => day, "error" => error });
+                    continue;
+                }
+            };
+
+            let mut steps = vec![];
+            for (step, statistics) in statistics {
+                if let Some(saved) = &mut saved {
+                    saved.insert(year.year, day, step, &statistics);
+                }
+                let change = baseline
+                    .as_ref()
+                    .and_then(|baseline| baseline.median(year.year, day, step))
+                    .filter(|median| !median.is_zero())
+                    .map(|median| crate::bench::change(median, statistics.median));
+                let regressed = change.is_some_and(|change| change > args.threshold);
+                if regressed {
+                    regressions += 1;
+                }
+
+                let change_cell = match change {
+                    Some(change) if regressed => format!("{:+.1}% (regression)", change),
+                    Some(change) => format!("{:+.1}%", change),
+                    None => "-".to_string(),
+                };
+                table.push(vec![
+                    year.year.to_string(),
+                    name.clone(),
+                    step.to_string(),
+                    format!("{:.1?}", statistics.min),
+                    format!("{:.1?}", statistics.median),
+                    format!("{:.1?}", statistics.mean),
+                    format!("{:.1?}", statistics.stddev),
+                    change_cell,
+                ]);
+                steps.push(json::object! {
+                    "step" => step,
+                    "min_us" => report::microseconds(statistics.min),
+                    "median_us" => report::microseconds(statistics.median),
+                    "mean_us" => report::microseconds(statistics.mean),
+                    "stddev_us" => report::microseconds(statistics.stddev),
+                    "change_percent" => change,
+                    "regression" => regressed,
+                });
+            }
+            days.push(json::object! { "day" => day, "steps" => steps });
+        }
+        results.push(report::year(year.year, days, &missing));
+        missing_inputs.push((year.year, missing));
+    }
+
+    if let (Some(path), Some(saved)) = (&args.save, &saved) {
+        saved.save(path)?;
+    }
+
+    match session.format {
+        Format::Text => {
+            print!("{}", table);
+            print_missing_inputs(session, &missing_inputs);
+            print_problems(&problems);
+        }
+        Format::Json => session.print_document(results),
+    }
+    if regressions > 0 {
+        anyhow::bail!(
+            "{} steps are more than {}% slower than the baseline",
+            regressions,
+            args.threshold
+        );
+    }
+
+    Ok(())
+}
+
+fn fetch(session: &Session, year: u16, day: usize, base_url: Option<&str>) -> anyhow::Result<()> {
+    if !(1..=25).contains(&day) {
+        anyhow::bail!("there is no day{:02}, choose one of day01 to day25", day);
+    }
+
+    let path = input_path(&session.inputs, year, day);
+    if path.exists() {
+        println!("Already downloaded to {}", path.display());
+        return Ok(());
+    }
+
+    let input = session.client(base_url)?.input(year, day)?;
+    save_input(&path, &input).with_context(|| format!("failed to write {}", path.display()))?;
+    println!("Downloaded to {}", path.display());
+
+    Ok(())
+}
+
+fn render(
+    session: &Session,
+    year: u16,
+    day: usize,
+    input: Option<&Path>,
+    out: &Path,
+) -> anyhow::Result<()> {
+    let year = session.year(year)?;
+    session.check_params(std::slice::from_ref(year), Some(day))?;
+    let puzzle = session.puzzle(year, day)?;
+    let input = read_day_input(session, year, day, input)?;
+
+    let mut renderer = Renderer::new(out, year.year, day);
+    let result = puzzle
+        .render(&input, &mut renderer)
+        .with_context(|| format!("failed to draw day{:02} of {}", day, year.year));
+    for path in renderer.written() {
+        println!("Wrote {}", path.display());
+    }
+
+    result
+}
+
+/// Clears the terminal and moves the cursor to its top left corner.
+const CLEAR_SCREEN: &str = "\x1b[2J\x1b[H";
+
+/// Prints the answers to a watched file along with their timings.
+fn print_watched_answers(answers: &anyhow::Result<Answers>) {
+    match answers {
+        Ok(answers) => {
+            for (part, answer) in [(1, &answers.part1), (2, &answers.part2)] {
+                if let Some(answer) = answer {
+                    print_answer(part, answer);
+                }
+            }
+            println!(
+                "Parsed in {:.1?}, solved in {:.1?}",
+                answers.timings.parse,
+                answers.timings.solve()
+            );
+        }
+        Err(error) => println!("error: {:#}", error),
+    }
+}
+
+fn watch(session: &Session, args: &WatchArgs) -> anyhow::Result<()> {
+    if session.format != Format::Text {
+        anyhow::bail!("watch only prints text");
+    }
+    let year = session.year(args.year)?;
+    year.puzzle(args.day)?;
+    session.check_params(std::slice::from_ref(year), Some(args.day))?;
+
+    let input = args
+        .input
+        .clone()
+        .unwrap_or_else(|| input_path(&session.inputs, year.year, args.day));
+    let mut files = vec![];
+    if let Some(examples) = &args.examples {
+        files.push(("Examples", examples.as_path()));
+    }
+    files.push(("Input", input.as_path()));
+    if files.iter().any(|(_, path)| *path == Path::new("-")) {
+        anyhow::bail!("stdin can't be watched, pass a file");
+    }
+
+    let paths: Vec<&Path> = files.iter().map(|(_, path)| *path).collect();
+    let mut watcher = Watcher::new(&paths);
+    let interval = Duration::from_millis(args.interval);
+    let mut stdout = std::io::stdout();
+    loop {
+        if stdout.is_terminal() {
+            print!("{}", CLEAR_SCREEN);
+        }
+        println!(
+            "Watching day{:02} of {}, stop with Ctrl+C",
+            args.day, year.year
+        );
+        for (name, path) in &files {
+            println!();
+            println!("{} from {}", name, path.display());
+            let answers = read_input(path)
+                .with_context(|| format!("failed to read {}", path.display()))
+                .and_then(|input| catch_panic(|| session.solve(year, args.day, input.as_str())));
+            print_watched_answers(&answers);
+        }
+        stdout.flush()?;
+        watcher.wait(interval);
+    }
+}
+
+/// The current time in seconds since the Unix epoch.
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |duration| duration.as_secs())
+}
+
+fn submit(session: &Session, args: &SubmitArgs) -> anyhow::Result<()> {
+    let year = session.year(args.year)?;
+    session.check_params(std::slice::from_ref(year), Some(args.day))?;
+    let puzzle = session.puzzle(year, args.day)?;
+    let name = format!("day{:02} part {} of {}", args.day, args.part, year.year);
+
+    let input = read_day_input(session, year, args.day, None)?;
+    let parts = if args.part == 1 {
+        Parts::One
+    } else {
+        Parts::Two
+    };
+    let answers = puzzle.solve(input.as_str(), parts)?;
+    let answer = if args.part == 1 {
+        answers.part1
+    } else {
+        answers.part2
+    };
+    let answer = answer
+        .context("the part wasn't solved")?
+        .with_context(|| format!("failed to solve {}", name))?;
+    if let Answer::Text(text) = &answer {
+        if text.contains('\n') {
+            anyhow::bail!(
+                "the answer spans multiple lines, read it and submit it by hand:\n{}",
+                text
+            );
+        }
+    }
+    let answer = answer.to_string();
+
+    let log_path = args
+        .log
+        .clone()
+        .unwrap_or_else(|| session.inputs.join("submissions.jsonl"));
+    let log = AttemptLog::load(&log_path)?;
+    log.check(year.year, args.day, args.part, &answer)?;
+    if let Some(wait_until) = log.wait_until() {
+        let remaining = wait_until.saturating_sub(unix_now());
+        if remaining > 0 && !args.wait {
+            anyhow::bail!(
+                "an answer was submitted too recently, wait {}s or pass --wait",
+                remaining
+            );
+        }
+        if remaining > 0 {
+            println!("Waiting {}s for the cooldown to end", remaining);
+            std::thread::sleep(Duration::from_secs(remaining));
+        }
+    }
+
+    let client = session.client(args.base_url.as_deref())?;
+    let submission = client.submit(year.year, args.day, args.part, &answer)?;
+    let time = unix_now();
+    let attempt = Attempt {
+        year: year.year,
+        day: args.day,
+        part: args.part,
+        answer: answer.clone(),
+        outcome: submission.outcome.as_str().to_string(),
+        time,
+        wait_until: submission.wait.map(|wait| time + wait.as_secs()),
+    };
+    AttemptLog::append(&log_path, &attempt)?;
+
+    println!("Submitted {} to {}: {}", answer, name, submission.outcome);
+    if let Some(wait) = submission.wait {
+        println!("Wait {}s before submitting another answer", wait.as_secs());
+    }
+    if submission.outcome != Outcome::Correct {
+        anyhow::bail!("{} was not accepted", answer);
+    }
+
+    Ok(())
+}
+
+fn list_params(session: &Session, year: Option<u16>, day: Option<usize>) -> anyhow::Result<()> {
+    let years = session.select_years(year)?;
+    let mut table = Table::new(&["year", "day", "name", "default", "description"]);
+    for year in years {
+        for candidate in 1..=year.days.len() {
+            if day.is_some_and(|day| day != candidate) {
+                continue;
+            }
+            for param in year.puzzle(candidate)?.params() {
+                table.push(vec![
+                    year.year.to_string(),
+                    format!("day{:02}", candidate),
+                    param.name.to_string(),
+                    param.value,
+                    param.description.to_string(),
+                ]);
+            }
+        }
+    }
+    print!("{}", table);
+
+    Ok(())
+}
+
+fn new_day(
+    year: u16,
+    day: Option<usize>,
+    src: Option<PathBuf>,
+    title: Option<&str>,
+) -> anyhow::Result<()> {
+    let src = src.unwrap_or_else(|| Path::new(&year.to_string()).join("rust").join("src"));
+    let days = aoc_build::find_days(&src)
+        .with_context(|| format!("failed to list the days in {}", src.display()))?;
+    let next = days.last().map_or(1, |last| last + 1);
+    let day = day.unwrap_or(next);
+    if !(1..=25).contains(&day) {
+        anyhow::bail!("there is no day{:02}, choose one of day01 to day25", day);
+    }
+    if days.contains(&day) {
+        anyhow::bail!("day{:02} of {} already exists", day, year);
+    }
+    if day > next {
+        anyhow::bail!(
+            "days have to be consecutive, create day{:02} of {} first",
+            next,
+            year
+        );
+    }
+
+    let path = src.join(format!("day{:02}.rs", day));
+    std::fs::write(&path, aoc_build::day_template(day, title))
+        .with_context(|| format!("failed to write {}", path.display()))?;
+    println!("Created {}", path.display());
+
+    Ok(())
+}
+
+/// Parses the command line and runs the selected command with the solvers of
+/// the given `years`.
+pub fn main(years: &[Year]) -> anyhow::Result<()> {
+    let args = Cli::parse();
+    if args.command.is_some() && args.run.year.is_some() {
+        anyhow::bail!("a year and day can't be combined with a subcommand");
+    }
+    logger::init(args.verbose, args.log_days)?;
+    let session = Session {
+        years,
+        inputs: args.inputs,
+        format: args.format,
+        parts: args.part,
+        config: args.config,
+        params: args.params,
+    };
+    match args.command {
+        Some(Command::All { year }) => run_all(&session, year),
+        Some(Command::Verify { year, answers }) => verify(&session, year, &answers),
+        Some(Command::Bench(bench_args)) => bench(&session, &bench_args),
+        Some(Command::Fetch {
+            year,
+            day,
+            base_url,
+        }) => fetch(&session, year, day, base_url.as_deref()),
+        Some(Command::Submit(submit_args)) => submit(&session, &submit_args),
+        Some(Command::Render {
+            year,
+            day,
+            input,
+            out,
+        }) => render(&session, year, day, input.as_deref(), &out),
+        Some(Command::Watch(watch_args)) => watch(&session, &watch_args),
+        Some(Command::Params { year, day }) => list_params(&session, year, day),
+        Some(Command::NewDay {
+            year,
+            day,
+            src,
+            title,
+        }) => new_day(year, day, src, title.as_deref()),
+        None => {
+            let year = args.run.year.context("no year given")?;
+            let day = args.run.day.context("no day given")?;
+            run(&session, year, day, args.run.input.as_deref())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_day() {
+        assert_eq!(parse_day("day07").unwrap(), 7);
+        assert_eq!(parse_day("day23").unwrap(), 23);
+        assert!(parse_day("07").is_err());
+        assert!(parse_day("dayx").is_err());
+    }
+
+    #[test]
+    fn test_parse_parts() {
+        assert_eq!(parse_parts("1").unwrap(), Parts::One);
+        assert_eq!(parse_parts("2").unwrap(), Parts::Two);
+        assert_eq!(parse_parts("both").unwrap(), Parts::Both);
+        assert!(parse_parts("3").is_err());
+    }
+
+    #[test]
+    fn test_parse_param() {
+        assert_eq!(
+            parse_param("steps=4").unwrap(),
+            ParamOverride {
+                day: None,
+                name: "steps".to_string(),
+                value: "4".to_string(),
+            }
+        );
+        assert_eq!(
+            parse_param("day13.target=7,4").unwrap(),
+            ParamOverride {
+                day: Some(13),
+                name: "target".to_string(),
+                value: "7,4".to_string(),
+            }
+        );
+        assert!(parse_param("steps").is_err());
+        assert!(parse_param("13.target=7,4").is_err());
+    }
+
+    #[test]
+    fn test_verify_answer() {
+        let answer: Result<Answer, String> = Ok(Answer::Integer(42));
+        assert_eq!(
+            verify_answer(Some("42".to_string()), &answer),
+            (Verdict::Pass, "42".to_string())
+        );
+        assert_eq!(
+            verify_answer(Some("41".to_string()), &answer),
+            (Verdict::Fail, "42 (expected 41)".to_string())
+        );
+        assert_eq!(
+            verify_answer(None, &answer),
+            (Verdict::Missing, "42".to_string())
+        );
+        assert_eq!(
+            verify_answer(Some("42".to_string()), &Err("oops".to_string())),
+            (Verdict::Fail, "error: oops".to_string())
+        );
+        assert_eq!(
+            verify_answer(None, &Err("oops".to_string())),
+            (Verdict::Missing, "error: oops".to_string())
+        );
+    }
+
+    #[test]
+    fn test_cli() {
+        use clap::CommandFactory;
+
+        Cli::command().debug_assert();
+    }
+}
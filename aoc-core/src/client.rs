@@ -0,0 +1,381 @@
+use std::time::Duration;
+
+use anyhow::Context;
+
+/// The Advent of Code website, used unless another base URL is configured.
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+
+/// Identifies us to the website, as its operators ask automated tools to do,
+/// with the `contact` of whoever runs it if configured.
+pub fn user_agent(contact: Option<&str>) -> String {
+    let mut comment = String::from(
+        "Advent of Code solutions runner; caches downloaded inputs and rate limits submissions",
+    );
+    if let Some(contact) = contact {
+        comment.push_str("; contact: ");
+        comment.push_str(contact);
+    }
+    format!("aoc/{} ({})", env!("CARGO_PKG_VERSION"), comment)
+}
+
+/// What the website made of a submitted answer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Outcome {
+    Correct,
+    Incorrect,
+    TooHigh,
+    TooLow,
+    /// The answer wasn't checked, because the previous one was submitted too
+    /// recently.
+    TooSoon,
+    /// The part was already solved, or its first part is still unsolved.
+    WrongLevel,
+    /// A response we don't understand, holding its text.
+    Unknown(String),
+}
+
+impl Outcome {
+    /// A short name of the outcome, as stored in the attempt log.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Outcome::Correct => "correct",
+            Outcome::Incorrect => "incorrect",
+            Outcome::TooHigh => "too_high",
+            Outcome::TooLow => "too_low",
+            Outcome::TooSoon => "too_soon",
+            Outcome::WrongLevel => "wrong_level",
+            Outcome::Unknown(_) => "unknown",
+        }
+    }
+
+    /// Whether the website rejected the answer as wrong.
+    pub fn is_wrong(&self) -> bool {
+        matches!(
+            self,
+            Outcome::Incorrect | Outcome::TooHigh | Outcome::TooLow
+        )
+    }
+}
+
+impl std::fmt::Display for Outcome {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Outcome::Correct => write!(f, "the answer is correct"),
+            Outcome::Incorrect => write!(f, "the answer is incorrect"),
+            Outcome::TooHigh => write!(f, "the answer is too high"),
+            Outcome::TooLow => write!(f, "the answer is too low"),
+            Outcome::TooSoon => write!(f, "the answer was submitted too soon"),
+            Outcome::WrongLevel => write!(f, "the part is already solved or not unlocked"),
+            Outcome::Unknown(text) => write!(f, "unexpected response: {}", text),
+        }
+    }
+}
+
+/// The response to a submitted answer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Submission {
+    pub outcome: Outcome,
+    /// How long to wait before submitting another answer.
+    pub wait: Option<Duration>,
+}
+
+/// Returns the text of the `<article>` in a response, without any tags.
+fn article_text(html: &str) -> String {
+    let start = html.find("<article").unwrap_or(0);
+    let end = html[start..]
+        .find("</article>")
+        .map_or(html.len(), |end| start + end);
+
+    let mut text = String::new();
+    let mut in_tag = false;
+    for c in html[start..end].chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            c if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Parses how long to wait out of sentences like "You have 1m 5s left to
+/// wait" or "please wait 5 minutes before trying again".
+fn parse_wait(text: &str) -> Option<Duration> {
+    if let Some(start) = text.find("You have ") {
+        let rest = &text[start + "You have ".len()..];
+        let end = rest.find(" left to wait")?;
+        let mut seconds = 0;
+        for amount in rest[..end].split_whitespace() {
+            let (unit_start, _) = amount.char_indices().next_back()?;
+            let (number, unit) = amount.split_at(unit_start);
+            let number: u64 = number.parse().ok()?;
+            seconds += match unit {
+                "h" => number * 3600,
+                "m" => number * 60,
+                "s" => number,
+                _ => return None,
+            };
+        }
+        return Some(Duration::from_secs(seconds));
+    }
+
+    let start = text.find("wait ")?;
+    let mut words = text[start + "wait ".len()..].split_whitespace();
+    let number = match words.next()? {
+        "one" | "a" => 1,
+        number => number.parse().ok()?,
+    };
+    match words.next()?.trim_end_matches(['.', ',']) {
+        "minute" | "minutes" => Some(Duration::from_secs(number * 60)),
+        "second" | "seconds" => Some(Duration::from_secs(number)),
+        _ => None,
+    }
+}
+
+/// Interprets the HTML `response` to a submitted answer.
+pub fn parse_submission(response: &str) -> Submission {
+    let text = article_text(response);
+    let outcome = if text.contains("That's the right answer") {
+        Outcome::Correct
+    } else if text.contains("That's not the right answer") {
+        if text.contains("too high") {
+            Outcome::TooHigh
+        } else if text.contains("too low") {
+            Outcome::TooLow
+        } else {
+            Outcome::Incorrect
+        }
+    } else if text.contains("You gave an answer too recently") {
+        Outcome::TooSoon
+    } else if text.contains("You don't seem to be solving the right level") {
+        Outcome::WrongLevel
+    } else {
+        Outcome::Unknown(text.clone())
+    };
+    let wait = match outcome {
+        Outcome::Correct | Outcome::WrongLevel | Outcome::Unknown(_) => None,
+        _ => parse_wait(&text),
+    };
+    Submission { outcome, wait }
+}
+
+/// A client for the Advent of Code website, authenticated with the session
+/// cookie of a logged in user.
+pub struct Client {
+    agent: ureq::Agent,
+    base_url: String,
+    session: String,
+}
+
+impl Client {
+    pub fn new(base_url: &str, session: &str, contact: Option<&str>) -> Self {
+        let agent = ureq::AgentBuilder::new()
+            .user_agent(&user_agent(contact))
+            .timeout(Duration::from_secs(30))
+            .build();
+        Self {
+            agent,
+            base_url: base_url.trim_end_matches('/').to_string(),
+            session: session.to_string(),
+        }
+    }
+
+    /// Downloads the personal puzzle input to `day` of `year`.
+    pub fn input(&self, year: u16, day: usize) -> anyhow::Result<String> {
+        let url = format!("{}/{}/day/{}/input", self.base_url, year, day);
+        let response = self
+            .agent
+            .get(&url)
+            .set("Cookie", &format!("session={}", self.session))
+            .call();
+        match response {
+            Ok(response) => response
+                .into_string()
+                .with_context(|| format!("failed to read the response from {}", url)),
+            Err(ureq::Error::Status(404, _)) => {
+                anyhow::bail!("the input to day{:02} of {} isn't available yet", day, year)
+            }
+            Err(ureq::Error::Status(400, _)) => {
+                anyhow::bail!("the session cookie was rejected, it may have expired")
+            }
+            Err(error) => Err(error).with_context(|| format!("failed to download {}", url)),
+        }
+    }
+
+    /// Submits `answer` to `part` of `day` in `year`.
+    pub fn submit(
+        &self,
+        year: u16,
+        day: usize,
+        part: u8,
+        answer: &str,
+    ) -> anyhow::Result<Submission> {
+        let url = format!("{}/{}/day/{}/answer", self.base_url, year, day);
+        let response = self
+            .agent
+            .post(&url)
+            .set("Cookie", &format!("session={}", self.session))
+            .send_form(&[("level", &part.to_string()), ("answer", answer)]);
+        match response {
+            Ok(response) => {
+                let body = response
+                    .into_string()
+                    .with_context(|| format!("failed to read the response from {}", url))?;
+                Ok(parse_submission(&body))
+            }
+            Err(ureq::Error::Status(400, _)) => {
+                anyhow::bail!("the session cookie was rejected, it may have expired")
+            }
+            Err(error) => Err(error).with_context(|| format!("failed to submit to {}", url)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpListener;
+    use std::thread::JoinHandle;
+
+    use super::*;
+
+    /// Serves `response` to a single request and returns the base URL of the
+    /// server along with a handle resolving to the request's head and body.
+    fn serve_once(response: String) -> (String, JoinHandle<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let handle = std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut request = String::new();
+            let mut reader = BufReader::new(&mut stream);
+            let mut length = 0;
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if line == "\r\n" || line.is_empty() {
+                    break;
+                }
+                if let Some(value) = line.strip_prefix("Content-Length: ") {
+                    length = value.trim().parse().unwrap();
+                }
+                request.push_str(&line);
+            }
+            let mut body = vec![0; length];
+            reader.read_exact(&mut body).unwrap();
+            request.push_str(std::str::from_utf8(&body).unwrap());
+            stream.write_all(response.as_bytes()).unwrap();
+            request
+        });
+        (url, handle)
+    }
+
+    #[test]
+    fn test_input() {
+        let (url, server) =
+            serve_once("HTTP/1.1 200 OK\r\nContent-Length: 6\r\n\r\n(()))\n".to_string());
+        let client = Client::new(&url, "secret", Some("me@example.com"));
+        assert_eq!(client.input(2015, 1).unwrap(), "(()))\n");
+
+        let request = server.join().unwrap();
+        assert!(request.starts_with("GET /2015/day/1/input HTTP/1.1\r\n"));
+        assert!(request.contains("Cookie: session=secret\r\n"));
+        assert!(request.contains(&format!(
+            "User-Agent: {}\r\n",
+            user_agent(Some("me@example.com"))
+        )));
+        assert!(request.contains("; contact: me@example.com)\r\n"));
+    }
+
+    #[test]
+    fn test_input_not_available() {
+        let (url, server) =
+            serve_once("HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\n\r\n".to_string());
+        let error = Client::new(&url, "secret", None)
+            .input(2015, 25)
+            .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "the input to day25 of 2015 isn't available yet"
+        );
+        server.join().unwrap();
+    }
+
+    #[test]
+    fn test_submit() {
+        let body = "<main><article><p>That's the right answer!</p></article></main>";
+        let response = format!(
+            "HTTP/1.1 200 OK\r\nContent-Length: {}\r\n\r\n{}",
+            body.len(),
+            body
+        );
+        let (url, server) = serve_once(response);
+        let submission = Client::new(&url, "secret", None)
+            .submit(2015, 1, 2, "1783")
+            .unwrap();
+        assert_eq!(submission.outcome, Outcome::Correct);
+
+        let request = server.join().unwrap();
+        assert!(request.starts_with("POST /2015/day/1/answer HTTP/1.1\r\n"));
+        assert!(request.contains("Cookie: session=secret\r\n"));
+        assert!(request.ends_with("level=2&answer=1783"));
+    }
+
+    #[test]
+    fn test_parse_submission() {
+        let parse =
+            |article: &str| parse_submission(&format!("<article><p>{}</p></article>", article));
+
+        assert_eq!(
+            parse("That's the right answer! You are <em>one gold star</em> closer."),
+            Submission {
+                outcome: Outcome::Correct,
+                wait: None
+            }
+        );
+        assert_eq!(
+            parse(
+                "That's not the right answer; your answer is too high. Please wait one \
+                 minute before trying again."
+            ),
+            Submission {
+                outcome: Outcome::TooHigh,
+                wait: Some(Duration::from_secs(60))
+            }
+        );
+        assert_eq!(
+            parse(
+                "That's not the right answer; your answer is too low. Please wait 5 \
+                 minutes before trying again."
+            ),
+            Submission {
+                outcome: Outcome::TooLow,
+                wait: Some(Duration::from_secs(300))
+            }
+        );
+        assert_eq!(
+            parse("That's not the right answer. Please wait one minute before trying again."),
+            Submission {
+                outcome: Outcome::Incorrect,
+                wait: Some(Duration::from_secs(60))
+            }
+        );
+        assert_eq!(
+            parse("You gave an answer too recently. You have 1m 5s left to wait."),
+            Submission {
+                outcome: Outcome::TooSoon,
+                wait: Some(Duration::from_secs(65))
+            }
+        );
+        assert_eq!(parse_wait("You have 5é left to wait."), None);
+        assert_eq!(
+            parse("You don't seem to be solving the right level. Did you already complete it?")
+                .outcome,
+            Outcome::WrongLevel
+        );
+        assert_eq!(
+            parse("Something else").outcome,
+            Outcome::Unknown("Something else".to_string())
+        );
+    }
+}
@@ -0,0 +1,112 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::Context;
+
+/// The environment variable holding the session cookie, which takes precedence
+/// over the one in the config file.
+pub const SESSION_VAR: &str = "AOC_SESSION";
+
+/// Settings for talking to the Advent of Code website, read from a TOML file:
+///
+/// ```toml
+/// session = "53616c7465645f5f..."
+/// base_url = "https://adventofcode.com"
+/// contact = "you@example.com"
+/// ```
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Config {
+    pub session: Option<String>,
+    pub base_url: Option<String>,
+    /// How the website's operators can reach you, sent in the User-Agent.
+    pub contact: Option<String>,
+}
+
+impl Config {
+    /// Loads the config from `path`, or from the default location if none is
+    /// given. A missing file at the default location results in an empty
+    /// config.
+    pub fn load(path: Option<&Path>) -> anyhow::Result<Self> {
+        let path = match path {
+            Some(path) => path.to_path_buf(),
+            None => match default_path() {
+                Some(path) if path.exists() => path,
+                _ => return Ok(Self::default()),
+            },
+        };
+        let contents = std::fs::read_to_string(&path)
+            .with_context(|| format!("failed to read config from {}", path.display()))?;
+        contents
+            .parse()
+            .with_context(|| format!("invalid config in {}", path.display()))
+    }
+
+    /// Returns the session cookie from the environment or the config.
+    pub fn session(&self) -> anyhow::Result<String> {
+        let session = std::env::var(SESSION_VAR)
+            .ok()
+            .or_else(|| self.session.clone())
+            .map(|session| session.trim().to_string())
+            .filter(|session| !session.is_empty());
+        session.with_context(|| {
+            format!(
+                "no session cookie, set {} or `session` in {}",
+                SESSION_VAR,
+                default_path().map_or("the config file".to_string(), |path| path
+                    .display()
+                    .to_string())
+            )
+        })
+    }
+}
+
+impl std::str::FromStr for Config {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let table: toml::Table = s.parse()?;
+        let string = |key: &str| -> anyhow::Result<Option<String>> {
+            match table.get(key) {
+                None => Ok(None),
+                Some(toml::Value::String(value)) => Ok(Some(value.clone())),
+                Some(value) => anyhow::bail!("expected `{}` to be a string, got {}", key, value),
+            }
+        };
+        Ok(Self {
+            session: string("session")?,
+            base_url: string("base_url")?,
+            contact: string("contact")?,
+        })
+    }
+}
+
+/// The default location of the config file, `aoc/config.toml` in the user's
+/// config directory.
+pub fn default_path() -> Option<PathBuf> {
+    let config_dir = std::env::var_os("XDG_CONFIG_HOME")
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|home| Path::new(&home).join(".config")))?;
+    Some(config_dir.join("aoc").join("config.toml"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let config: Config = r#"
+            session = "abc"
+            base_url = "http://localhost:8080"
+            contact = "me@example.com"
+        "#
+        .parse()
+        .unwrap();
+        assert_eq!(config.session.as_deref(), Some("abc"));
+        assert_eq!(config.base_url.as_deref(), Some("http://localhost:8080"));
+        assert_eq!(config.contact.as_deref(), Some("me@example.com"));
+
+        assert_eq!("".parse::<Config>().unwrap(), Config::default());
+        assert!("session = 42".parse::<Config>().is_err());
+    }
+}
@@ -0,0 +1,72 @@
+use std::path::Path;
+
+use anyhow::Context;
+
+/// Known correct answers, used to check that solvers still produce them.
+///
+/// The answers are stored as TOML, with a table per year and day:
+///
+/// ```toml
+/// [2015.day01]
+/// part1 = 232
+/// part2 = 1783
+/// ```
+#[derive(Debug, Default)]
+pub struct ExpectedAnswers(toml::Table);
+
+impl ExpectedAnswers {
+    pub fn load(path: &Path) -> anyhow::Result<Self> {
+        let contents = std::fs::read_to_string(path)
+            .with_context(|| format!("failed to read answers from {}", path.display()))?;
+        contents
+            .parse()
+            .with_context(|| format!("invalid answers in {}", path.display()))
+    }
+
+    /// Returns the expected answer to `part` of `day` in `year` as it would be
+    /// displayed.
+    pub fn get(&self, year: u16, day: usize, part: u8) -> Option<String> {
+        let value = self
+            .0
+            .get(&year.to_string())?
+            .get(format!("day{:02}", day))?
+            .get(format!("part{}", part))?;
+        match value {
+            toml::Value::String(s) => Some(s.clone()),
+            value => Some(value.to_string()),
+        }
+    }
+}
+
+impl std::str::FromStr for ExpectedAnswers {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Self(s.parse()?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_get() {
+        let expected: ExpectedAnswers = r#"
+            [2015.day01]
+            part1 = 232
+            part2 = "1783"
+
+            [2016.day02]
+            part1 = "abc"
+        "#
+        .parse()
+        .unwrap();
+        assert_eq!(expected.get(2015, 1, 1), Some("232".to_string()));
+        assert_eq!(expected.get(2015, 1, 2), Some("1783".to_string()));
+        assert_eq!(expected.get(2016, 2, 1), Some("abc".to_string()));
+        assert_eq!(expected.get(2016, 2, 2), None);
+        assert_eq!(expected.get(2016, 3, 1), None);
+        assert_eq!(expected.get(2017, 1, 1), None);
+    }
+}
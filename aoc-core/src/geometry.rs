@@ -0,0 +1,252 @@
+use std::fmt;
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+
+/// A point on a 2D grid, or the offset between two points. `y` grows
+/// downwards like the rows of a [`Grid`](crate::grid::Grid), so north is up.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub struct Point {
+    pub x: i64,
+    pub y: i64,
+}
+
+impl Point {
+    pub const ORIGIN: Point = Point::new(0, 0);
+
+    pub const fn new(x: i64, y: i64) -> Self {
+        Self { x, y }
+    }
+
+    /// The distance when moving only horizontally and vertically.
+    pub fn manhattan_distance(self, other: Point) -> i64 {
+        (self.x - other.x).abs() + (self.y - other.y).abs()
+    }
+
+    /// The distance when moving diagonally too, like a king on a chessboard.
+    pub fn chebyshev_distance(self, other: Point) -> i64 {
+        (self.x - other.x).abs().max((self.y - other.y).abs())
+    }
+
+    /// Returns the points sharing an edge with this one, in the order of
+    /// [`Direction::ALL`].
+    pub fn neighbors(self) -> [Point; 4] {
+        Direction::ALL.map(|direction| self + direction)
+    }
+}
+
+impl fmt::Display for Point {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{},{}", self.x, self.y)
+    }
+}
+
+impl Add for Point {
+    type Output = Point;
+
+    fn add(self, other: Point) -> Point {
+        Point::new(self.x + other.x, self.y + other.y)
+    }
+}
+
+impl Sub for Point {
+    type Output = Point;
+
+    fn sub(self, other: Point) -> Point {
+        Point::new(self.x - other.x, self.y - other.y)
+    }
+}
+
+impl Neg for Point {
+    type Output = Point;
+
+    fn neg(self) -> Point {
+        Point::new(-self.x, -self.y)
+    }
+}
+
+impl Mul<i64> for Point {
+    type Output = Point;
+
+    fn mul(self, factor: i64) -> Point {
+        Point::new(self.x * factor, self.y * factor)
+    }
+}
+
+impl AddAssign for Point {
+    fn add_assign(&mut self, other: Point) {
+        *self = *self + other;
+    }
+}
+
+impl SubAssign for Point {
+    fn sub_assign(&mut self, other: Point) {
+        *self = *self - other;
+    }
+}
+
+/// Moves one step in the direction.
+impl Add<Direction> for Point {
+    type Output = Point;
+
+    fn add(self, direction: Direction) -> Point {
+        self + direction.offset()
+    }
+}
+
+impl AddAssign<Direction> for Point {
+    fn add_assign(&mut self, direction: Direction) {
+        *self = *self + direction;
+    }
+}
+
+/// Which way to turn, by a quarter turn.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Turn {
+    Left,
+    Right,
+}
+
+/// One of the four directions of a grid.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Direction {
+    /// All directions, clockwise from up.
+    pub const ALL: [Direction; 4] = [
+        Direction::Up,
+        Direction::Right,
+        Direction::Down,
+        Direction::Left,
+    ];
+
+    /// The offset of a step in the direction.
+    pub fn offset(self) -> Point {
+        match self {
+            Direction::Up => Point::new(0, -1),
+            Direction::Right => Point::new(1, 0),
+            Direction::Down => Point::new(0, 1),
+            Direction::Left => Point::new(-1, 0),
+        }
+    }
+
+    /// Returns the direction `quarters` quarter turns clockwise from this one.
+    fn rotate(self, quarters: usize) -> Direction {
+        Direction::ALL[(self as usize + quarters) % 4]
+    }
+
+    pub fn turn_left(self) -> Direction {
+        self.rotate(3)
+    }
+
+    pub fn turn_right(self) -> Direction {
+        self.rotate(1)
+    }
+
+    pub fn turn(self, turn: Turn) -> Direction {
+        match turn {
+            Turn::Left => self.turn_left(),
+            Turn::Right => self.turn_right(),
+        }
+    }
+
+    pub fn reverse(self) -> Direction {
+        self.rotate(2)
+    }
+
+    /// Parses an arrow: `^`, `>`, `v` or `<`.
+    pub fn from_arrow(c: char) -> Option<Direction> {
+        match c {
+            '^' => Some(Direction::Up),
+            '>' => Some(Direction::Right),
+            'v' => Some(Direction::Down),
+            '<' => Some(Direction::Left),
+            _ => None,
+        }
+    }
+
+    /// Parses the initial of up, right, down or left: `U`, `R`, `D` or `L`.
+    pub fn from_letter(c: char) -> Option<Direction> {
+        match c {
+            'U' => Some(Direction::Up),
+            'R' => Some(Direction::Right),
+            'D' => Some(Direction::Down),
+            'L' => Some(Direction::Left),
+            _ => None,
+        }
+    }
+
+    /// Parses a point of the compass: `N`, `E`, `S` or `W`, north being up.
+    pub fn from_compass(c: char) -> Option<Direction> {
+        match c {
+            'N' => Some(Direction::Up),
+            'E' => Some(Direction::Right),
+            'S' => Some(Direction::Down),
+            'W' => Some(Direction::Left),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_arithmetic() {
+        let point = Point::new(3, -2);
+        assert_eq!(point + Point::new(1, 1), Point::new(4, -1));
+        assert_eq!(point - point, Point::ORIGIN);
+        assert_eq!(-point * 2, Point::new(-6, 4));
+        assert_eq!(point + Direction::Up, Point::new(3, -3));
+        assert_eq!(
+            Point::ORIGIN.neighbors(),
+            [
+                Point::new(0, -1),
+                Point::new(1, 0),
+                Point::new(0, 1),
+                Point::new(-1, 0)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_distances() {
+        let point = Point::new(3, -2);
+        assert_eq!(point.manhattan_distance(Point::ORIGIN), 5);
+        assert_eq!(point.chebyshev_distance(Point::ORIGIN), 3);
+        assert_eq!(point.manhattan_distance(point), 0);
+    }
+
+    #[test]
+    fn test_turns() {
+        for direction in Direction::ALL {
+            assert_eq!(direction.turn_left().turn_right(), direction);
+            assert_eq!(
+                direction.turn(Turn::Right).turn(Turn::Right),
+                direction.reverse()
+            );
+            assert_eq!(
+                direction.offset() + direction.reverse().offset(),
+                Point::ORIGIN
+            );
+        }
+        assert_eq!(Direction::Up.turn_left(), Direction::Left);
+        assert_eq!(Direction::Left.turn_right(), Direction::Up);
+    }
+
+    #[test]
+    fn test_parse() {
+        let parsed = |parse: fn(char) -> Option<Direction>, s: &str| -> Vec<_> {
+            s.chars().map(parse).collect()
+        };
+        let all = Direction::ALL.map(Some).to_vec();
+        assert_eq!(parsed(Direction::from_arrow, "^>v<"), all);
+        assert_eq!(parsed(Direction::from_letter, "URDL"), all);
+        assert_eq!(parsed(Direction::from_compass, "NESW"), all);
+        assert_eq!(Direction::from_arrow('U'), None);
+    }
+}
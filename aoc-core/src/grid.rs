@@ -0,0 +1,447 @@
+use std::fmt;
+use std::ops::{Bound, Index, IndexMut, Range, RangeBounds};
+
+use crate::geometry::Point;
+use crate::parse::{self, ParseError};
+
+/// The offsets of the neighbors sharing an edge with a cell: up, right, down
+/// and left.
+const NEIGHBORS4: [(isize, isize); 4] = [(0, -1), (1, 0), (0, 1), (-1, 0)];
+
+/// The offsets of the neighbors sharing an edge or a corner with a cell,
+/// clockwise from the top.
+const NEIGHBORS8: [(isize, isize); 8] = [
+    (0, -1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+    (0, 1),
+    (-1, 1),
+    (-1, 0),
+    (-1, -1),
+];
+
+/// A rectangular grid of cells, addressed by `(x, y)` with `0, 0` at the top
+/// left. The cells are stored row by row in a single `Vec`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// Creates a grid with every cell set to `value`.
+    pub fn new(width: usize, height: usize, value: T) -> Self
+    where
+        T: Clone,
+    {
+        Self {
+            width,
+            height,
+            cells: vec![value; width * height],
+        }
+    }
+
+    /// Creates a grid with each cell given by `cell(x, y)`.
+    pub fn from_fn<F>(width: usize, height: usize, mut cell: F) -> Self
+    where
+        F: FnMut(usize, usize) -> T,
+    {
+        let cells = (0..height)
+            .flat_map(|y| (0..width).map(move |x| (x, y)))
+            .map(|(x, y)| cell(x, y))
+            .collect();
+        Self {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    /// Parses a map with a character per cell and a line per row, converting
+    /// each character with `cell`. Characters `cell` returns `None` for are
+    /// reported as not being `expected`, as are rows differing in length from
+    /// the first.
+    pub fn parse<F>(input: &str, mut cell: F, expected: &str) -> Result<Self, ParseError>
+    where
+        F: FnMut(char) -> Option<T>,
+    {
+        let mut width = None;
+        let mut height = 0;
+        let mut cells = vec![];
+        for line in parse::lines(input) {
+            let mut row_width = 0;
+            for (i, c) in line.text.char_indices() {
+                let value =
+                    cell(c).ok_or_else(|| line.error(&line.text[i..i + c.len_utf8()], expected))?;
+                cells.push(value);
+                row_width += 1;
+            }
+            match width {
+                Some(width) if width != row_width => {
+                    return Err(line.mismatch(format!("{} cells like the first line", width)));
+                }
+                _ => width = Some(row_width),
+            }
+            height += 1;
+        }
+        Ok(Self {
+            width: width.unwrap_or(0),
+            height,
+            cells,
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Whether `x`, `y` is a cell of the grid, given as signed numbers so that
+    /// positions left of or above the grid can be checked too.
+    pub fn contains(&self, x: isize, y: isize) -> bool {
+        x >= 0 && y >= 0 && (x as usize) < self.width && (y as usize) < self.height
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> Option<&T> {
+        if x < self.width && y < self.height {
+            self.cells.get(y * self.width + x)
+        } else {
+            None
+        }
+    }
+
+    pub fn get_mut(&mut self, x: usize, y: usize) -> Option<&mut T> {
+        if x < self.width && y < self.height {
+            self.cells.get_mut(y * self.width + x)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the cell at `point`, if it is in the grid.
+    pub fn get_point(&self, point: Point) -> Option<&T> {
+        let x = usize::try_from(point.x).ok()?;
+        let y = usize::try_from(point.y).ok()?;
+        self.get(x, y)
+    }
+
+    /// Returns the cells row by row.
+    pub fn cells(&self) -> impl Iterator<Item = &T> {
+        self.cells.iter()
+    }
+
+    /// Returns the cells row by row, along with their coordinates.
+    pub fn iter(&self) -> impl Iterator<Item = ((usize, usize), &T)> {
+        let width = self.width.max(1);
+        self.cells
+            .iter()
+            .enumerate()
+            .map(move |(i, cell)| ((i % width, i / width), cell))
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.cells.chunks(self.width.max(1))
+    }
+
+    /// Returns the coordinates of the first cell matching `predicate`, row by
+    /// row.
+    pub fn find<P>(&self, mut predicate: P) -> Option<(usize, usize)>
+    where
+        P: FnMut(&T) -> bool,
+    {
+        self.iter()
+            .find(|(_, cell)| predicate(cell))
+            .map(|(coordinate, _)| coordinate)
+    }
+
+    /// Returns a grid of the same size with each cell converted by `f`.
+    pub fn map<U, F>(&self, mut f: F) -> Grid<U>
+    where
+        F: FnMut(&T) -> U,
+    {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(&mut f).collect(),
+        }
+    }
+
+    /// Returns the coordinates of the cells sharing an edge with `x`, `y`.
+    pub fn neighbors4(&self, x: usize, y: usize) -> impl Iterator<Item = (usize, usize)> {
+        self.neighbors(x, y, &NEIGHBORS4)
+    }
+
+    /// Returns the coordinates of the cells sharing an edge or a corner with
+    /// `x`, `y`.
+    pub fn neighbors8(&self, x: usize, y: usize) -> impl Iterator<Item = (usize, usize)> {
+        self.neighbors(x, y, &NEIGHBORS8)
+    }
+
+    fn neighbors(
+        &self,
+        x: usize,
+        y: usize,
+        offsets: &'static [(isize, isize)],
+    ) -> impl Iterator<Item = (usize, usize)> {
+        let (width, height) = (self.width, self.height);
+        offsets.iter().filter_map(move |(dx, dy)| {
+            let x = x.checked_add_signed(*dx).filter(|x| *x < width)?;
+            let y = y.checked_add_signed(*dy).filter(|y| *y < height)?;
+            Some((x, y))
+        })
+    }
+
+    /// Shifts the cells of row `y` right by `k`, wrapping around.
+    ///
+    /// # Panics
+    ///
+    /// If `y` is outside of the grid.
+    pub fn rotate_row(&mut self, y: usize, k: usize) {
+        assert!(y < self.height, "row {} is outside of the grid", y);
+        let row = &mut self.cells[y * self.width..(y + 1) * self.width];
+        row.rotate_right(k % self.width.max(1));
+    }
+
+    /// Shifts the cells of column `x` down by `k`, wrapping around.
+    ///
+    /// # Panics
+    ///
+    /// If `x` is outside of the grid.
+    pub fn rotate_column(&mut self, x: usize, k: usize) {
+        assert!(x < self.width, "column {} is outside of the grid", x);
+        let k = k % self.height.max(1);
+        for _ in 0..k {
+            for y in (1..self.height).rev() {
+                self.cells
+                    .swap(y * self.width + x, (y - 1) * self.width + x);
+            }
+        }
+    }
+
+    /// Returns the cells in `columns` and `rows`, leaving out the parts of the
+    /// ranges outside of the grid.
+    pub fn view(
+        &self,
+        columns: impl RangeBounds<usize>,
+        rows: impl RangeBounds<usize>,
+    ) -> View<'_, T> {
+        View {
+            columns: clamp(columns, self.width),
+            rows: clamp(rows, self.height),
+            grid: self,
+        }
+    }
+
+    /// Returns the cells in `columns` and `rows` for changing them, leaving out
+    /// the parts of the ranges outside of the grid.
+    pub fn view_mut(
+        &mut self,
+        columns: impl RangeBounds<usize>,
+        rows: impl RangeBounds<usize>,
+    ) -> ViewMut<'_, T> {
+        ViewMut {
+            columns: clamp(columns, self.width),
+            rows: clamp(rows, self.height),
+            grid: self,
+        }
+    }
+}
+
+/// Returns the part of `range` below `len`.
+fn clamp(range: impl RangeBounds<usize>, len: usize) -> Range<usize> {
+    let start = match range.start_bound() {
+        Bound::Included(start) => *start,
+        Bound::Excluded(start) => start.saturating_add(1),
+        Bound::Unbounded => 0,
+    };
+    let end = match range.end_bound() {
+        Bound::Included(end) => end.saturating_add(1),
+        Bound::Excluded(end) => *end,
+        Bound::Unbounded => len,
+    };
+    let end = end.min(len);
+    start.min(end)..end
+}
+
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, (x, y): (usize, usize)) -> &T {
+        self.get(x, y)
+            .unwrap_or_else(|| panic!("{},{} is outside of the grid", x, y))
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    fn index_mut(&mut self, (x, y): (usize, usize)) -> &mut T {
+        self.get_mut(x, y)
+            .unwrap_or_else(|| panic!("{},{} is outside of the grid", x, y))
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_rows(f, self.rows())
+    }
+}
+
+/// Writes each row on a line of its own.
+fn write_rows<'a, T: fmt::Display + 'a>(
+    f: &mut fmt::Formatter<'_>,
+    rows: impl Iterator<Item = &'a [T]>,
+) -> fmt::Result {
+    for row in rows {
+        for cell in row {
+            write!(f, "{}", cell)?;
+        }
+        writeln!(f)?;
+    }
+    Ok(())
+}
+
+/// A rectangle of the cells of a grid.
+#[derive(Debug, Clone)]
+pub struct View<'a, T> {
+    grid: &'a Grid<T>,
+    columns: Range<usize>,
+    rows: Range<usize>,
+}
+
+impl<'a, T> View<'a, T> {
+    pub fn width(&self) -> usize {
+        self.columns.len()
+    }
+
+    pub fn height(&self) -> usize {
+        self.rows.len()
+    }
+
+    /// Returns the cell at `x`, `y` of the view.
+    pub fn get(&self, x: usize, y: usize) -> Option<&'a T> {
+        if x < self.width() && y < self.height() {
+            self.grid.get(self.columns.start + x, self.rows.start + y)
+        } else {
+            None
+        }
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &'a [T]> {
+        let columns = self.columns.clone();
+        self.grid
+            .rows()
+            .skip(self.rows.start)
+            .take(self.rows.len())
+            .map(move |row| &row[columns.clone()])
+    }
+
+    /// Returns the cells row by row.
+    pub fn cells(&self) -> impl Iterator<Item = &'a T> {
+        self.rows().flatten()
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for View<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_rows(f, self.rows())
+    }
+}
+
+/// A rectangle of the cells of a grid, which can be changed.
+#[derive(Debug)]
+pub struct ViewMut<'a, T> {
+    grid: &'a mut Grid<T>,
+    columns: Range<usize>,
+    rows: Range<usize>,
+}
+
+impl<T> ViewMut<'_, T> {
+    pub fn width(&self) -> usize {
+        self.columns.len()
+    }
+
+    pub fn height(&self) -> usize {
+        self.rows.len()
+    }
+
+    pub fn rows_mut(&mut self) -> impl Iterator<Item = &mut [T]> {
+        let columns = self.columns.clone();
+        self.grid
+            .cells
+            .chunks_mut(self.grid.width.max(1))
+            .skip(self.rows.start)
+            .take(self.rows.len())
+            .map(move |row| &mut row[columns.clone()])
+    }
+
+    /// Returns the cells row by row.
+    pub fn cells_mut(&mut self) -> impl Iterator<Item = &mut T> {
+        self.rows_mut().flatten()
+    }
+
+    /// Sets every cell to `value`.
+    pub fn fill(&mut self, value: T)
+    where
+        T: Clone,
+    {
+        for row in self.rows_mut() {
+            row.fill(value.clone());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn numbers() -> Grid<u32> {
+        Grid::parse("123\n456", |c| c.to_digit(10), "a digit").unwrap()
+    }
+
+    #[test]
+    fn test_parse() {
+        let grid = numbers();
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid[(2, 1)], 6);
+        assert_eq!(grid.get(3, 0), None);
+        assert_eq!(grid.to_string(), "123\n456\n");
+
+        let error = Grid::parse("12\n3x", |c| c.to_digit(10), "a digit").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 2));
+        let error = Grid::parse("12\n345", |c| c.to_digit(10), "a digit").unwrap_err();
+        assert_eq!(error.expected, "2 cells like the first line");
+    }
+
+    #[test]
+    fn test_neighbors() {
+        let grid = numbers();
+        let neighbors: Vec<_> = grid.neighbors4(0, 0).collect();
+        assert_eq!(neighbors, vec![(1, 0), (0, 1)]);
+        let neighbors: Vec<_> = grid.neighbors8(1, 1).map(|c| grid[c]).collect();
+        assert_eq!(neighbors, vec![2, 3, 6, 4, 1]);
+    }
+
+    #[test]
+    fn test_rotate() {
+        let mut grid = numbers();
+        grid.rotate_row(0, 4);
+        assert_eq!(grid.to_string(), "312\n456\n");
+        grid.rotate_column(1, 1);
+        assert_eq!(grid.to_string(), "352\n416\n");
+    }
+
+    #[test]
+    fn test_view() {
+        let mut grid = numbers();
+        assert_eq!(grid.view(1.., ..1).to_string(), "23\n");
+        assert_eq!(grid.view(2..=5, 1..9).cells().sum::<u32>(), 6);
+        assert_eq!(grid.view(5.., ..).cells().count(), 0);
+
+        grid.view_mut(..2, ..).fill(0);
+        assert_eq!(grid.to_string(), "003\n006\n");
+    }
+}
@@ -0,0 +1,183 @@
+use std::collections::VecDeque;
+use std::fmt::Write;
+
+use md5::{Digest, Md5};
+use rayon::prelude::*;
+
+/// The raw bytes of an MD5 digest.
+pub type Md5Hash = [u8; 16];
+
+/// Returns the MD5 digest of `s` as a lowercase hexadecimal string.
+pub fn to_md5_hex(s: &str) -> String {
+    let mut hasher = Md5::new();
+    hasher.update(s.as_bytes());
+    to_hex(&hasher.finalize().into())
+}
+
+/// Returns `hash` as a lowercase hexadecimal string.
+pub fn to_hex(hash: &Md5Hash) -> String {
+    let mut hex = String::new();
+    for byte in hash {
+        write!(&mut hex, "{:02x}", byte).unwrap();
+    }
+    hex
+}
+
+/// Counts the zeroes starting the hexadecimal form of `hash`.
+pub fn leading_zeros(hash: &Md5Hash) -> u32 {
+    u128::from_be_bytes(*hash).leading_zeros() / 4
+}
+
+/// Returns the value of the hexadecimal digit of `hash` at `index`.
+///
+/// # Panics
+///
+/// Panics if `index` isn't below 32.
+pub fn hex_digit(hash: &Md5Hash, index: usize) -> u8 {
+    let byte = hash[index / 2];
+    if index.is_multiple_of(2) {
+        byte >> 4
+    } else {
+        byte & 0x0f
+    }
+}
+
+/// Hashes a fixed prefix followed by increasing decimal numbers, the nonces,
+/// to find those whose hashes match a condition.
+#[derive(Debug, Clone)]
+pub struct Miner {
+    /// The hasher state after the prefix, cloned for each nonce.
+    prefix: Md5,
+    batch_size: u64,
+}
+
+impl Miner {
+    pub fn new(prefix: &str) -> Self {
+        let mut hasher = Md5::new();
+        hasher.update(prefix.as_bytes());
+        Self {
+            prefix: hasher,
+            batch_size: 1 << 16,
+        }
+    }
+
+    /// Sets how many nonces are hashed in parallel at a time, 65536 by default.
+    /// Smaller batches waste less work past the last hit needed.
+    pub fn with_batch_size(mut self, batch_size: u64) -> Self {
+        self.batch_size = batch_size.max(1);
+        self
+    }
+
+    /// Returns the hash of the prefix followed by `nonce`.
+    pub fn hash(&self, nonce: u64) -> Md5Hash {
+        let mut digits = [0; 20];
+        let mut start = digits.len();
+        let mut n = nonce;
+        loop {
+            start -= 1;
+            digits[start] = b'0' + (n % 10) as u8;
+            n /= 10;
+            if n == 0 {
+                break;
+            }
+        }
+        let mut hasher = self.prefix.clone();
+        hasher.update(&digits[start..]);
+        hasher.finalize().into()
+    }
+
+    /// Returns the nonces from `start` on whose hashes match `is_hit`, in
+    /// increasing order and along with their hashes. Nonces are only hashed
+    /// as the hits are consumed, a batch at a time.
+    pub fn mine<F>(&self, start: u64, is_hit: F) -> Hits<'_, F>
+    where
+        F: Fn(&Md5Hash) -> bool + Sync,
+    {
+        Hits {
+            miner: self,
+            is_hit,
+            next: Some(start),
+            hits: VecDeque::new(),
+        }
+    }
+}
+
+/// The iterator returned by [`Miner::mine`].
+#[derive(Debug)]
+pub struct Hits<'a, F> {
+    miner: &'a Miner,
+    is_hit: F,
+    /// The first nonce of the next batch, `None` once every nonce was hashed.
+    next: Option<u64>,
+    /// The hits of the last batch not consumed yet.
+    hits: VecDeque<(u64, Md5Hash)>,
+}
+
+impl<F> Iterator for Hits<'_, F>
+where
+    F: Fn(&Md5Hash) -> bool + Sync,
+{
+    type Item = (u64, Md5Hash);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(hit) = self.hits.pop_front() {
+                return Some(hit);
+            }
+            let start = self.next?;
+            let end = start.saturating_add(self.miner.batch_size - 1);
+            self.next = end.checked_add(1);
+            let (miner, is_hit) = (self.miner, &self.is_hit);
+            let hits: Vec<_> = (start..=end)
+                .into_par_iter()
+                .filter_map(|nonce| {
+                    let hash = miner.hash(nonce);
+                    is_hit(&hash).then_some((nonce, hash))
+                })
+                .collect();
+            log::trace!("Mined {}..={}, {} hits", start, end, hits.len());
+            self.hits.extend(hits);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_to_md5_hex() {
+        assert_eq!(to_md5_hex(""), "d41d8cd98f00b204e9800998ecf8427e");
+        assert_eq!(
+            to_md5_hex("abcdef609043"),
+            "000001dbbfa3a5c83a2d506429c7b00e"
+        );
+    }
+
+    #[test]
+    fn test_digits() {
+        let miner = Miner::new("abcdef");
+        let hash = miner.hash(609043);
+        assert_eq!(to_hex(&hash), to_md5_hex("abcdef609043"));
+        assert_eq!(leading_zeros(&hash), 5);
+        assert_eq!((hex_digit(&hash, 5), hex_digit(&hash, 6)), (1, 0xd));
+        assert_eq!(to_hex(&miner.hash(0)), to_md5_hex("abcdef0"));
+        assert_eq!(leading_zeros(&[0; 16]), 32);
+    }
+
+    #[test]
+    fn test_mine() {
+        // Small batches, so that the hits span several of them.
+        let miner = Miner::new("abc").with_batch_size(100);
+        let starts_with_zero = |hash: &Md5Hash| leading_zeros(hash) >= 1;
+        let hits: Vec<_> = miner.mine(0, starts_with_zero).take(20).collect();
+        let expected: Vec<_> = (0..)
+            .map(|nonce| (nonce, miner.hash(nonce)))
+            .filter(|(_, hash)| starts_with_zero(hash))
+            .take(20)
+            .collect();
+        assert_eq!(hits, expected);
+        assert!(hits.last().unwrap().0 > 100);
+        assert_eq!(miner.mine(u64::MAX, |_| true).count(), 1);
+    }
+}
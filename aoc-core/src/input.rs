@@ -0,0 +1,49 @@
+use std::fs::File;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+/// Reads the file at `path` into a String. If the `path` is `-`, stdin is read
+/// instead of the file at path `-`.
+pub fn read_input(path: &Path) -> std::io::Result<String> {
+    let mut buffer = String::new();
+    if let Some("-") = path.to_str() {
+        std::io::stdin().read_to_string(&mut buffer)?;
+    } else {
+        let mut f = File::open(path)?;
+        f.read_to_string(&mut buffer)?;
+    }
+    Ok(buffer)
+}
+
+/// Writes `input` to `path`, creating missing directories. The input is
+/// written to a temporary file first, so that an interrupted write doesn't
+/// leave a truncated input behind.
+pub fn save_input(path: &Path, input: &str) -> std::io::Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let partial = path.with_extension("partial");
+    std::fs::write(&partial, input)?;
+    std::fs::rename(&partial, path)
+}
+
+/// Returns the conventional location of the input to `day` of `year` below the
+/// `inputs` directory, e.g. `inputs/2015/day07.input`.
+pub fn input_path(inputs: &Path, year: u16, day: usize) -> PathBuf {
+    inputs
+        .join(year.to_string())
+        .join(format!("day{:02}.input", day))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_input_path() {
+        assert_eq!(
+            input_path(Path::new("inputs"), 2015, 7),
+            Path::new("inputs/2015/day07.input")
+        );
+    }
+}
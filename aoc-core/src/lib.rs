@@ -0,0 +1,37 @@
+extern crate anyhow;
+extern crate aoc_build;
+extern crate clap;
+extern crate gif;
+extern crate json;
+extern crate log;
+extern crate md5;
+extern crate png;
+extern crate rayon;
+extern crate toml;
+extern crate ureq;
+
+mod answer;
+pub mod attempts;
+pub mod bench;
+pub mod cli;
+pub mod client;
+pub mod config;
+pub mod expected;
+pub mod geometry;
+pub mod grid;
+pub mod hash;
+pub mod input;
+pub mod logger;
+pub mod machine;
+pub mod params;
+pub mod parse;
+pub mod render;
+pub mod report;
+pub mod search;
+mod solution;
+pub mod table;
+pub mod watch;
+
+pub use answer::Answer;
+pub use params::Param;
+pub use solution::{solve_part, Answers, Parts, Puzzle, Solution, Timings, Year};
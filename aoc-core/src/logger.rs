@@ -0,0 +1,149 @@
+use log::{Level, LevelFilter, Log, Metadata, Record};
+
+use crate::cli::parse_day;
+
+/// Selects the days whose events are logged, given like `2015/day18` or like
+/// `day18` for that day of every year.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DayFilter {
+    pub year: Option<u16>,
+    pub day: usize,
+}
+
+impl DayFilter {
+    fn matches(&self, year: u16, day: usize) -> bool {
+        self.year.is_none_or(|only| only == year) && self.day == day
+    }
+}
+
+impl std::str::FromStr for DayFilter {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (year, day) = match s.split_once('/') {
+            Some((year, day)) => (Some(year.parse()?), day),
+            None => (None, s),
+        };
+        Ok(Self {
+            year,
+            day: parse_day(day)?,
+        })
+    }
+}
+
+/// Returns the year and day of a day module, given by its log target like
+/// `aoc_2015::day18`.
+fn day_of(target: &str) -> Option<(u16, usize)> {
+    let mut modules = target.split("::");
+    let year = modules.next()?.strip_prefix("aoc_")?.parse().ok()?;
+    let day = parse_day(modules.next()?).ok()?;
+    Some((year, day))
+}
+
+/// Prints the events of the days to stderr.
+#[derive(Debug)]
+struct Logger {
+    level: LevelFilter,
+    days: Vec<DayFilter>,
+}
+
+impl Log for Logger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        if metadata.level() > self.level {
+            return false;
+        }
+        match day_of(metadata.target()) {
+            Some((year, day)) => {
+                self.days.is_empty() || self.days.iter().any(|days| days.matches(year, day))
+            }
+            // The shared code the days call into, e.g. the hash miner, isn't
+            // attributed to a day so it's logged whatever the days selected.
+            None if metadata.target().starts_with("aoc_core") => true,
+            // Only warnings of other crates, e.g. of the HTTP client.
+            None => metadata.level() <= Level::Warn,
+        }
+    }
+
+    fn log(&self, record: &Record) {
+        if !self.enabled(record.metadata()) {
+            return;
+        }
+        let level = record.level().as_str().to_lowercase();
+        match day_of(record.target()) {
+            Some((year, day)) => {
+                eprintln!("[{} day{:02} {}] {}", year, day, level, record.args())
+            }
+            None => eprintln!("[{} {}] {}", record.target(), level, record.args()),
+        }
+    }
+
+    fn flush(&self) {}
+}
+
+/// Installs the logger. Warnings are always logged, `verbosity` 1 adds debug
+/// events and 2 or more trace events, of the given `days` only if any and of
+/// `aoc_core`.
+pub fn init(verbosity: u8, days: Vec<DayFilter>) -> anyhow::Result<()> {
+    let level = match verbosity {
+        0 => LevelFilter::Warn,
+        1 => LevelFilter::Debug,
+        _ => LevelFilter::Trace,
+    };
+    log::set_boxed_logger(Box::new(Logger { level, days }))?;
+    log::set_max_level(level);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_day_filter() {
+        let filter: DayFilter = "2015/day18".parse().unwrap();
+        assert!(filter.matches(2015, 18));
+        assert!(!filter.matches(2016, 18));
+
+        let filter: DayFilter = "day08".parse().unwrap();
+        assert!(filter.matches(2015, 8));
+        assert!(filter.matches(2016, 8));
+        assert!(!filter.matches(2016, 9));
+
+        assert!("2015/18".parse::<DayFilter>().is_err());
+    }
+
+    #[test]
+    fn test_day_of() {
+        assert_eq!(day_of("aoc_2015::day18"), Some((2015, 18)));
+        assert_eq!(day_of("aoc_2016::day08::screen"), Some((2016, 8)));
+        assert_eq!(day_of("aoc_core::cli"), None);
+        assert_eq!(day_of("ureq::unit"), None);
+    }
+
+    #[test]
+    fn test_enabled() {
+        let logger = Logger {
+            level: LevelFilter::Trace,
+            days: vec!["day18".parse().unwrap()],
+        };
+        let enabled = |target, level| {
+            let metadata = Metadata::builder().target(target).level(level).build();
+            logger.enabled(&metadata)
+        };
+        assert!(enabled("aoc_2015::day18", Level::Trace));
+        assert!(!enabled("aoc_2015::day04", Level::Trace));
+        assert!(enabled("aoc_core::hash", Level::Trace));
+        assert!(!enabled("ureq::unit", Level::Debug));
+        assert!(enabled("ureq::unit", Level::Warn));
+
+        let quiet = Logger {
+            level: LevelFilter::Warn,
+            days: vec![],
+        };
+        let metadata = Metadata::builder()
+            .target("aoc_core::hash")
+            .level(Level::Trace)
+            .build();
+        assert!(!quiet.enabled(&metadata));
+    }
+}
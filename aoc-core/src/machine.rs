@@ -0,0 +1,372 @@
+use std::fmt;
+use std::marker::PhantomData;
+use std::ops::{Index, IndexMut};
+use std::str::FromStr;
+
+use crate::parse::{Line, ParseError};
+
+/// The name of a register of a [`Machine`], usually an enum.
+pub trait Register: Copy + 'static {
+    /// Every register, in the order of their indices.
+    const ALL: &'static [Self];
+
+    /// The position of the register in [`ALL`](Register::ALL).
+    fn index(self) -> usize;
+
+    /// The name of the register in programs, like `a`.
+    fn name(self) -> &'static str;
+}
+
+/// Parses the name of a register, `text` being a slice of `line`.
+pub fn parse_register<R: Register>(line: &Line, text: &str) -> Result<R, ParseError> {
+    R::ALL
+        .iter()
+        .copied()
+        .find(|register| register.name() == text)
+        .ok_or_else(|| {
+            let names: Vec<_> = R::ALL.iter().map(|register| register.name()).collect();
+            line.error(text, format!("a register, one of {}", names.join(", ")))
+        })
+}
+
+/// An operand given either as a register or as a value, like `a` or `-2`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Operand<R, V> {
+    Register(R),
+    Value(V),
+}
+
+impl<R: Register, V: Copy> Operand<R, V> {
+    pub fn value(self, registers: &Registers<R, V>) -> V {
+        match self {
+            Self::Register(register) => registers[register],
+            Self::Value(value) => value,
+        }
+    }
+}
+
+impl<R: Register, V: fmt::Display> fmt::Display for Operand<R, V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Register(register) => write!(f, "{}", register.name()),
+            Self::Value(value) => write!(f, "{}", value),
+        }
+    }
+}
+
+/// Parses an operand, `text` being a slice of `line`.
+pub fn parse_operand<R: Register, V: FromStr>(
+    line: &Line,
+    text: &str,
+) -> Result<Operand<R, V>, ParseError> {
+    match parse_register(line, text) {
+        Ok(register) => Ok(Operand::Register(register)),
+        Err(_) => Ok(Operand::Value(line.parse(text, "a register or a value")?)),
+    }
+}
+
+/// Splits an instruction like `cpy 41 a` or `jie a, +4` into its mnemonic and
+/// its operands, which are separated by spaces or commas.
+pub fn split_instruction<'a>(line: &Line<'a>) -> (&'a str, Vec<&'a str>) {
+    let mut words = line
+        .text
+        .split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|word| !word.is_empty());
+    let mnemonic = words.next().unwrap_or(line.text);
+    (mnemonic, words.collect())
+}
+
+/// The values of the registers named by `R`, all starting at the default
+/// value of `V`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Registers<R, V> {
+    values: Vec<V>,
+    names: PhantomData<R>,
+}
+
+impl<R: Register, V: Copy + Default> Default for Registers<R, V> {
+    fn default() -> Self {
+        Self {
+            values: vec![V::default(); R::ALL.len()],
+            names: PhantomData,
+        }
+    }
+}
+
+impl<R: Register, V: Copy> Registers<R, V> {
+    pub fn get(&self, register: R) -> V {
+        self.values[register.index()]
+    }
+
+    pub fn set(&mut self, register: R, value: V) {
+        self.values[register.index()] = value;
+    }
+}
+
+impl<R: Register, V> Index<R> for Registers<R, V> {
+    type Output = V;
+
+    fn index(&self, register: R) -> &V {
+        &self.values[register.index()]
+    }
+}
+
+impl<R: Register, V> IndexMut<R> for Registers<R, V> {
+    fn index_mut(&mut self, register: R) -> &mut V {
+        &mut self.values[register.index()]
+    }
+}
+
+/// An instruction of the language a [`Machine`] runs.
+pub trait Instruction {
+    type Register: Register + fmt::Debug;
+    /// What the registers hold.
+    type Value: Copy + Default + fmt::Debug;
+
+    /// Whether a jump before the first instruction halts the machine, like a
+    /// jump past the last one, instead of failing.
+    const HALTS_BEFORE_START: bool = false;
+
+    /// Executes the instruction, returning by how many instructions to move
+    /// the program counter: 1 to go on with the next one.
+    fn execute(&self, registers: &mut Registers<Self::Register, Self::Value>) -> isize;
+}
+
+/// Runs a program of instructions `I` on a register file, until the program
+/// counter goes out of the program.
+#[derive(Debug, Clone)]
+pub struct Machine<I: Instruction> {
+    program: Vec<I>,
+    registers: Registers<I::Register, I::Value>,
+    program_counter: usize,
+    executed: u64,
+}
+
+impl<I: Instruction> Machine<I> {
+    /// Creates a machine about to run `program`, with all registers at their
+    /// default value.
+    pub fn new(program: Vec<I>) -> Self {
+        Self {
+            program,
+            registers: Registers::default(),
+            program_counter: 0,
+            executed: 0,
+        }
+    }
+
+    pub fn program(&self) -> &[I] {
+        &self.program
+    }
+
+    pub fn registers(&self) -> &Registers<I::Register, I::Value> {
+        &self.registers
+    }
+
+    pub fn registers_mut(&mut self) -> &mut Registers<I::Register, I::Value> {
+        &mut self.registers
+    }
+
+    pub fn register(&self, register: I::Register) -> I::Value {
+        self.registers.get(register)
+    }
+
+    pub fn set_register(&mut self, register: I::Register, value: I::Value) {
+        self.registers.set(register, value);
+    }
+
+    /// The position of the next instruction to execute.
+    pub fn program_counter(&self) -> usize {
+        self.program_counter
+    }
+
+    /// How many instructions were executed so far.
+    pub fn executed(&self) -> u64 {
+        self.executed
+    }
+
+    /// Tells whether the program counter went out of the program. It's then
+    /// past the end of the program, even if it jumped before its start.
+    pub fn is_halted(&self) -> bool {
+        self.program_counter >= self.program.len()
+    }
+
+    /// Executes the next instruction, returning `false` without doing anything
+    /// if the machine is halted. Fails if the instruction jumps before the
+    /// start of the program, unless [`Instruction::HALTS_BEFORE_START`].
+    pub fn step(&mut self) -> anyhow::Result<bool> {
+        let Some(instruction) = self.program.get(self.program_counter) else {
+            return Ok(false);
+        };
+        let offset = instruction.execute(&mut self.registers);
+        self.executed += 1;
+        self.program_counter = match self.program_counter.checked_add_signed(offset) {
+            Some(program_counter) => program_counter,
+            None if offset > 0 || I::HALTS_BEFORE_START => self.program.len(),
+            None => anyhow::bail!(
+                "instruction {} jumps by {} before the start of the program",
+                self.program_counter,
+                offset
+            ),
+        };
+        Ok(true)
+    }
+
+    /// Runs the program until the machine halts.
+    pub fn run(&mut self) -> anyhow::Result<()> {
+        while self.step()? {}
+        Ok(())
+    }
+
+    /// Runs the program until `condition` holds before an instruction,
+    /// returning `true`, or until the machine halts, returning `false`.
+    pub fn run_until<F>(&mut self, mut condition: F) -> anyhow::Result<bool>
+    where
+        F: FnMut(&Self) -> bool,
+    {
+        while !self.is_halted() {
+            if condition(self) {
+                return Ok(true);
+            }
+            self.step()?;
+        }
+        Ok(false)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, Clone, Copy)]
+    enum Counter {
+        X,
+        Y,
+    }
+
+    impl Register for Counter {
+        const ALL: &'static [Self] = &[Counter::X, Counter::Y];
+
+        fn index(self) -> usize {
+            self as usize
+        }
+
+        fn name(self) -> &'static str {
+            match self {
+                Counter::X => "x",
+                Counter::Y => "y",
+            }
+        }
+    }
+
+    /// Adds to a register, or jumps unless a register is 0.
+    enum Op {
+        Add(Counter, i32),
+        JumpUnlessZero(Counter, isize),
+    }
+
+    impl Instruction for Op {
+        type Register = Counter;
+        type Value = i32;
+
+        fn execute(&self, registers: &mut Registers<Counter, i32>) -> isize {
+            match *self {
+                Op::Add(register, value) => {
+                    registers[register] += value;
+                    1
+                }
+                Op::JumpUnlessZero(register, offset) => {
+                    if registers[register] == 0 {
+                        1
+                    } else {
+                        offset
+                    }
+                }
+            }
+        }
+    }
+
+    /// Adds `x` to `y` 2 at a time, counting down `x`.
+    fn program() -> Vec<Op> {
+        vec![
+            Op::Add(Counter::Y, 2),
+            Op::Add(Counter::X, -1),
+            Op::JumpUnlessZero(Counter::X, -2),
+        ]
+    }
+
+    #[test]
+    fn test_run() {
+        let mut machine = Machine::new(program());
+        machine.set_register(Counter::X, 3);
+        machine.run().unwrap();
+        assert_eq!(machine.register(Counter::Y), 6);
+        assert_eq!(machine.executed(), 9);
+        assert!(machine.is_halted());
+        assert!(!machine.step().unwrap());
+
+        let mut machine = Machine::new(program());
+        machine.set_register(Counter::X, 3);
+        let stopped = machine.run_until(|machine| machine.registers()[Counter::Y] == 4);
+        assert!(stopped.unwrap());
+        assert_eq!((machine.program_counter(), machine.executed()), (1, 4));
+        assert!(!machine.run_until(|_| false).unwrap());
+        assert_eq!(machine.register(Counter::X), 0);
+    }
+
+    /// The same instructions, but jumping before the start halts.
+    struct Halting(Op);
+
+    impl Instruction for Halting {
+        type Register = Counter;
+        type Value = i32;
+
+        const HALTS_BEFORE_START: bool = true;
+
+        fn execute(&self, registers: &mut Registers<Counter, i32>) -> isize {
+            self.0.execute(registers)
+        }
+    }
+
+    #[test]
+    fn test_jump_before_start() {
+        let mut machine = Machine::new(vec![Op::JumpUnlessZero(Counter::X, -1)]);
+        machine.registers_mut().set(Counter::X, 1);
+        assert!(machine.run().is_err());
+        assert_eq!((machine.program_counter(), machine.executed()), (0, 1));
+
+        let program = vec![
+            Halting(Op::Add(Counter::X, 1)),
+            Halting(Op::JumpUnlessZero(Counter::X, -5)),
+        ];
+        let mut machine = Machine::new(program);
+        machine.run().unwrap();
+        assert!(machine.is_halted());
+        assert_eq!((machine.program_counter(), machine.executed()), (2, 2));
+    }
+
+    #[test]
+    fn test_parse() {
+        let line = Line::new(1, "add  x, -3 ");
+        let (mnemonic, operands) = split_instruction(&line);
+        assert_eq!((mnemonic, operands.as_slice()), ("add", &["x", "-3"][..]));
+
+        let register = parse_register::<Counter>(&line, operands[0]);
+        assert!(matches!(register, Ok(Counter::X)));
+        let operand = parse_operand::<Counter, i32>(&line, operands[1]).unwrap();
+        assert_eq!(operand.to_string(), "-3");
+        assert_eq!(operand.value(&Registers::default()), -3);
+
+        let operand = parse_operand::<Counter, i32>(&line, &line.text[5..6]).unwrap();
+        assert_eq!(operand.to_string(), "x");
+        let error = parse_register::<Counter>(&line, mnemonic).unwrap_err();
+        assert_eq!(error.expected, "a register, one of x, y");
+        let error = parse_operand::<Counter, i32>(&line, mnemonic).unwrap_err();
+        assert_eq!(
+            (error.column, error.expected.as_str()),
+            (1, "a register or a value")
+        );
+
+        let (mnemonic, operands) = split_instruction(&Line::new(1, ""));
+        assert_eq!((mnemonic, operands.len()), ("", 0));
+    }
+}
@@ -0,0 +1,65 @@
+use std::fmt::Display;
+use std::str::FromStr;
+
+use anyhow::Context;
+
+/// A parameter of a day, which replaces a constant of the puzzle so that it
+/// can be overridden with `--param name=value`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Param {
+    pub name: &'static str,
+    pub description: &'static str,
+    /// The current value, formatted like it is given on the command line.
+    pub value: String,
+}
+
+impl Param {
+    pub fn new(name: &'static str, description: &'static str, value: impl Display) -> Self {
+        Self {
+            name,
+            description,
+            value: value.to_string(),
+        }
+    }
+}
+
+/// Parses `value` given for the parameter `name`.
+pub fn parse_value<T>(name: &str, value: &str) -> anyhow::Result<T>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    value
+        .trim()
+        .parse()
+        .map_err(|error| anyhow::anyhow!("{}", error))
+        .with_context(|| format!("invalid value {:?} for parameter {}", value, name))
+}
+
+/// Parses a pair of values separated by a comma, e.g. `31,39`.
+pub fn parse_pair<T>(name: &str, value: &str) -> anyhow::Result<(T, T)>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    let (first, second) = value
+        .split_once(',')
+        .with_context(|| format!("expected two values like `1,2` for parameter {}", name))?;
+    Ok((parse_value(name, first)?, parse_value(name, second)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_value() {
+        assert_eq!(parse_value::<u32>("steps", "100").unwrap(), 100);
+        assert_eq!(
+            parse_value::<u32>("steps", "x").unwrap_err().to_string(),
+            "invalid value \"x\" for parameter steps"
+        );
+        assert_eq!(parse_pair::<usize>("target", "31,39").unwrap(), (31, 39));
+        assert!(parse_pair::<usize>("target", "31").is_err());
+    }
+}
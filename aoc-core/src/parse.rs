@@ -0,0 +1,205 @@
+use std::fmt;
+use std::str::FromStr;
+
+/// A malformed puzzle input, pointing at the offending text.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    /// The line of the input, starting at 1.
+    pub line: usize,
+    /// The column of the offending text in characters, starting at 1.
+    pub column: usize,
+    pub text: String,
+    /// What was expected instead, e.g. "a number" or "`#` or `.`".
+    pub expected: String,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: expected {}, ",
+            self.line, self.column, self.expected
+        )?;
+        if self.text.is_empty() {
+            write!(f, "found the end of the line")
+        } else {
+            write!(f, "found {:?}", self.text)
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// A line of the puzzle input along with its number, so that errors can point
+/// at it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Line<'a> {
+    /// The number of the line, starting at 1.
+    pub number: usize,
+    pub text: &'a str,
+}
+
+impl<'a> Line<'a> {
+    pub fn new(number: usize, text: &'a str) -> Self {
+        Self { number, text }
+    }
+
+    /// Reports that `part` isn't `expected`. The column is found from where
+    /// `part` is in the line, so it has to be a slice of [`Line::text`],
+    /// otherwise the error points at the start of the line.
+    pub fn error(&self, part: &str, expected: impl Into<String>) -> ParseError {
+        let start = self.text.as_ptr() as usize;
+        let offset = (part.as_ptr() as usize)
+            .checked_sub(start)
+            .filter(|offset| offset + part.len() <= self.text.len())
+            .filter(|offset| self.text.is_char_boundary(*offset))
+            .unwrap_or(0);
+        ParseError {
+            line: self.number,
+            column: self.text[..offset].chars().count() + 1,
+            text: part.to_string(),
+            expected: expected.into(),
+        }
+    }
+
+    /// Reports that the whole line isn't `expected`.
+    pub fn mismatch(&self, expected: impl Into<String>) -> ParseError {
+        self.error(self.text, expected)
+    }
+
+    /// Parses `part`, a slice of the line.
+    pub fn parse<T: FromStr>(&self, part: &str, expected: &str) -> Result<T, ParseError> {
+        part.parse().map_err(|_| self.error(part, expected))
+    }
+
+    /// Splits `part`, a slice of the line, at the first `delimiter`.
+    pub fn split_once(
+        &self,
+        part: &'a str,
+        delimiter: &str,
+        expected: &str,
+    ) -> Result<(&'a str, &'a str), ParseError> {
+        part.split_once(delimiter)
+            .ok_or_else(|| self.error(part, expected))
+    }
+
+    /// Removes `prefix` from the start of `part`, a slice of the line.
+    pub fn strip_prefix(
+        &self,
+        part: &'a str,
+        prefix: &str,
+        expected: &str,
+    ) -> Result<&'a str, ParseError> {
+        part.strip_prefix(prefix)
+            .ok_or_else(|| self.error(part, expected))
+    }
+}
+
+/// Reports that `input` ends where `expected` should follow, pointing at the
+/// line after its last one.
+pub fn end_of_input(input: &str, expected: impl Into<String>) -> ParseError {
+    ParseError {
+        line: input.lines().count() + 1,
+        column: 1,
+        text: String::new(),
+        expected: expected.into(),
+    }
+}
+
+/// Returns the numbered lines of `input`.
+pub fn lines(input: &str) -> impl Iterator<Item = Line<'_>> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(i, text)| Line::new(i + 1, text))
+}
+
+/// Parses every line of `input` with `parse`, stopping at the first error.
+pub fn parse_lines<'a, T, F>(input: &'a str, parse: F) -> Result<Vec<T>, ParseError>
+where
+    F: FnMut(Line<'a>) -> Result<T, ParseError>,
+{
+    lines(input).map(parse).collect()
+}
+
+/// Parses `text` with `parse` and, if it's valid, checks that formatting the
+/// result gives text that parses back to the same value. The fuzz targets call
+/// this with arbitrary text.
+pub fn check_round_trip<T, E, F>(text: &str, parse: F)
+where
+    T: fmt::Display + fmt::Debug + PartialEq,
+    F: Fn(&str) -> Result<T, E>,
+{
+    if let Ok(parsed) = parse(text) {
+        let formatted = parsed.to_string();
+        assert_eq!(
+            parse(&formatted).ok(),
+            Some(parsed),
+            "{:?} is formatted as {:?}",
+            text,
+            formatted
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_error() {
+        let line = Line::new(3, "Dancer flies 16 km/s");
+        assert_eq!(line.parse::<u32>(&line.text[13..15], "a speed"), Ok(16));
+
+        let error = line.parse::<u32>(&line.text[7..12], "a speed").unwrap_err();
+        assert_eq!((error.line, error.column), (3, 8));
+        assert_eq!(
+            error.to_string(),
+            "line 3, column 8: expected a speed, found \"flies\""
+        );
+
+        let error = line
+            .split_once(line.text, " for ", "a duration")
+            .unwrap_err();
+        assert_eq!(error.column, 1);
+
+        let error = line
+            .strip_prefix(line.text, "Comet", "`Comet`")
+            .unwrap_err();
+        assert_eq!(error.text, line.text);
+
+        let error = line.error(&line.text[line.text.len()..], "` for `");
+        assert_eq!(
+            error.to_string(),
+            "line 3, column 21: expected ` for `, found the end of the line"
+        );
+    }
+
+    #[test]
+    fn test_parse_lines() {
+        let numbers = parse_lines("1\n2\n3", |line| line.parse::<u8>(line.text, "a number"));
+        assert_eq!(numbers, Ok(vec![1, 2, 3]));
+
+        let error = parse_lines("1\n2\nx", |line| line.parse::<u8>(line.text, "a number"));
+        assert_eq!(error.unwrap_err().line, 3);
+
+        let error = end_of_input("1\n2\n", "a number");
+        assert_eq!(
+            error.to_string(),
+            "line 3, column 1: expected a number, found the end of the line"
+        );
+    }
+
+    #[test]
+    fn test_check_round_trip() {
+        check_round_trip("+12", str::parse::<u8>);
+        check_round_trip("x", str::parse::<u8>);
+    }
+
+    #[test]
+    #[should_panic(expected = "is formatted as")]
+    fn test_check_round_trip_mismatch() {
+        // Formats as "1" which parses back to 2.
+        check_round_trip("1", |text| text.parse::<u8>().map(|n| n + 1));
+    }
+}
@@ -0,0 +1,233 @@
+use std::fs::File;
+use std::io::BufWriter;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use anyhow::Context;
+
+/// A color given by its red, green and blue components.
+pub type Rgb = [u8; 3];
+
+pub const BLACK: Rgb = [0x0f, 0x0f, 0x23];
+pub const WHITE: Rgb = [0xff, 0xff, 0xff];
+pub const GRAY: Rgb = [0x66, 0x66, 0x66];
+pub const RED: Rgb = [0xe0, 0x30, 0x30];
+pub const GREEN: Rgb = [0x00, 0x99, 0x00];
+pub const YELLOW: Rgb = [0xff, 0xff, 0x66];
+
+/// Images are scaled up until their longer side is at least this many pixels,
+/// so that small grids remain visible.
+const MIN_SIZE: usize = 400;
+
+/// Returns a palette of 256 colors going from black to `color`.
+pub fn gradient(color: Rgb) -> Vec<Rgb> {
+    (0..=255u16)
+        .map(|level| {
+            let mix = |from: u8, to: u8| {
+                let (from, to) = (u16::from(from), u16::from(to));
+                ((from * (255 - level) + to * level) / 255) as u8
+            };
+            [
+                mix(BLACK[0], color[0]),
+                mix(BLACK[1], color[1]),
+                mix(BLACK[2], color[2]),
+            ]
+        })
+        .collect()
+}
+
+/// An image made of cells, each holding the index of its color in the
+/// palette.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Image {
+    width: usize,
+    height: usize,
+    palette: Vec<Rgb>,
+    cells: Vec<u8>,
+}
+
+impl Image {
+    /// Creates an image filled with the first color of `palette`.
+    pub fn new(width: usize, height: usize, palette: &[Rgb]) -> Self {
+        Self::from_fn(width, height, palette, |_, _| 0)
+    }
+
+    /// Creates an image with the color of each cell given by `color(x, y)`.
+    pub fn from_fn<F>(width: usize, height: usize, palette: &[Rgb], mut color: F) -> Self
+    where
+        F: FnMut(usize, usize) -> u8,
+    {
+        assert!(
+            !palette.is_empty() && palette.len() <= 256,
+            "a palette has 1 to 256 colors"
+        );
+        let cells = (0..height)
+            .flat_map(|y| (0..width).map(move |x| (x, y)))
+            .map(|(x, y)| color(x, y))
+            .collect();
+        Self {
+            width,
+            height,
+            palette: palette.to_vec(),
+            cells,
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Sets the cell at `x`, `y` to the color at index `color` of the palette.
+    pub fn set(&mut self, x: usize, y: usize, color: u8) {
+        assert!(x < self.width && y < self.height, "{},{} is outside", x, y);
+        self.cells[y * self.width + x] = color;
+    }
+
+    /// How many pixels wide and tall each cell is drawn.
+    fn scale(&self) -> usize {
+        let size = self.width.max(self.height).max(1);
+        MIN_SIZE.div_ceil(size)
+    }
+
+    /// Returns the palette indices of the pixels, row by row.
+    fn pixels(&self, scale: usize) -> Vec<u8> {
+        let mut pixels = Vec::with_capacity(self.cells.len() * scale * scale);
+        for row in self.cells.chunks(self.width.max(1)) {
+            for _ in 0..scale {
+                for cell in row {
+                    pixels.extend(std::iter::repeat_n(*cell, scale));
+                }
+            }
+        }
+        pixels
+    }
+
+    /// The palette as expected by the encoders, with the components of all
+    /// colors following each other.
+    fn flat_palette(&self) -> Vec<u8> {
+        self.palette.iter().flatten().copied().collect()
+    }
+}
+
+/// Writes images to a directory, with file names prefixed by the puzzle they
+/// visualize.
+#[derive(Debug)]
+pub struct Renderer {
+    dir: PathBuf,
+    prefix: String,
+    written: Vec<PathBuf>,
+}
+
+impl Renderer {
+    /// Creates a renderer writing the images of `day` of `year` to `dir`.
+    pub fn new(dir: &Path, year: u16, day: usize) -> Self {
+        Self {
+            dir: dir.join(year.to_string()),
+            prefix: format!("day{:02}", day),
+            written: vec![],
+        }
+    }
+
+    /// The files written so far.
+    pub fn written(&self) -> &[PathBuf] {
+        &self.written
+    }
+
+    /// Creates the file `name` with `extension`, e.g. `2015/day18-part1.gif`.
+    fn create(&mut self, name: &str, extension: &str) -> anyhow::Result<BufWriter<File>> {
+        std::fs::create_dir_all(&self.dir)
+            .with_context(|| format!("failed to create {}", self.dir.display()))?;
+        let path = self
+            .dir
+            .join(format!("{}-{}.{}", self.prefix, name, extension));
+        let file =
+            File::create(&path).with_context(|| format!("failed to create {}", path.display()))?;
+        self.written.push(path);
+        Ok(BufWriter::new(file))
+    }
+
+    /// Writes `image` as the PNG image `name`.
+    pub fn png(&mut self, name: &str, image: &Image) -> anyhow::Result<()> {
+        let scale = image.scale();
+        let mut encoder = png::Encoder::new(
+            self.create(name, "png")?,
+            (image.width * scale).try_into()?,
+            (image.height * scale).try_into()?,
+        );
+        encoder.set_color(png::ColorType::Indexed);
+        encoder.set_depth(png::BitDepth::Eight);
+        encoder.set_palette(image.flat_palette());
+        let mut writer = encoder.write_header()?;
+        writer.write_image_data(&image.pixels(scale))?;
+        writer.finish()?;
+        Ok(())
+    }
+
+    /// Writes `frames` as the animated GIF `name`, showing each frame for
+    /// `delay`. All frames have to have the size and palette of the first.
+    pub fn gif(&mut self, name: &str, frames: &[Image], delay: Duration) -> anyhow::Result<()> {
+        let first = frames.first().context("an animation needs frames")?;
+        anyhow::ensure!(
+            frames.iter().all(|frame| frame.width == first.width
+                && frame.height == first.height
+                && frame.palette == first.palette),
+            "the frames of {} differ in size or palette",
+            name
+        );
+        let scale = first.scale();
+        let width = (first.width * scale).try_into()?;
+        let height = (first.height * scale).try_into()?;
+        let mut encoder = gif::Encoder::new(
+            self.create(name, "gif")?,
+            width,
+            height,
+            &first.flat_palette(),
+        )?;
+        encoder.set_repeat(gif::Repeat::Infinite)?;
+        // GIF delays are given in hundredths of a second.
+        let delay = (delay.as_millis() / 10).try_into().unwrap_or(u16::MAX);
+        for frame in frames {
+            let frame = gif::Frame {
+                width,
+                height,
+                delay,
+                buffer: frame.pixels(scale).into(),
+                ..gif::Frame::default()
+            };
+            encoder.write_frame(&frame)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pixels() {
+        let image = Image::from_fn(2, 1, &[BLACK, WHITE], |x, _| x as u8);
+        assert_eq!(image.scale(), 200);
+        assert_eq!(image.pixels(2), vec![0, 0, 1, 1, 0, 0, 1, 1]);
+
+        let mut image = Image::new(1000, 2, &[BLACK, WHITE]);
+        image.set(999, 1, 1);
+        assert_eq!(image.scale(), 1);
+        assert_eq!(
+            image.pixels(1).iter().filter(|pixel| **pixel == 1).count(),
+            1
+        );
+    }
+
+    #[test]
+    fn test_gradient() {
+        let palette = gradient(WHITE);
+        assert_eq!(palette.len(), 256);
+        assert_eq!(palette[0], BLACK);
+        assert_eq!(palette[255], WHITE);
+    }
+}
@@ -0,0 +1,107 @@
+use std::time::Duration;
+
+use json::{object, JsonValue};
+
+use crate::{Answer, Answers, Timings};
+
+/// Converts an answer into a JSON number or string.
+pub fn answer(answer: &Answer) -> JsonValue {
+    match answer {
+        Answer::Integer(n) => (*n).into(),
+        Answer::Text(text) => text.as_str().into(),
+    }
+}
+
+/// Describes the answer to `part` as an object holding either the `answer` or
+/// the `error`.
+pub fn part(part: u8, result: Result<&Answer, String>) -> JsonValue {
+    match result {
+        Ok(value) => object! { "part" => part, "answer" => answer(value) },
+        Err(error) => object! { "part" => part, "error" => error },
+    }
+}
+
+/// Converts `duration` into whole microseconds.
+pub fn microseconds(duration: Duration) -> u64 {
+    duration.as_micros().try_into().unwrap_or(u64::MAX)
+}
+
+/// Describes the timings in whole microseconds.
+pub fn timings(timings: &Timings) -> JsonValue {
+    object! {
+        "parse_us" => microseconds(timings.parse),
+        "part1_us" => microseconds(timings.part1),
+        "part2_us" => microseconds(timings.part2),
+    }
+}
+
+/// Describes the result of running `day`, with an entry for each solved part.
+/// A day failing as a whole only has an `error`.
+pub fn day(day: usize, answers: &anyhow::Result<Answers>) -> JsonValue {
+    match answers {
+        Ok(answers) => {
+            let parts = [(1, &answers.part1), (2, &answers.part2)]
+                .into_iter()
+                .filter_map(|(number, answer)| {
+                    let answer = answer.as_ref()?;
+                    Some(part(
+                        number,
+                        answer.as_ref().map_err(|error| format!("{:#}", error)),
+                    ))
+                })
+                .collect::<Vec<_>>();
+            object! {
+                "day" => day,
+                "parts" => parts,
+                "timings" => timings(&answers.timings),
+            }
+        }
+        Err(error) => object! { "day" => day, "error" => format!("{:#}", error) },
+    }
+}
+
+/// Describes the results of running `days` of `year`.
+pub fn year(year: u16, days: Vec<JsonValue>, missing_inputs: &[usize]) -> JsonValue {
+    object! {
+        "year" => year,
+        "days" => days,
+        "missing_inputs" => missing_inputs.to_vec(),
+    }
+}
+
+/// The document describing a run over the given `years`.
+pub fn document(years: Vec<JsonValue>) -> JsonValue {
+    object! { "years" => years }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_day() {
+        let answers = Ok(Answers {
+            part1: Some(Ok(Answer::Integer(42))),
+            part2: Some(Err(anyhow::anyhow!("oops"))),
+            timings: Timings::default(),
+        });
+        let value = day(7, &answers);
+        assert_eq!(value["day"], 7);
+        assert_eq!(value["parts"][0]["answer"], 42);
+        assert_eq!(value["parts"][1]["error"], "oops");
+        assert_eq!(value["timings"]["parse_us"], 0);
+
+        let answers = Ok(Answers {
+            part1: None,
+            part2: Some(Ok(Answer::Integer(7))),
+            timings: Timings::default(),
+        });
+        let value = day(7, &answers);
+        assert_eq!(value["parts"].len(), 1);
+        assert_eq!(value["parts"][0]["part"], 2);
+
+        let value = day(7, &Err(anyhow::anyhow!("no input")));
+        assert_eq!(value["error"], "no input");
+        assert!(value["parts"].is_null());
+    }
+}
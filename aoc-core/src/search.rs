@@ -0,0 +1,274 @@
+use std::cmp::Reverse;
+use std::collections::hash_map::Entry;
+use std::collections::{BinaryHeap, HashMap};
+use std::hash::Hash;
+use std::ops::Add;
+
+/// How much work a search did.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Stats {
+    /// How many states were taken from the frontier and expanded.
+    pub expanded: usize,
+    /// How many distinct states were found, the start included.
+    pub discovered: usize,
+    /// The most states waiting in the frontier at once.
+    pub max_frontier: usize,
+}
+
+/// A path from the start of a search to a goal.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Path<S, C> {
+    /// The states along the path, from the start to the goal.
+    pub states: Vec<S>,
+    /// The sum of the costs of the steps.
+    pub cost: C,
+}
+
+impl<S, C> Path<S, C> {
+    /// How many steps the path takes.
+    pub fn steps(&self) -> usize {
+        self.states.len() - 1
+    }
+
+    pub fn goal(&self) -> &S {
+        self.states.last().expect("a path has at least the start")
+    }
+
+    pub fn into_goal(mut self) -> S {
+        self.states.pop().expect("a path has at least the start")
+    }
+}
+
+/// What a search found, if anything, and how much work it did.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SearchResult<S, C> {
+    pub path: Option<Path<S, C>>,
+    pub stats: Stats,
+}
+
+/// Searches for the path with the fewest steps to a goal.
+pub fn bfs<S, I, FS, FG>(start: S, mut successors: FS, is_goal: FG) -> SearchResult<S, usize>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = S>,
+    FS: FnMut(&S) -> I,
+    FG: FnMut(&S) -> bool,
+{
+    let successors = move |state: &S| successors(state).into_iter().map(|next| (next, 1));
+    search(start, successors, is_goal, |_, cost| cost)
+}
+
+/// Searches for the cheapest path to a goal, with `successors` returning the
+/// next states along with the cost of getting there.
+pub fn dijkstra<S, C, I, FS, FG>(start: S, successors: FS, is_goal: FG) -> SearchResult<S, C>
+where
+    S: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (S, C)>,
+    FS: FnMut(&S) -> I,
+    FG: FnMut(&S) -> bool,
+{
+    search(start, successors, is_goal, |_, cost| cost)
+}
+
+/// Searches for the cheapest path to a goal like [`dijkstra`], exploring the
+/// states `heuristic` estimates to be closer to a goal first.
+///
+/// Expanded states are never reopened, so the path is only guaranteed to be
+/// the cheapest if the heuristic is consistent: it is zero at the goals and
+/// never drops by more than the cost of a step. A heuristic that merely never
+/// overestimates the remaining cost isn't enough.
+pub fn astar<S, C, I, FS, FH, FG>(
+    start: S,
+    successors: FS,
+    mut heuristic: FH,
+    is_goal: FG,
+) -> SearchResult<S, C>
+where
+    S: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (S, C)>,
+    FS: FnMut(&S) -> I,
+    FH: FnMut(&S) -> C,
+    FG: FnMut(&S) -> bool,
+{
+    search(start, successors, is_goal, |state, cost| {
+        cost + heuristic(state)
+    })
+}
+
+/// Searches for a path to a goal, always exploring the state `heuristic`
+/// ranks best first and the cheapest of equally ranked states. It finds a path
+/// quickly when the heuristic is good, but not necessarily the cheapest one.
+pub fn best_first<S, C, H, I, FS, FH, FG>(
+    start: S,
+    successors: FS,
+    mut heuristic: FH,
+    is_goal: FG,
+) -> SearchResult<S, C>
+where
+    S: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    H: Ord,
+    I: IntoIterator<Item = (S, C)>,
+    FS: FnMut(&S) -> I,
+    FH: FnMut(&S) -> H,
+    FG: FnMut(&S) -> bool,
+{
+    search(start, successors, is_goal, |state, cost| {
+        (heuristic(state), cost)
+    })
+}
+
+/// A state found by a search, along with how it was reached most cheaply so
+/// far.
+struct Node<S, C> {
+    state: S,
+    parent: Option<usize>,
+    cost: C,
+    expanded: bool,
+}
+
+/// Explores the states in the order of `priority`, given each state and the
+/// cost of reaching it, the lowest first. Equal priorities are explored in the
+/// order they were found. A state is expanded at most once, even if a cheaper
+/// path to it is found afterwards.
+fn search<S, C, P, I, FS, FG, FP>(
+    start: S,
+    mut successors: FS,
+    mut is_goal: FG,
+    mut priority: FP,
+) -> SearchResult<S, C>
+where
+    S: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    P: Ord,
+    I: IntoIterator<Item = (S, C)>,
+    FS: FnMut(&S) -> I,
+    FG: FnMut(&S) -> bool,
+    FP: FnMut(&S, C) -> P,
+{
+    let mut stats = Stats::default();
+    let mut indices = HashMap::from([(start.clone(), 0)]);
+    let mut frontier = BinaryHeap::from([Reverse((priority(&start, C::default()), 0, 0))]);
+    let mut nodes = vec![Node {
+        state: start,
+        parent: None,
+        cost: C::default(),
+        expanded: false,
+    }];
+    // Breaks ties between equal priorities, so that they leave the frontier in
+    // the order they entered it.
+    let mut order = 1;
+
+    while let Some(Reverse((_, _, index))) = frontier.pop() {
+        if nodes[index].expanded {
+            // Reached more cheaply after this entry was added.
+            continue;
+        }
+        nodes[index].expanded = true;
+        stats.expanded += 1;
+
+        if is_goal(&nodes[index].state) {
+            stats.discovered = nodes.len();
+            let cost = nodes[index].cost;
+            let mut states = vec![];
+            let mut current = Some(index);
+            while let Some(index) = current {
+                states.push(nodes[index].state.clone());
+                current = nodes[index].parent;
+            }
+            states.reverse();
+            let path = Some(Path { states, cost });
+            return SearchResult { path, stats };
+        }
+
+        for (next, step_cost) in successors(&nodes[index].state) {
+            let cost = nodes[index].cost + step_cost;
+            let next_index = match indices.entry(next) {
+                Entry::Occupied(entry) => {
+                    let node = &mut nodes[*entry.get()];
+                    if node.expanded || node.cost <= cost {
+                        continue;
+                    }
+                    node.parent = Some(index);
+                    node.cost = cost;
+                    *entry.get()
+                }
+                Entry::Vacant(entry) => {
+                    let next_index = nodes.len();
+                    nodes.push(Node {
+                        state: entry.key().clone(),
+                        parent: Some(index),
+                        cost,
+                        expanded: false,
+                    });
+                    entry.insert(next_index);
+                    next_index
+                }
+            };
+            let next_priority = priority(&nodes[next_index].state, cost);
+            frontier.push(Reverse((next_priority, order, next_index)));
+            order += 1;
+        }
+        stats.max_frontier = stats.max_frontier.max(frontier.len());
+    }
+
+    stats.discovered = nodes.len();
+    SearchResult { path: None, stats }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The numbers reachable by adding 1 or doubling, up to 100.
+    fn successors(n: &u32) -> Vec<u32> {
+        [n + 1, n * 2].into_iter().filter(|n| *n <= 100).collect()
+    }
+
+    #[test]
+    fn test_bfs() {
+        let result = bfs(1, successors, |n| *n == 10);
+        let path = result.path.unwrap();
+        assert_eq!(path.states, vec![1, 2, 4, 5, 10]);
+        assert_eq!((path.steps(), path.cost), (4, 4));
+        assert!(result.stats.expanded <= result.stats.discovered);
+
+        let result = bfs(1, successors, |n| *n == 101);
+        assert_eq!(result.path, None);
+        assert_eq!(result.stats.discovered, 100);
+        assert_eq!(result.stats.expanded, 100);
+    }
+
+    #[test]
+    fn test_dijkstra() {
+        // Doubling costs 3, so adding is cheaper for small numbers.
+        let weighted = |n: &u32| {
+            let mut next = vec![(n + 1, 1)];
+            if n * 2 <= 100 {
+                next.push((n * 2, 3));
+            }
+            next
+        };
+        let path = dijkstra(1, weighted, |n| *n == 10).path.unwrap();
+        assert_eq!(path.states, vec![1, 2, 3, 4, 5, 10]);
+        assert_eq!(path.cost, 7);
+
+        // Every step costs at least 1 and this drops by at most 1, so it is
+        // consistent.
+        let at_least_one = |n: &u32| u32::from(*n != 10);
+        let path = astar(1, weighted, at_least_one, |n| *n == 10).path.unwrap();
+        assert_eq!(path.cost, 7);
+    }
+
+    #[test]
+    fn test_best_first() {
+        // Heading straight for the goal explores fewer states than BFS.
+        let steps = |n: &u32| successors(n).into_iter().map(|n| (n, 1));
+        let result = best_first(1, steps, |n: &u32| 64u32.abs_diff(*n), |n| *n == 64);
+        assert_eq!(result.path.unwrap().into_goal(), 64);
+        let expanded = bfs(1, successors, |n| *n == 64).stats.expanded;
+        assert!(result.stats.expanded < expanded);
+    }
+}
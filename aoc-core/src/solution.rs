@@ -0,0 +1,191 @@
+use std::time::{Duration, Instant};
+
+use anyhow::Context;
+
+use crate::render::Renderer;
+use crate::{Answer, Param};
+
+/// A solver for the puzzle of a single day.
+///
+/// The input is parsed once and then shared by both parts.
+pub trait Solution {
+    /// The parsed puzzle input.
+    type Input;
+
+    fn parse(&self, input: &str) -> anyhow::Result<Self::Input>;
+
+    fn part1(&self, input: &Self::Input) -> anyhow::Result<Answer>;
+
+    fn part2(&self, input: &Self::Input) -> anyhow::Result<Answer>;
+
+    /// The parameters of the day with their current values, which default to
+    /// the constants given by the puzzle.
+    fn params(&self) -> Vec<Param> {
+        vec![]
+    }
+
+    /// Sets the parameter `name`, one of [`Solution::params`], to `value`.
+    fn set_param(&mut self, name: &str, _value: &str) -> anyhow::Result<()> {
+        anyhow::bail!("unknown parameter {}", name)
+    }
+
+    /// Draws how the input is solved as images written with `renderer`, for
+    /// days that are worth looking at.
+    fn render(&self, _input: &Self::Input, _renderer: &mut Renderer) -> anyhow::Result<()> {
+        anyhow::bail!("this day has no visualization")
+    }
+}
+
+/// Parses `input` and solves `part` of it with `solver`, e.g. to test a day
+/// on the examples of the puzzle.
+pub fn solve_part<S: Solution>(solver: &S, input: &str, part: u8) -> anyhow::Result<Answer> {
+    let input = solver.parse(input)?;
+    match part {
+        1 => solver.part1(&input),
+        2 => solver.part2(&input),
+        _ => anyhow::bail!("there is no part {}", part),
+    }
+}
+
+/// How long each step of solving a puzzle took.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Timings {
+    pub parse: Duration,
+    pub part1: Duration,
+    pub part2: Duration,
+}
+
+impl Timings {
+    /// The time spent on solving both parts, excluding parsing.
+    pub fn solve(&self) -> Duration {
+        self.part1 + self.part2
+    }
+}
+
+/// Which parts of a puzzle to solve.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Parts {
+    One,
+    Two,
+    #[default]
+    Both,
+}
+
+impl Parts {
+    /// Whether `part`, either 1 or 2, is to be solved.
+    pub fn includes(self, part: u8) -> bool {
+        matches!(
+            (self, part),
+            (Parts::One, 1) | (Parts::Two, 2) | (Parts::Both, 1 | 2)
+        )
+    }
+}
+
+/// The answers to the solved parts of a puzzle, parts that weren't asked for
+/// are `None`. Each part may fail independently.
+#[derive(Debug)]
+pub struct Answers {
+    pub part1: Option<anyhow::Result<Answer>>,
+    pub part2: Option<anyhow::Result<Answer>>,
+    pub timings: Timings,
+}
+
+impl Answers {
+    /// Whether all solved parts succeeded.
+    pub fn is_ok(&self) -> bool {
+        [&self.part1, &self.part2]
+            .into_iter()
+            .flatten()
+            .all(Result::is_ok)
+    }
+}
+
+/// Object safe counterpart to [`Solution`], which allows days with different
+/// input types to be used interchangeably.
+pub trait Puzzle {
+    /// Parses `input` and solves the selected `parts`.
+    fn solve(&self, input: &str, parts: Parts) -> anyhow::Result<Answers>;
+
+    /// See [`Solution::params`].
+    fn params(&self) -> Vec<Param>;
+
+    /// See [`Solution::set_param`].
+    fn set_param(&mut self, name: &str, value: &str) -> anyhow::Result<()>;
+
+    /// Parses `input` and draws it, see [`Solution::render`].
+    fn render(&self, input: &str, renderer: &mut Renderer) -> anyhow::Result<()>;
+}
+
+/// Calls `f` and returns its result along with the time it took.
+fn timed<T, F: FnOnce() -> T>(f: F) -> (T, Duration) {
+    let start = Instant::now();
+    let result = f();
+    (result, start.elapsed())
+}
+
+impl<S: Solution> Puzzle for S {
+    fn solve(&self, input: &str, parts: Parts) -> anyhow::Result<Answers> {
+        let (input, parse) = timed(|| self.parse(input));
+        let input = input?;
+        let (part1, part1_time) = if parts.includes(1) {
+            let (answer, time) = timed(|| self.part1(&input));
+            (Some(answer), time)
+        } else {
+            (None, Duration::ZERO)
+        };
+        let (part2, part2_time) = if parts.includes(2) {
+            let (answer, time) = timed(|| self.part2(&input));
+            (Some(answer), time)
+        } else {
+            (None, Duration::ZERO)
+        };
+        Ok(Answers {
+            part1,
+            part2,
+            timings: Timings {
+                parse,
+                part1: part1_time,
+                part2: part2_time,
+            },
+        })
+    }
+
+    fn params(&self) -> Vec<Param> {
+        Solution::params(self)
+    }
+
+    fn set_param(&mut self, name: &str, value: &str) -> anyhow::Result<()> {
+        Solution::set_param(self, name, value)
+    }
+
+    fn render(&self, input: &str, renderer: &mut Renderer) -> anyhow::Result<()> {
+        let input = self.parse(input)?;
+        Solution::render(self, &input, renderer)
+    }
+}
+
+/// The solvers of all days of a year.
+#[derive(Clone, Copy)]
+pub struct Year {
+    pub year: u16,
+    /// Creates the solver of each day with default parameters, the first
+    /// element of which is day 1.
+    pub days: &'static [fn() -> Box<dyn Puzzle>],
+}
+
+impl Year {
+    /// Creates the solver for `day`.
+    pub fn puzzle(&self, day: usize) -> anyhow::Result<Box<dyn Puzzle>> {
+        day.checked_sub(1)
+            .and_then(|index| self.days.get(index))
+            .map(|create| create())
+            .with_context(|| {
+                format!(
+                    "day{:02} of {} is not available, choose one of day01 to day{:02}",
+                    day,
+                    self.year,
+                    self.days.len()
+                )
+            })
+    }
+}
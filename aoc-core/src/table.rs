@@ -0,0 +1,78 @@
+use std::fmt;
+
+/// A plain text table whose columns are as wide as their widest cell.
+#[derive(Debug)]
+pub struct Table {
+    header: Vec<String>,
+    rows: Vec<Vec<String>>,
+}
+
+impl Table {
+    pub fn new<S: ToString>(header: &[S]) -> Self {
+        Self {
+            header: header.iter().map(|cell| cell.to_string()).collect(),
+            rows: vec![],
+        }
+    }
+
+    /// Adds a row, which is expected to have as many cells as the header.
+    pub fn push(&mut self, row: Vec<String>) {
+        debug_assert_eq!(row.len(), self.header.len());
+        self.rows.push(row);
+    }
+
+    fn column_widths(&self) -> Vec<usize> {
+        let mut widths: Vec<usize> = self
+            .header
+            .iter()
+            .map(|cell| cell.chars().count())
+            .collect();
+        for row in &self.rows {
+            for (width, cell) in widths.iter_mut().zip(row) {
+                *width = (*width).max(cell.chars().count());
+            }
+        }
+        widths
+    }
+}
+
+impl fmt::Display for Table {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let widths = self.column_widths();
+        let write_row = |f: &mut fmt::Formatter<'_>, row: &[String]| {
+            let cells: Vec<String> = row
+                .iter()
+                .zip(&widths)
+                .map(|(cell, width)| format!("{:width$}", cell, width = width))
+                .collect();
+            writeln!(f, "{}", cells.join(" | ").trim_end())
+        };
+
+        write_row(f, &self.header)?;
+        let separator: Vec<String> = widths.iter().map(|width| "-".repeat(*width)).collect();
+        writeln!(f, "{}", separator.join("-+-"))?;
+        for row in &self.rows {
+            write_row(f, row)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display() {
+        let mut table = Table::new(&["day", "answer"]);
+        table.push(vec!["day01".to_string(), "1".to_string()]);
+        table.push(vec!["day02".to_string(), "12345678".to_string()]);
+        assert_eq!(
+            table.to_string(),
+            "day   | answer\n\
+             ------+---------\n\
+             day01 | 1\n\
+             day02 | 12345678\n"
+        );
+    }
+}
@@ -0,0 +1,87 @@
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+/// What is compared to tell whether a file changed: its modification time and
+/// size, `None` while the file doesn't exist.
+type Stamp = Option<(SystemTime, u64)>;
+
+fn stamp(path: &Path) -> Stamp {
+    let metadata = std::fs::metadata(path).ok()?;
+    Some((metadata.modified().ok()?, metadata.len()))
+}
+
+/// Detects changes to files by polling them, which works the same on every
+/// platform and for editors replacing files instead of writing to them.
+#[derive(Debug)]
+pub struct Watcher {
+    files: Vec<(PathBuf, Stamp)>,
+}
+
+impl Watcher {
+    /// Watches the files at `paths`, which don't have to exist yet.
+    pub fn new(paths: &[&Path]) -> Self {
+        let files = paths
+            .iter()
+            .map(|path| (path.to_path_buf(), stamp(path)))
+            .collect();
+        Self { files }
+    }
+
+    /// Returns the files modified, created or deleted since the last call, or
+    /// since the watcher was created.
+    pub fn changed(&mut self) -> Vec<PathBuf> {
+        let mut changed = vec![];
+        for (path, last) in &mut self.files {
+            let current = stamp(path);
+            if current != *last {
+                *last = current;
+                changed.push(path.clone());
+            }
+        }
+        changed
+    }
+
+    /// Blocks until files change, checking every `interval`, and returns them.
+    /// Waits for the files to stay unchanged for an `interval`, so that an
+    /// editor saving in several steps causes a single change.
+    pub fn wait(&mut self, interval: Duration) -> Vec<PathBuf> {
+        let mut changed = vec![];
+        loop {
+            std::thread::sleep(interval);
+            let more = self.changed();
+            if more.is_empty() && !changed.is_empty() {
+                return changed;
+            }
+            for path in more {
+                if !changed.contains(&path) {
+                    changed.push(path);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_changed() {
+        let path = std::env::temp_dir().join(format!("aoc-watch-{}.input", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+
+        let mut watcher = Watcher::new(&[&path]);
+        assert!(watcher.changed().is_empty());
+
+        std::fs::write(&path, "1").unwrap();
+        assert_eq!(watcher.changed(), vec![path.clone()]);
+        assert!(watcher.changed().is_empty());
+
+        std::fs::write(&path, "1\n2").unwrap();
+        assert_eq!(watcher.changed(), vec![path.clone()]);
+
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(watcher.changed(), vec![path.clone()]);
+        assert!(watcher.changed().is_empty());
+    }
+}
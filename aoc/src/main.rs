@@ -0,0 +1,22 @@
+extern crate anyhow;
+extern crate aoc_2015;
+extern crate aoc_2016;
+extern crate aoc_core;
+
+use aoc_core::Year;
+
+/// Every year with solvers, a new year only has to be added here.
+const YEARS: &[Year] = &[
+    Year {
+        year: 2015,
+        days: aoc_2015::DAYS,
+    },
+    Year {
+        year: 2016,
+        days: aoc_2016::DAYS,
+    },
+];
+
+fn main() -> anyhow::Result<()> {
+    aoc_core::cli::main(YEARS)
+}